@@ -81,8 +81,19 @@ internal static class EntryPoint
         return null;
     }
 
+    // Set by a running bepinex_gui when it restarts the game, so that it keeps showing its logs
+    private const string AttachFileEnvironmentVariable = "BEPINEX_GUI_ATTACH_FILE";
+    private const string GUIProcessIdEnvironmentVariable = "BEPINEX_GUI_PROCESS_ID";
+
     private static void FindAndLaunchGUI()
     {
+        var attachFilePath = Environment.GetEnvironmentVariable(AttachFileEnvironmentVariable);
+        if (!string.IsNullOrEmpty(attachFilePath))
+        {
+            AttachToRunningGUI(attachFilePath);
+            return;
+        }
+
         Log.Info("Finding and launching GUI");
 
         var executablePath = FindGUIExecutable();
@@ -94,6 +105,7 @@ internal static class EntryPoint
             {
                 Logger.Listeners.Add(new SendLogToClientSocket(freePort));
                 Logger.Listeners.Add(new CloseProcessOnChainloaderDone(process));
+                GameDiscoveryFile.Write(GetGUIArguments(freePort));
             }
             else
             {
@@ -106,6 +118,46 @@ internal static class EntryPoint
         }
     }
 
+    private static void AttachToRunningGUI(string attachFilePath)
+    {
+        Log.Info("Attaching to the running GUI");
+
+        // Child processes of the game shouldn't attach too
+        Environment.SetEnvironmentVariable(AttachFileEnvironmentVariable, null);
+
+        Process guiProcess = null;
+        if (int.TryParse(Environment.GetEnvironmentVariable(GUIProcessIdEnvironmentVariable), out var guiProcessId))
+        {
+            Environment.SetEnvironmentVariable(GUIProcessIdEnvironmentVariable, null);
+
+            try
+            {
+                guiProcess = Process.GetProcessById(guiProcessId);
+            }
+            catch (Exception e)
+            {
+                Log.Error($"Running GUI process not found: {e.Message}");
+            }
+        }
+
+        var freePort = FindFreePort();
+        Logger.Listeners.Add(new SendLogToClientSocket(freePort));
+        if (guiProcess != null)
+        {
+            Logger.Listeners.Add(new CloseProcessOnChainloaderDone(guiProcess));
+        }
+        GameDiscoveryFile.Write(GetGUIArguments(freePort));
+
+        // Written in one go so that the GUI never reads half of it
+        var tempAttachFilePath = attachFilePath + ".tmp";
+        File.WriteAllText(tempAttachFilePath, $"{Process.GetCurrentProcess().Id}\n{freePort}");
+        if (File.Exists(attachFilePath))
+        {
+            File.Delete(attachFilePath);
+        }
+        File.Move(tempAttachFilePath, attachFilePath);
+    }
+
     private static int FindFreePort()
     {
         int port = 0;
@@ -131,18 +183,22 @@ internal static class EntryPoint
         processStartInfo.FileName = executablePath;
         processStartInfo.WorkingDirectory = Path.GetDirectoryName(executablePath);
 
-        processStartInfo.Arguments =
-            $"\"{typeof(Paths).Assembly.GetName().Version}\" " +
-            $"\"{Paths.ProcessName}\" " +
-            $"\"{Paths.GameRootPath}\" " +
-            $"\"{GetLogOutputFilePath()}\" " +
-            $"\"{Config.ConfigFilePath}\" " +
-            $"\"{Process.GetCurrentProcess().Id}\" " +
-            $"\"{socketPort}\"";
+        processStartInfo.Arguments = string.Join(" ", Array.ConvertAll(GetGUIArguments(socketPort), argument => $"\"{argument}\""));
 
         return Process.Start(processStartInfo);
     }
 
+    private static string[] GetGUIArguments(int socketPort) => new[]
+    {
+        typeof(Paths).Assembly.GetName().Version.ToString(),
+        Paths.ProcessName,
+        Paths.GameRootPath,
+        GetLogOutputFilePath(),
+        Config.ConfigFilePath,
+        Process.GetCurrentProcess().Id.ToString(),
+        socketPort.ToString(),
+    };
+
     // Bad and hacky way to retrieve the correct log file path
     private static string GetLogOutputFilePath()
     {
@@ -0,0 +1,59 @@
+﻿using System.Diagnostics;
+using System.IO;
+
+namespace BepInEx.GUI.Loader;
+
+// Lets a bepinex_gui started by hand find the games it can attach to,
+// each line is one of the arguments the GUI is normally launched with
+internal static class GameDiscoveryFile
+{
+    private static string _filePath;
+
+    private static string GetFolderPath() => Path.Combine(Path.GetTempPath(), "bepinex_gui", "games");
+
+    internal static void Write(string[] guiArguments)
+    {
+        try
+        {
+            var folderPath = GetFolderPath();
+            Directory.CreateDirectory(folderPath);
+
+            _filePath = Path.Combine(folderPath, $"{Process.GetCurrentProcess().Id}.txt");
+
+            // Written in one go so that the GUI never reads half of it
+            var tempFilePath = _filePath + ".tmp";
+            File.WriteAllLines(tempFilePath, guiArguments);
+            if (File.Exists(_filePath))
+            {
+                File.Delete(_filePath);
+            }
+            File.Move(tempFilePath, _filePath);
+
+            AppDomain.CurrentDomain.ProcessExit += (_, _) => Delete();
+        }
+        catch (Exception e)
+        {
+            Log.Error($"Failed writing the game discovery file: {e.Message}");
+        }
+    }
+
+    // Once the log socket is closed there is nothing left to attach to
+    internal static void Delete()
+    {
+        if (_filePath == null)
+        {
+            return;
+        }
+
+        try
+        {
+            File.Delete(_filePath);
+        }
+        catch (Exception e)
+        {
+            Log.Error($"Failed deleting the game discovery file: {e.Message}");
+        }
+
+        _filePath = null;
+    }
+}
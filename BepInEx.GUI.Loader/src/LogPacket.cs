@@ -5,31 +5,66 @@ namespace BepInEx.GUI.Loader;
 
 internal unsafe struct LogPacket
 {
-    internal byte[] Bytes;
+    // Must match bepinex_gui packet_protocol.rs
+    internal const UInt32 HandshakeMarker = UInt32.MaxValue;
 
+    internal const UInt32 LegacyProtocolVersion = 0;
+    internal const UInt32 TimestampProtocolVersion = 1;
+
+    internal const UInt32 ProtocolVersion = TimestampProtocolVersion;
+
+    private readonly byte[] _logStringByteArray;
+    private readonly LogLevel _logLevel;
+    private readonly Int64 _unixTimestampMs;
+
+    internal LogPacket(LogEventArgs log)
+    {
+        _logStringByteArray = Encoding.UTF8.GetBytes(log.ToString());
+
+        _logLevel = log.Level;
+
+        // Taken when the log is emitted and not when it's sent,
+        // logs are queued until the GUI connects to the socket
+        _unixTimestampMs = DateTimeOffset.UtcNow.ToUnixTimeMilliseconds();
+    }
+
+    // Legacy Protocol
     // Field                        - Offset
     // Log String Byte Array Length - 0x0000
     // Log Level                    - 0x0004
     // Log String Byte Array        - 0x0008
 
-    internal unsafe LogPacket(LogEventArgs log)
-    {
-        var logStringByteArray = Encoding.UTF8.GetBytes(log.ToString());
+    // Timestamp Protocol
+    // Field                        - Offset
+    // Log String Byte Array Length - 0x0000
+    // Log Level                    - 0x0004
+    // Unix Timestamp (ms)          - 0x0008
+    // Log String Byte Array        - 0x0010
 
-        var payloadSize = logStringByteArray.Length;
+    internal byte[] GetBytes(UInt32 protocolVersion)
+    {
+        var payloadSize = _logStringByteArray.Length;
 
         const Int32 SizeOfLengthPrefix = sizeof(UInt32);
         const Int32 SizeOfLogLevel = sizeof(Int32);
+        var sizeOfTimestamp = protocolVersion >= TimestampProtocolVersion ? sizeof(Int64) : 0;
 
-        Bytes = new byte[SizeOfLengthPrefix + SizeOfLogLevel + payloadSize];
+        var bytes = new byte[SizeOfLengthPrefix + SizeOfLogLevel + sizeOfTimestamp + payloadSize];
 
-        fixed (byte* byteArrayPtr = Bytes)
+        fixed (byte* byteArrayPtr = bytes)
         {
             *(UInt32*)byteArrayPtr = (UInt32)payloadSize;
 
-            *(Int32*)(&byteArrayPtr[SizeOfLengthPrefix]) = (Int32)log.Level;
+            *(Int32*)(&byteArrayPtr[SizeOfLengthPrefix]) = (Int32)_logLevel;
+
+            if (sizeOfTimestamp != 0)
+            {
+                *(Int64*)(&byteArrayPtr[SizeOfLengthPrefix + SizeOfLogLevel]) = _unixTimestampMs;
+            }
 
-            Marshal.Copy(logStringByteArray, 0, (IntPtr)(&byteArrayPtr[SizeOfLengthPrefix + SizeOfLogLevel]), payloadSize);
+            Marshal.Copy(_logStringByteArray, 0, (IntPtr)(&byteArrayPtr[SizeOfLengthPrefix + SizeOfLogLevel + sizeOfTimestamp]), payloadSize);
         }
+
+        return bytes;
     }
 }
@@ -12,7 +12,7 @@ internal class SendLogToClientSocket : ILogListener
     private readonly Thread _thread;
 
     private readonly object _queueLock = new();
-    private readonly Queue<LogEventArgs> _logQueue = new();
+    private readonly Queue<LogPacket> _logQueue = new();
 
     private bool _isDisposed = false;
 
@@ -49,8 +49,65 @@ internal class SendLogToClientSocket : ILogListener
         _thread.Start();
     }
 
+    // Older GUIs never send the handshake, they get the legacy packets after the timeout
+    private static UInt32 ReceiveHandshake(Socket clientSocket)
+    {
+        const int HandshakeTimeoutMs = 1000;
+
+        var handshakeBytes = new byte[2 * sizeof(UInt32)];
+        var receivedByteCount = 0;
+
+        clientSocket.ReceiveTimeout = HandshakeTimeoutMs;
+        try
+        {
+            while (receivedByteCount < handshakeBytes.Length)
+            {
+                var byteCount = clientSocket.Receive(handshakeBytes, receivedByteCount, handshakeBytes.Length - receivedByteCount, SocketFlags.None);
+                if (byteCount == 0)
+                {
+                    return LogPacket.LegacyProtocolVersion;
+                }
+
+                receivedByteCount += byteCount;
+            }
+        }
+        catch (SocketException)
+        {
+            return LogPacket.LegacyProtocolVersion;
+        }
+        finally
+        {
+            clientSocket.ReceiveTimeout = 0;
+        }
+
+        if (BitConverter.ToUInt32(handshakeBytes, 0) != LogPacket.HandshakeMarker)
+        {
+            return LogPacket.LegacyProtocolVersion;
+        }
+
+        var protocolVersion = Math.Min(BitConverter.ToUInt32(handshakeBytes, sizeof(UInt32)), LogPacket.ProtocolVersion);
+
+        var answerBytes = new byte[2 * sizeof(UInt32)];
+        BitConverter.GetBytes(LogPacket.HandshakeMarker).CopyTo(answerBytes, 0);
+        BitConverter.GetBytes(protocolVersion).CopyTo(answerBytes, sizeof(UInt32));
+        clientSocket.Send(answerBytes);
+
+        return protocolVersion;
+    }
+
     private void SendPacketsToClientUntilConnectionIsClosed(Socket clientSocket)
     {
+        UInt32 protocolVersion;
+        try
+        {
+            protocolVersion = ReceiveHandshake(clientSocket);
+        }
+        catch (Exception e)
+        {
+            Log.Error($"Error during the handshake with the socket: {e}{Environment.NewLine}Disconnecting socket.");
+            return;
+        }
+
         while (true)
         {
             if (_isDisposed)
@@ -60,16 +117,15 @@ internal class SendLogToClientSocket : ILogListener
 
             while (_logQueue.Count > 0)
             {
-                LogEventArgs log;
+                LogPacket logPacket;
                 lock (_queueLock)
                 {
-                    log = _logQueue.Peek();
+                    logPacket = _logQueue.Peek();
                 }
-                var logPacket = new LogPacket(log);
 
                 try
                 {
-                    clientSocket.Send(logPacket.Bytes);
+                    clientSocket.Send(logPacket.GetBytes(protocolVersion));
                 }
                 catch (Exception e)
                 {
@@ -90,13 +146,17 @@ internal class SendLogToClientSocket : ILogListener
     public void Dispose()
     {
         _isDisposed = true;
+
+        GameDiscoveryFile.Delete();
     }
 
     internal void StoreLog(LogEventArgs eventArgs)
     {
+        var logPacket = new LogPacket(eventArgs);
+
         lock (_queueLock)
         {
-            _logQueue.Enqueue(eventArgs);
+            _logQueue.Enqueue(logPacket);
         }
     }
 
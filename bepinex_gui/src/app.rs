@@ -1,22 +1,43 @@
-use sysinfo::Pid;
-
 use eframe::CreationContext;
 use eframe::{self, *};
 
-use std::sync::{atomic::AtomicBool, Arc};
+use std::io;
+use std::path::PathBuf;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 use std::time::Duration;
 
-use crossbeam_channel::Receiver;
-
-use views::tabs::{console::ConsoleTab, general::GeneralTab, settings::SettingsTab, Tab};
+use crossbeam_channel::{Receiver, Sender};
+use sysinfo::Pid;
 
-use crate::backend::{process, window};
+use views::tabs::{
+    configs::ConfigsTab, console::ConsoleTab, general::GeneralTab, resources::ResourcesTab,
+    settings::SettingsTab, timeline::TimelineTab, Tab,
+};
+
+use crate::backend::game_pause::GamePause;
+use crate::backend::game_restart::{GameAttach, LaunchCommand};
+use crate::backend::game_shutdown::GameShutdown;
+use crate::backend::hang_watchdog::GameHangWatch;
+use crate::backend::process::resource_monitor;
+use crate::backend::{file_watcher, process, window};
+use crate::config::bepinex_cfg;
 use crate::config::launch::AppLaunchConfig;
 use crate::config::Config;
+use crate::config::{schema, snapshots};
+use crate::data::bepinex_log::chainloader::TimedChainloaderEvent;
 use crate::data::bepinex_log::receiver::LogReceiver;
 use crate::data::bepinex_log::BepInExLogEntry;
-use crate::data::bepinex_mod::BepInExMod;
+use crate::data::crash_report::{CrashReport, CrashWatch};
+use crate::data::installed_plugins::{self, InstalledPlugins};
+use crate::data::resource_usage::ResourceSample;
+use crate::views::command_palette::CommandPalette;
+use crate::views::crash_summary::CrashSummary;
 use crate::views::disclaimer::Disclaimer;
+use crate::views::game_picker::GamePicker;
+use crate::views::shutdown_dialog::ShutdownDialog;
 use crate::{theme, views};
 
 pub const NAME: &str = "BepInExGUI";
@@ -33,19 +54,83 @@ pub struct BepInExGUI {
 
     pub log_receiver_thread: Option<LogReceiver>,
 
+    pub gui_cfg_file_changed_receiver: Option<Receiver<PathBuf>>,
+
     pub is_window_title_set: bool,
 
+    pub command_palette: CommandPalette,
+
+    pub crash_watch: Option<CrashWatch>,
+
+    pub crash_report_receiver: Option<Receiver<CrashReport>>,
+
+    pub crash_summary: Option<CrashSummary>,
+
+    pub launch_command: Option<LaunchCommand>,
+
+    pub game_attach_receiver: Option<Receiver<io::Result<GameAttach>>>,
+
+    // kept for monitoring the game again once it restarts
+    pub resource_sample_senders: Vec<Sender<ResourceSample>>,
+
+    pub should_stop_resource_monitor: Arc<AtomicBool>,
+
+    pub game_picker: Option<GamePicker>,
+
+    pub shutdown_dialog: Option<ShutdownDialog>,
+
+    pub game_shutdown: Option<GameShutdown>,
+
+    pub game_hang_watch: Option<GameHangWatch>,
+
+    // until the game responds again
+    pub is_game_hang_dialog_dismissed: bool,
+
     pub dark_theme: egui::Style,
 }
 
+// One receiver per consumer of the log receiver thread
+struct LogReceivers {
+    general_tab_mod_r: Receiver<TimedChainloaderEvent>,
+    console_tab_mod_r: Receiver<TimedChainloaderEvent>,
+    timeline_tab_mod_r: Receiver<TimedChainloaderEvent>,
+    console_tab_log_r: Receiver<BepInExLogEntry>,
+    crash_watch_log_r: Receiver<BepInExLogEntry>,
+    game_pause_log_r: Receiver<BepInExLogEntry>,
+    game_hang_watch_log_r: Receiver<BepInExLogEntry>,
+}
+
+// What the tabs receive from the backend threads
+struct TabReceivers {
+    general_tab_mod_r: Receiver<TimedChainloaderEvent>,
+    installed_plugins_r: Receiver<InstalledPlugins>,
+    console_tab_mod_r: Receiver<TimedChainloaderEvent>,
+    console_tab_log_r: Receiver<BepInExLogEntry>,
+    console_tab_resource_sample_r: Receiver<ResourceSample>,
+    timeline_tab_mod_r: Receiver<TimedChainloaderEvent>,
+    resources_tab_resource_sample_r: Receiver<ResourceSample>,
+    configs_tab_cfg_file_changed_r: Receiver<PathBuf>,
+}
+
 const FPS_15: Duration = Duration::from_micros(66666);
 
 impl App for BepInExGUI {
     fn update(&mut self, ctx: &eframe::egui::Context, frame: &mut eframe::Frame) {
         ctx.request_repaint_after(FPS_15);
 
+        // started by hand, there is nothing to show until a game is picked
+        if !self.is_game_attached() {
+            self.update_game_picker(ctx);
+            views::notifications::show(ctx);
+            return;
+        }
+
         self.backend_update(frame);
 
+        self.update_gui_cfg_file_watcher(ctx);
+
+        self.update_game_attach_receiver(ctx);
+
         // #[cfg(debug_assertions)]
         // ctx.set_debug_on_hover(true);
 
@@ -53,15 +138,23 @@ impl App for BepInExGUI {
     }
 
     fn save(&mut self, storage: &mut dyn eframe::Storage) {
-        eframe::set_value(storage, NAME, &self.config);
+        if !self.is_game_attached() {
+            return;
+        }
+
+        self.config.store_current_profile();
+        schema::save(storage, &self.config);
         _ = self.config.save_bepinex_toml_cfg_file();
     }
 }
 
 impl BepInExGUI {
-    pub fn new(init_config: AppLaunchConfig) -> Self {
+    // Without a launch config the user picks the game to attach to
+    pub fn new(init_config: Option<AppLaunchConfig>) -> Self {
+        let game_picker = init_config.is_none().then(GamePicker::default);
+
         Self {
-            app_launch_config: init_config,
+            app_launch_config: init_config.unwrap_or_else(AppLaunchConfig::default),
             config: Default::default(),
             disclaimer: Disclaimer {
                 first_time_showing_it: true,
@@ -70,14 +163,28 @@ impl BepInExGUI {
             should_exit_app: Arc::new(AtomicBool::new(false)),
             tabs: vec![],
             log_receiver_thread: None,
+            gui_cfg_file_changed_receiver: None,
             is_window_title_set: false,
+            command_palette: CommandPalette::default(),
+            crash_watch: None,
+            crash_report_receiver: None,
+            crash_summary: None,
+            launch_command: None,
+            game_attach_receiver: None,
+            resource_sample_senders: vec![],
+            should_stop_resource_monitor: Arc::new(AtomicBool::new(false)),
+            game_picker,
+            shutdown_dialog: None,
+            game_shutdown: None,
+            game_hang_watch: None,
+            is_game_hang_dialog_dismissed: false,
             dark_theme: theme::get_dark_theme(),
         }
     }
 
     pub fn init(mut self, cc: &CreationContext) -> Self {
         if let Some(storage) = cc.storage {
-            self.config = eframe::get_value(storage, NAME).unwrap_or_default();
+            self.config = schema::load(storage);
         }
 
         theme::configure_fonts(&cc.egui_ctx);
@@ -88,16 +195,88 @@ impl BepInExGUI {
             cc.egui_ctx.set_visuals(egui::Visuals::light());
         }
 
-        self.start_thread_exit_gui_if_target_process_not_alive(
-            self.app_launch_config.target_process_id(),
-        );
+        if self.game_picker.is_none() {
+            self.init_game();
+        }
 
+        self
+    }
+
+    // The tabs only exist once the GUI is attached to a game
+    pub fn is_game_attached(&self) -> bool {
+        !self.tabs.is_empty()
+    }
+
+    pub(crate) fn init_game(&mut self) {
         window::window_topmost_on_target_start::init(self.app_launch_config.target_process_id());
 
-        let (general_tab_mod_r, console_tab_mod_r, log_r) =
+        let log_receivers =
             self.init_log_receiver(self.app_launch_config.log_socket_port_receiver());
 
-        self.init_tabs(general_tab_mod_r, console_tab_mod_r, log_r);
+        self.crash_watch = Some(CrashWatch::new(log_receivers.crash_watch_log_r));
+        self.init_crash_watch_process(self.app_launch_config.target_process_id());
+
+        self.launch_command = LaunchCommand::capture(self.app_launch_config.target_process_id());
+
+        let game_pause = GamePause::new(
+            self.app_launch_config.target_process_id(),
+            log_receivers.game_pause_log_r,
+        );
+        self.game_hang_watch = Some(GameHangWatch::new(
+            self.app_launch_config.target_process_id(),
+            game_pause.is_paused_flag(),
+            log_receivers.game_hang_watch_log_r,
+        ));
+
+        let (installed_plugins_s, installed_plugins_r) = crossbeam_channel::bounded(1);
+        installed_plugins::spawn_thread_scan(
+            self.app_launch_config.bepinex_folder_full_path(),
+            installed_plugins_s,
+        );
+
+        snapshots::spawn_thread_take_snapshot(
+            self.app_launch_config.bepinex_config_folder_full_path(),
+            self.app_launch_config.target_name().to_string(),
+            self.app_launch_config.modpack_name(),
+        );
+
+        let (console_tab_resource_sample_s, console_tab_resource_sample_r) =
+            crossbeam_channel::unbounded();
+        let (resources_tab_resource_sample_s, resources_tab_resource_sample_r) =
+            crossbeam_channel::unbounded();
+        self.resource_sample_senders = vec![
+            console_tab_resource_sample_s,
+            resources_tab_resource_sample_s,
+        ];
+        self.should_stop_resource_monitor = resource_monitor::spawn_thread_monitor(
+            self.app_launch_config.target_process_id(),
+            self.resource_sample_senders.clone(),
+        );
+
+        let (gui_cfg_file_changed_s, gui_cfg_file_changed_r) = crossbeam_channel::unbounded();
+        let (configs_tab_cfg_file_changed_s, configs_tab_cfg_file_changed_r) =
+            crossbeam_channel::unbounded();
+        file_watcher::spawn_thread_watch_folder(
+            self.app_launch_config.bepinex_config_folder_full_path(),
+            bepinex_cfg::CFG_EXTENSION,
+            vec![gui_cfg_file_changed_s, configs_tab_cfg_file_changed_s],
+        );
+        self.gui_cfg_file_changed_receiver = Some(gui_cfg_file_changed_r);
+
+        self.init_tabs(
+            TabReceivers {
+                general_tab_mod_r: log_receivers.general_tab_mod_r,
+                installed_plugins_r,
+                console_tab_mod_r: log_receivers.console_tab_mod_r,
+                console_tab_log_r: log_receivers.console_tab_log_r,
+                console_tab_resource_sample_r,
+                timeline_tab_mod_r: log_receivers.timeline_tab_mod_r,
+                resources_tab_resource_sample_r,
+                configs_tab_cfg_file_changed_r,
+            },
+            game_pause,
+        );
+        schema::validate(&mut self.config, self.tabs.len());
 
         self.config.bepinex_gui_csharp_cfg_full_path = self
             .app_launch_config
@@ -106,52 +285,125 @@ impl BepInExGUI {
 
         _ = self.config.read_bepinex_toml_cfg_file();
 
-        self
+        self.config.init_profiles(
+            self.app_launch_config.target_name(),
+            self.app_launch_config.modpack_name(),
+        );
     }
 
-    fn init_log_receiver(
-        &mut self,
-        log_socket_port_receiver: u16,
-    ) -> (
-        Receiver<BepInExMod>,
-        Receiver<BepInExMod>,
-        Receiver<BepInExLogEntry>,
-    ) {
+    fn init_log_receiver(&mut self, log_socket_port_receiver: u16) -> LogReceivers {
         let (general_tab_mod_s, general_tab_mod_r) = crossbeam_channel::unbounded();
         let (console_tab_mod_s, console_tab_mod_r) = crossbeam_channel::unbounded();
-        let (log_s, log_r) = crossbeam_channel::unbounded();
+        let (timeline_tab_mod_s, timeline_tab_mod_r) = crossbeam_channel::unbounded();
+        let (console_tab_log_s, console_tab_log_r) = crossbeam_channel::unbounded();
+        let (crash_watch_log_s, crash_watch_log_r) = crossbeam_channel::unbounded();
+        let (game_pause_log_s, game_pause_log_r) = crossbeam_channel::unbounded();
+        let (game_hang_watch_log_s, game_hang_watch_log_r) = crossbeam_channel::unbounded();
 
         let log_receiver = LogReceiver::new(
             log_socket_port_receiver,
-            vec![log_s],
-            vec![general_tab_mod_s, console_tab_mod_s],
+            vec![
+                console_tab_log_s,
+                crash_watch_log_s,
+                game_pause_log_s,
+                game_hang_watch_log_s,
+            ],
+            vec![general_tab_mod_s, console_tab_mod_s, timeline_tab_mod_s],
         );
         log_receiver.start_thread_loop();
         self.log_receiver_thread = Some(log_receiver);
 
-        (general_tab_mod_r, console_tab_mod_r, log_r)
+        LogReceivers {
+            general_tab_mod_r,
+            console_tab_mod_r,
+            timeline_tab_mod_r,
+            console_tab_log_r,
+            crash_watch_log_r,
+            game_pause_log_r,
+            game_hang_watch_log_r,
+        }
     }
 
-    fn init_tabs(
-        &mut self,
-        general_tab_mod_r: Receiver<BepInExMod>,
-        console_tab_mod_r: Receiver<BepInExMod>,
-        log_r: Receiver<BepInExLogEntry>,
-    ) {
-        self.tabs.push(Box::new(GeneralTab::new(general_tab_mod_r)));
+    pub(crate) fn init_crash_watch_process(&mut self, target_process_id: Pid) {
+        let Some(crash_watch) = &mut self.crash_watch else {
+            return;
+        };
+
+        let (process_exit_s, process_exit_r) = crossbeam_channel::bounded(1);
+        process::spawn_thread_is_process_dead(target_process_id, process_exit_s);
+
+        crash_watch.watch(process_exit_r, process::start_time(target_process_id));
+    }
+
+    fn init_tabs(&mut self, receivers: TabReceivers, game_pause: GamePause) {
+        self.tabs.push(Box::new(GeneralTab::new(
+            receivers.general_tab_mod_r,
+            receivers.installed_plugins_r,
+        )));
         self.tabs.push(Box::new(ConsoleTab::new(
-            console_tab_mod_r,
-            log_r,
-            self.should_exit_app.clone(),
+            receivers.console_tab_mod_r,
+            receivers.console_tab_log_r,
+            receivers.console_tab_resource_sample_r,
+            game_pause,
+        )));
+        self.tabs.push(Box::new(TimelineTab::new(
+            receivers.timeline_tab_mod_r,
+            process::start_time(self.app_launch_config.target_process_id()),
+        )));
+        self.tabs.push(Box::new(ResourcesTab::new(
+            receivers.resources_tab_resource_sample_r,
+        )));
+        self.tabs.push(Box::new(ConfigsTab::new(
+            self.app_launch_config.bepinex_config_folder_full_path(),
+            receivers.configs_tab_cfg_file_changed_r,
+        )));
+        self.tabs.push(Box::new(SettingsTab::new(
+            self.app_launch_config
+                .bepinex_gui_csharp_cfg_full_path()
+                .clone(),
+            self.app_launch_config.bepinex_cfg_full_path(),
         )));
-        self.tabs.push(Box::new(SettingsTab::new()));
     }
 
-    fn start_thread_exit_gui_if_target_process_not_alive(&self, target_process_id: Pid) {
-        process::spawn_thread_is_process_dead(
-            target_process_id,
-            self.config.close_window_when_game_closes.clone(),
-            self.should_exit_app.clone(),
-        )
+    // Mod managers and text editors can change the file while the GUI is open
+    fn update_gui_cfg_file_watcher(&mut self, ctx: &egui::Context) {
+        let Some(gui_cfg_file_changed_receiver) = &self.gui_cfg_file_changed_receiver else {
+            return;
+        };
+
+        let mut is_gui_cfg_file_changed = false;
+        while let Ok(changed_file_full_path) = gui_cfg_file_changed_receiver.try_recv() {
+            is_gui_cfg_file_changed |=
+                changed_file_full_path == self.config.bepinex_gui_csharp_cfg_full_path;
+        }
+
+        if !is_gui_cfg_file_changed {
+            return;
+        }
+
+        let close_window_when_game_loaded = self.config.close_window_when_game_loaded;
+        let close_window_when_game_closes = self
+            .config
+            .close_window_when_game_closes
+            .load(Ordering::Relaxed);
+
+        if let Err(err) = self.config.read_bepinex_toml_cfg_file() {
+            tracing::error!("Failed reloading the GUI config file: {}", err);
+            return;
+        }
+
+        // our own saves also end up here
+        if close_window_when_game_loaded != self.config.close_window_when_game_loaded
+            || close_window_when_game_closes
+                != self
+                    .config
+                    .close_window_when_game_closes
+                    .load(Ordering::Relaxed)
+        {
+            views::notifications::push(
+                ctx,
+                "The GUI config file was modified outside of the GUI, its settings were reloaded.",
+            );
+        }
     }
 }
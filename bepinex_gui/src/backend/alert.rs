@@ -0,0 +1,108 @@
+use std::thread;
+
+#[cfg(windows)]
+use std::time::Duration;
+
+#[cfg(not(windows))]
+use std::process::Command;
+
+use crate::app;
+
+// Balloons don't go away by themselves while their tray icon exists
+#[cfg(windows)]
+const DESKTOP_NOTIFICATION_DURATION: Duration = Duration::from_secs(10);
+
+// The notification is shown even when the GUI is minimized or behind the game
+pub fn spawn_thread_show_desktop_notification(title: String, body: String) {
+    thread::spawn(move || {
+        show_desktop_notification(&title, &body);
+    });
+}
+
+#[cfg(windows)]
+fn show_desktop_notification(title: &str, body: &str) {
+    use std::mem::size_of;
+
+    use sysinfo::Pid;
+    use winapi::um::{
+        shellapi::{
+            Shell_NotifyIconW, NIF_ICON, NIF_INFO, NIF_TIP, NIIF_WARNING, NIM_ADD, NIM_DELETE,
+            NOTIFYICONDATAW,
+        },
+        winuser::{LoadIconW, IDI_WARNING},
+    };
+
+    use crate::backend::process;
+
+    fn copy_to_wide(destination: &mut [u16], text: &str) {
+        // keeps the null terminator
+        for (destination, c) in destination
+            .iter_mut()
+            .take(destination.len() - 1)
+            .zip(text.encode_utf16())
+        {
+            *destination = c;
+        }
+    }
+
+    // the tray icon needs a window of the GUI
+    let Some(window) = process::main_window(Pid::from(std::process::id() as usize)) else {
+        tracing::error!("No GUI window for showing the desktop notification");
+        return;
+    };
+
+    unsafe {
+        let mut notify_icon_data: NOTIFYICONDATAW = std::mem::zeroed();
+        notify_icon_data.cbSize = size_of::<NOTIFYICONDATAW>() as u32;
+        notify_icon_data.hWnd = window;
+        notify_icon_data.uID = 1;
+        notify_icon_data.uFlags = NIF_ICON | NIF_TIP | NIF_INFO;
+        notify_icon_data.hIcon = LoadIconW(std::ptr::null_mut(), IDI_WARNING);
+        notify_icon_data.dwInfoFlags = NIIF_WARNING;
+        copy_to_wide(&mut notify_icon_data.szTip, app::NAME);
+        copy_to_wide(&mut notify_icon_data.szInfoTitle, title);
+        copy_to_wide(&mut notify_icon_data.szInfo, body);
+
+        if Shell_NotifyIconW(NIM_ADD, &mut notify_icon_data) == 0 {
+            tracing::error!("Failed showing the desktop notification");
+            return;
+        }
+
+        thread::sleep(DESKTOP_NOTIFICATION_DURATION);
+
+        Shell_NotifyIconW(NIM_DELETE, &mut notify_icon_data);
+    }
+}
+
+// Through the notification daemon of the desktop environment
+#[cfg(not(windows))]
+fn show_desktop_notification(title: &str, body: &str) {
+    if let Err(err) = Command::new("notify-send")
+        .args(["--app-name", app::NAME, title, body])
+        .status()
+    {
+        tracing::error!("Failed showing the desktop notification: {}", err);
+    }
+}
+
+#[cfg(windows)]
+pub fn play_sound() {
+    use winapi::um::winuser::{MessageBeep, MB_ICONEXCLAMATION};
+
+    unsafe {
+        MessageBeep(MB_ICONEXCLAMATION);
+    }
+}
+
+// The freedesktop sound theme, when libcanberra is installed
+#[cfg(not(windows))]
+pub fn play_sound() {
+    thread::spawn(|| {
+        if let Err(err) = Command::new("canberra-gtk-play")
+            .args(["--id", "dialog-warning"])
+            .status()
+        {
+            tracing::error!("Failed playing the alert sound: {}", err);
+        }
+    });
+}
@@ -0,0 +1,681 @@
+use std::{fs, path::Path};
+
+// Reads the [BepInPlugin] attributes of a .NET assembly
+// without loading it, straight from the ECMA-335 metadata tables
+// https://www.ecma-international.org/wp-content/uploads/ECMA-335_6th_edition_june_2012.pdf
+
+const BEPINEX_PLUGIN_ATTRIBUTE_NAME: &str = "BepInPlugin";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BepInPlugin {
+    pub guid: String,
+    pub name: String,
+    pub version: String,
+    // Full name of the class the attribute is on
+    pub type_name: String,
+}
+
+pub fn read_bepinex_plugins(assembly_full_path: &Path) -> Vec<BepInPlugin> {
+    let Ok(bytes) = fs::read(assembly_full_path) else {
+        return Vec::new();
+    };
+
+    // Native dlls and non .NET files have no metadata, that's expected
+    let Some(metadata) = Metadata::parse(&bytes) else {
+        return Vec::new();
+    };
+
+    metadata.bepinex_plugins().unwrap_or_else(|| {
+        tracing::error!(
+            "Failed reading the metadata tables of {}",
+            assembly_full_path.display()
+        );
+        Vec::new()
+    })
+}
+
+const TYPE_REF_TABLE: usize = 0x01;
+const TYPE_DEF_TABLE: usize = 0x02;
+const FIELD_TABLE: usize = 0x04;
+const METHOD_DEF_TABLE: usize = 0x06;
+const PARAM_TABLE: usize = 0x08;
+const MEMBER_REF_TABLE: usize = 0x0A;
+const CUSTOM_ATTRIBUTE_TABLE: usize = 0x0C;
+const TABLE_COUNT: usize = 64;
+
+// Coded index tags, in the order of the tables in the spec (II.24.2.6)
+const RESOLUTION_SCOPE_TABLES: &[usize] = &[0x00, 0x1A, 0x23, 0x01];
+const TYPE_DEF_OR_REF_TABLES: &[usize] = &[0x02, 0x01, 0x1B];
+const MEMBER_REF_PARENT_TABLES: &[usize] = &[0x02, 0x01, 0x1A, 0x06, 0x1B];
+const HAS_CONSTANT_TABLES: &[usize] = &[0x04, 0x08, 0x17];
+const HAS_CUSTOM_ATTRIBUTE_TABLES: &[usize] = &[
+    0x06, 0x04, 0x01, 0x02, 0x08, 0x09, 0x0A, 0x00, 0x0E, 0x17, 0x14, 0x11, 0x1A, 0x1B, 0x20, 0x23,
+    0x26, 0x27, 0x28, 0x2A, 0x2C, 0x2B,
+];
+// Only MethodDef (2) and MemberRef (3) are used, the others are padding
+const CUSTOM_ATTRIBUTE_TYPE_TABLES: &[usize] = &[0x3F, 0x3F, 0x06, 0x0A, 0x3F];
+
+const HAS_CUSTOM_ATTRIBUTE_TYPE_DEF_TAG: u32 = 3;
+const CUSTOM_ATTRIBUTE_TYPE_MEMBER_REF_TAG: u32 = 3;
+const MEMBER_REF_PARENT_TYPE_REF_TAG: u32 = 1;
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    const fn new(bytes: &'a [u8], offset: usize) -> Self {
+        Self { bytes, offset }
+    }
+
+    fn read_bytes(&mut self, count: usize) -> Option<&'a [u8]> {
+        let bytes = self
+            .bytes
+            .get(self.offset..self.offset.checked_add(count)?)?;
+        self.offset += count;
+        Some(bytes)
+    }
+
+    fn read_u8(&mut self) -> Option<u8> {
+        Some(self.read_bytes(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Option<u16> {
+        Some(u16::from_le_bytes(self.read_bytes(2)?.try_into().ok()?))
+    }
+
+    fn read_u32(&mut self) -> Option<u32> {
+        Some(u32::from_le_bytes(self.read_bytes(4)?.try_into().ok()?))
+    }
+
+    fn read_u64(&mut self) -> Option<u64> {
+        Some(u64::from_le_bytes(self.read_bytes(8)?.try_into().ok()?))
+    }
+
+    fn read_index(&mut self, size: usize) -> Option<u32> {
+        if size == 2 {
+            self.read_u16().map(u32::from)
+        } else {
+            self.read_u32()
+        }
+    }
+
+    // II.23.2
+    fn read_compressed_u32(&mut self) -> Option<u32> {
+        let first = self.read_u8()?;
+
+        if first & 0x80 == 0 {
+            Some(u32::from(first))
+        } else if first & 0xC0 == 0x80 {
+            Some((u32::from(first & 0x3F) << 8) | u32::from(self.read_u8()?))
+        } else if first & 0xE0 == 0xC0 {
+            let rest = self.read_bytes(3)?;
+            Some(
+                (u32::from(first & 0x1F) << 24)
+                    | (u32::from(rest[0]) << 16)
+                    | (u32::from(rest[1]) << 8)
+                    | u32::from(rest[2]),
+            )
+        } else {
+            None
+        }
+    }
+
+    // II.23.3, None for a null string
+    fn read_ser_string(&mut self) -> Option<Option<String>> {
+        if self.bytes.get(self.offset) == Some(&0xFF) {
+            self.offset += 1;
+            return Some(None);
+        }
+
+        let length = self.read_compressed_u32()? as usize;
+        let bytes = self.read_bytes(length)?;
+
+        Some(Some(String::from_utf8_lossy(bytes).to_string()))
+    }
+}
+
+struct Metadata<'a> {
+    strings_heap: &'a [u8],
+    blob_heap: &'a [u8],
+    row_counts: [u32; TABLE_COUNT],
+    string_index_size: usize,
+    guid_index_size: usize,
+    blob_index_size: usize,
+    table_offsets: [usize; TABLE_COUNT],
+    tables: &'a [u8],
+}
+
+impl<'a> Metadata<'a> {
+    fn parse(bytes: &'a [u8]) -> Option<Self> {
+        let metadata = metadata_root(bytes)?;
+
+        let mut reader = Reader::new(metadata, 12);
+        let version_length = reader.read_u32()? as usize;
+        reader.read_bytes(version_length)?;
+        let _flags = reader.read_u16()?;
+        let stream_count = reader.read_u16()?;
+
+        let mut tables_stream = None;
+        let mut strings_heap: &[u8] = &[];
+        let mut blob_heap: &[u8] = &[];
+        for _ in 0..stream_count {
+            let offset = reader.read_u32()? as usize;
+            let size = reader.read_u32()? as usize;
+            let stream = metadata.get(offset..offset.checked_add(size)?)?;
+
+            let name_start = reader.offset;
+            let name_length = metadata[name_start..].iter().position(|&b| b == 0)?;
+            let name = &metadata[name_start..name_start + name_length];
+            // null terminated and padded to 4 bytes
+            reader.offset = name_start + (name_length + 4) / 4 * 4;
+
+            match name {
+                b"#~" | b"#-" => tables_stream = Some(stream),
+                b"#Strings" => strings_heap = stream,
+                b"#Blob" => blob_heap = stream,
+                _ => {}
+            }
+        }
+
+        let tables_stream = tables_stream?;
+        let mut reader = Reader::new(tables_stream, 6);
+        let heap_sizes = reader.read_u8()?;
+        let _reserved = reader.read_u8()?;
+        let valid = reader.read_u64()?;
+        let _sorted = reader.read_u64()?;
+
+        let mut row_counts = [0u32; TABLE_COUNT];
+        for (table, row_count) in row_counts.iter_mut().enumerate() {
+            if valid & (1 << table) != 0 {
+                *row_count = reader.read_u32()?;
+            }
+        }
+
+        // Uncompressed (#-) streams written by some obfuscators have an extra field
+        if heap_sizes & 0x40 != 0 {
+            reader.read_u32()?;
+        }
+
+        let mut metadata = Self {
+            strings_heap,
+            blob_heap,
+            row_counts,
+            string_index_size: if heap_sizes & 0x01 != 0 { 4 } else { 2 },
+            guid_index_size: if heap_sizes & 0x02 != 0 { 4 } else { 2 },
+            blob_index_size: if heap_sizes & 0x04 != 0 { 4 } else { 2 },
+            table_offsets: [0; TABLE_COUNT],
+            tables: &tables_stream[reader.offset..],
+        };
+
+        // Only the tables up to the custom attributes one are needed
+        let mut table_offset = 0;
+        for table in 0..=CUSTOM_ATTRIBUTE_TABLE {
+            metadata.table_offsets[table] = table_offset;
+            table_offset += metadata.row_size(table) * metadata.row_counts[table] as usize;
+        }
+
+        Some(metadata)
+    }
+
+    fn table_index_size(&self, table: usize) -> usize {
+        if self.row_counts[table] < 1 << 16 {
+            2
+        } else {
+            4
+        }
+    }
+
+    fn coded_index_size(&self, tables: &[usize]) -> usize {
+        let tag_bits = usize::BITS - (tables.len() - 1).leading_zeros();
+        let max_row_count = tables
+            .iter()
+            .map(|&table| self.row_counts.get(table).copied().unwrap_or(0))
+            .max()
+            .unwrap_or(0);
+
+        if max_row_count < 1 << (16 - tag_bits) {
+            2
+        } else {
+            4
+        }
+    }
+
+    // II.22
+    fn row_size(&self, table: usize) -> usize {
+        let string = self.string_index_size;
+        let guid = self.guid_index_size;
+        let blob = self.blob_index_size;
+
+        match table {
+            // Module
+            0x00 => 2 + string + 3 * guid,
+            // TypeRef
+            0x01 => self.coded_index_size(RESOLUTION_SCOPE_TABLES) + 2 * string,
+            // TypeDef
+            0x02 => {
+                4 + 2 * string
+                    + self.coded_index_size(TYPE_DEF_OR_REF_TABLES)
+                    + self.table_index_size(FIELD_TABLE)
+                    + self.table_index_size(METHOD_DEF_TABLE)
+            }
+            // FieldPtr
+            0x03 => self.table_index_size(FIELD_TABLE),
+            // Field
+            0x04 => 2 + string + blob,
+            // MethodPtr
+            0x05 => self.table_index_size(METHOD_DEF_TABLE),
+            // MethodDef
+            0x06 => 4 + 2 + 2 + string + blob + self.table_index_size(PARAM_TABLE),
+            // ParamPtr
+            0x07 => self.table_index_size(PARAM_TABLE),
+            // Param
+            0x08 => 2 + 2 + string,
+            // InterfaceImpl
+            0x09 => {
+                self.table_index_size(TYPE_DEF_TABLE)
+                    + self.coded_index_size(TYPE_DEF_OR_REF_TABLES)
+            }
+            // MemberRef
+            0x0A => self.coded_index_size(MEMBER_REF_PARENT_TABLES) + string + blob,
+            // Constant
+            0x0B => 2 + self.coded_index_size(HAS_CONSTANT_TABLES) + blob,
+            // CustomAttribute
+            0x0C => {
+                self.coded_index_size(HAS_CUSTOM_ATTRIBUTE_TABLES)
+                    + self.coded_index_size(CUSTOM_ATTRIBUTE_TYPE_TABLES)
+                    + blob
+            }
+            _ => unreachable!("only the tables before the custom attributes are skipped"),
+        }
+    }
+
+    // Rows are 1 based
+    fn row(&self, table: usize, row: u32) -> Option<Reader<'a>> {
+        if row == 0 || row > self.row_counts[table] {
+            return None;
+        }
+
+        let offset = self.table_offsets[table] + (row as usize - 1) * self.row_size(table);
+        Some(Reader::new(self.tables, offset))
+    }
+
+    fn read_string(&self, reader: &mut Reader) -> Option<&'a str> {
+        let index = reader.read_index(self.string_index_size)? as usize;
+        let string = self.strings_heap.get(index..)?;
+        let length = string.iter().position(|&b| b == 0)?;
+
+        std::str::from_utf8(&string[..length]).ok()
+    }
+
+    fn read_blob(&self, reader: &mut Reader) -> Option<&'a [u8]> {
+        let index = reader.read_index(self.blob_index_size)? as usize;
+        let mut blob_reader = Reader::new(self.blob_heap, index);
+        let length = blob_reader.read_compressed_u32()? as usize;
+
+        blob_reader.read_bytes(length)
+    }
+
+    fn read_coded_index(&self, reader: &mut Reader, tables: &[usize]) -> Option<(u32, u32)> {
+        let tag_bits = usize::BITS - (tables.len() - 1).leading_zeros();
+        let coded_index = reader.read_index(self.coded_index_size(tables))?;
+
+        Some((coded_index & ((1 << tag_bits) - 1), coded_index >> tag_bits))
+    }
+
+    fn type_def_full_name(&self, row: u32) -> Option<String> {
+        let mut reader = self.row(TYPE_DEF_TABLE, row)?;
+        let _flags = reader.read_u32()?;
+        let name = self.read_string(&mut reader)?;
+        let namespace = self.read_string(&mut reader)?;
+
+        Some(if namespace.is_empty() {
+            name.to_string()
+        } else {
+            format!("{namespace}.{name}")
+        })
+    }
+
+    fn is_bepinex_plugin_attribute_constructor(&self, member_ref_row: u32) -> Option<bool> {
+        let mut reader = self.row(MEMBER_REF_TABLE, member_ref_row)?;
+        let (tag, type_ref_row) = self.read_coded_index(&mut reader, MEMBER_REF_PARENT_TABLES)?;
+        if tag != MEMBER_REF_PARENT_TYPE_REF_TAG {
+            return Some(false);
+        }
+
+        let mut reader = self.row(TYPE_REF_TABLE, type_ref_row)?;
+        let _resolution_scope = self.read_coded_index(&mut reader, RESOLUTION_SCOPE_TABLES)?;
+        let name = self.read_string(&mut reader)?;
+        let namespace = self.read_string(&mut reader)?;
+
+        Some(name == BEPINEX_PLUGIN_ATTRIBUTE_NAME && namespace == "BepInEx")
+    }
+
+    fn bepinex_plugins(&self) -> Option<Vec<BepInPlugin>> {
+        let mut bepinex_plugins = Vec::new();
+
+        for row in 1..=self.row_counts[CUSTOM_ATTRIBUTE_TABLE] {
+            let mut reader = self.row(CUSTOM_ATTRIBUTE_TABLE, row)?;
+            let (parent_tag, parent_row) =
+                self.read_coded_index(&mut reader, HAS_CUSTOM_ATTRIBUTE_TABLES)?;
+            let (type_tag, type_row) =
+                self.read_coded_index(&mut reader, CUSTOM_ATTRIBUTE_TYPE_TABLES)?;
+            let value = self.read_blob(&mut reader)?;
+
+            // The attribute constructor is a MethodDef only inside BepInEx itself
+            if parent_tag != HAS_CUSTOM_ATTRIBUTE_TYPE_DEF_TAG
+                || type_tag != CUSTOM_ATTRIBUTE_TYPE_MEMBER_REF_TAG
+                || !self.is_bepinex_plugin_attribute_constructor(type_row)?
+            {
+                continue;
+            }
+
+            // II.23.3, prolog then the (guid, name, version) constructor arguments
+            let mut value_reader = Reader::new(value, 0);
+            if value_reader.read_u16() != Some(0x0001) {
+                continue;
+            }
+            let (Some(Some(guid)), Some(Some(name)), Some(Some(version))) = (
+                value_reader.read_ser_string(),
+                value_reader.read_ser_string(),
+                value_reader.read_ser_string(),
+            ) else {
+                continue;
+            };
+
+            bepinex_plugins.push(BepInPlugin {
+                guid,
+                name,
+                version,
+                type_name: self.type_def_full_name(parent_row)?,
+            });
+        }
+
+        Some(bepinex_plugins)
+    }
+}
+
+// II.25 PE file format, returns the bytes of the metadata root
+fn metadata_root(bytes: &[u8]) -> Option<&[u8]> {
+    const CLI_HEADER_DATA_DIRECTORY_INDEX: usize = 14;
+    const METADATA_ROOT_SIGNATURE: u32 = 0x424A_5342;
+
+    let mut reader = Reader::new(bytes, 0);
+    if reader.read_bytes(2)? != b"MZ" {
+        return None;
+    }
+
+    reader.offset = 0x3C;
+    let pe_header_offset = reader.read_u32()? as usize;
+
+    reader.offset = pe_header_offset;
+    if reader.read_bytes(4)? != b"PE\0\0" {
+        return None;
+    }
+
+    let _machine = reader.read_u16()?;
+    let section_count = reader.read_u16()?;
+    reader.read_bytes(12)?;
+    let optional_header_size = reader.read_u16()? as usize;
+    let _characteristics = reader.read_u16()?;
+
+    let optional_header_offset = reader.offset;
+    let data_directories_offset = match reader.read_u16()? {
+        // PE32
+        0x10B => 96,
+        // PE32+
+        0x20B => 112,
+        _ => return None,
+    };
+
+    reader.offset =
+        optional_header_offset + data_directories_offset + CLI_HEADER_DATA_DIRECTORY_INDEX * 8;
+    let cli_header_rva = reader.read_u32()?;
+    if cli_header_rva == 0 {
+        return None;
+    }
+
+    let section_table_offset = optional_header_offset + optional_header_size;
+    let rva_to_offset = |rva: u32| -> Option<usize> {
+        let (virtual_address, raw_data_offset) = (0..section_count as usize).find_map(|i| {
+            let mut reader = Reader::new(bytes, section_table_offset + i * 40 + 8);
+            let virtual_size = reader.read_u32()?;
+            let virtual_address = reader.read_u32()?;
+            let _raw_data_size = reader.read_u32()?;
+            let raw_data_offset = reader.read_u32()?;
+
+            (virtual_address..virtual_address.checked_add(virtual_size)?)
+                .contains(&rva)
+                .then_some((virtual_address, raw_data_offset))
+        })?;
+
+        // the section header comes from the file, it can point anywhere
+        (rva - virtual_address)
+            .checked_add(raw_data_offset)
+            .map(|offset| offset as usize)
+    };
+
+    let mut reader = Reader::new(bytes, rva_to_offset(cli_header_rva)?.checked_add(8)?);
+    let metadata_rva = reader.read_u32()?;
+    let metadata_size = reader.read_u32()? as usize;
+
+    let metadata_offset = rva_to_offset(metadata_rva)?;
+    let metadata = bytes.get(metadata_offset..metadata_offset.checked_add(metadata_size)?)?;
+
+    if Reader::new(metadata, 0).read_u32()? != METADATA_ROOT_SIGNATURE {
+        return None;
+    }
+
+    Some(metadata)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ser_string(string: &str) -> Vec<u8> {
+        let mut bytes = vec![string.len() as u8];
+        bytes.extend_from_slice(string.as_bytes());
+        bytes
+    }
+
+    fn push_u16(bytes: &mut Vec<u8>, value: u16) {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn push_u32(bytes: &mut Vec<u8>, value: u32) {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    // Smallest PE32 with a metadata root holding:
+    // - a TypeRef to the attribute type and a MemberRef to its constructor
+    // - one TypeDef with the attribute on it
+    fn make_assembly(attribute_namespace: &str, guid: &str, name: &str, version: &str) -> Vec<u8> {
+        let mut strings_heap = vec![0u8];
+        let mut add_string = |string: &str| {
+            let index = strings_heap.len() as u16;
+            strings_heap.extend_from_slice(string.as_bytes());
+            strings_heap.push(0);
+            index
+        };
+        let attribute_name_index = add_string(BEPINEX_PLUGIN_ATTRIBUTE_NAME);
+        let attribute_namespace_index = add_string(attribute_namespace);
+        let type_name_index = add_string("Plugin");
+        let type_namespace_index = add_string("MyMod");
+        let constructor_name_index = add_string(".ctor");
+        while strings_heap.len() % 4 != 0 {
+            strings_heap.push(0);
+        }
+
+        let mut attribute_value = vec![0x01, 0x00];
+        attribute_value.extend(ser_string(guid));
+        attribute_value.extend(ser_string(name));
+        attribute_value.extend(ser_string(version));
+        attribute_value.extend([0x00, 0x00]);
+        let mut blob_heap = vec![0u8, attribute_value.len() as u8];
+        blob_heap.extend(attribute_value);
+        while blob_heap.len() % 4 != 0 {
+            blob_heap.push(0);
+        }
+
+        let mut tables = Vec::new();
+        push_u32(&mut tables, 0);
+        tables.extend([2, 0, 0, 1]);
+        let valid: u64 = (1 << TYPE_REF_TABLE)
+            | (1 << TYPE_DEF_TABLE)
+            | (1 << MEMBER_REF_TABLE)
+            | (1 << CUSTOM_ATTRIBUTE_TABLE);
+        tables.extend_from_slice(&valid.to_le_bytes());
+        tables.extend_from_slice(&0u64.to_le_bytes());
+        for _ in 0..4 {
+            push_u32(&mut tables, 1);
+        }
+        // TypeRef
+        push_u16(&mut tables, 0);
+        push_u16(&mut tables, attribute_name_index);
+        push_u16(&mut tables, attribute_namespace_index);
+        // TypeDef
+        push_u32(&mut tables, 0);
+        push_u16(&mut tables, type_name_index);
+        push_u16(&mut tables, type_namespace_index);
+        push_u16(&mut tables, 0);
+        push_u16(&mut tables, 1);
+        push_u16(&mut tables, 1);
+        // MemberRef, parent is TypeRef 1
+        push_u16(
+            &mut tables,
+            (1 << 3) | MEMBER_REF_PARENT_TYPE_REF_TAG as u16,
+        );
+        push_u16(&mut tables, constructor_name_index);
+        push_u16(&mut tables, 0);
+        // CustomAttribute, on TypeDef 1 with the constructor MemberRef 1
+        push_u16(
+            &mut tables,
+            (1 << 5) | HAS_CUSTOM_ATTRIBUTE_TYPE_DEF_TAG as u16,
+        );
+        push_u16(
+            &mut tables,
+            (1 << 3) | CUSTOM_ATTRIBUTE_TYPE_MEMBER_REF_TAG as u16,
+        );
+        push_u16(&mut tables, 1);
+        while tables.len() % 4 != 0 {
+            tables.push(0);
+        }
+
+        let version_string = b"v4.0.30319\0\0";
+        let stream_names: [&[u8]; 3] = [b"#~\0\0", b"#Strings\0\0\0\0", b"#Blob\0\0\0"];
+        let streams = [&tables, &strings_heap, &blob_heap];
+        let header_size = 16
+            + version_string.len()
+            + 4
+            + stream_names
+                .iter()
+                .map(|name| 8 + name.len())
+                .sum::<usize>();
+
+        let mut metadata = Vec::new();
+        push_u32(&mut metadata, 0x424A_5342);
+        push_u16(&mut metadata, 1);
+        push_u16(&mut metadata, 1);
+        push_u32(&mut metadata, 0);
+        push_u32(&mut metadata, version_string.len() as u32);
+        metadata.extend_from_slice(version_string);
+        push_u16(&mut metadata, 0);
+        push_u16(&mut metadata, streams.len() as u16);
+        let mut stream_offset = header_size;
+        for (stream, name) in streams.iter().zip(stream_names) {
+            push_u32(&mut metadata, stream_offset as u32);
+            push_u32(&mut metadata, stream.len() as u32);
+            metadata.extend_from_slice(name);
+            stream_offset += stream.len();
+        }
+        for stream in streams {
+            metadata.extend_from_slice(stream);
+        }
+
+        const SECTION_RVA: u32 = 0x2000;
+        const SECTION_FILE_OFFSET: usize = 0x200;
+        const CLI_HEADER_SIZE: u32 = 72;
+
+        let mut section = Vec::new();
+        push_u32(&mut section, CLI_HEADER_SIZE);
+        push_u16(&mut section, 2);
+        push_u16(&mut section, 5);
+        push_u32(&mut section, SECTION_RVA + CLI_HEADER_SIZE);
+        push_u32(&mut section, metadata.len() as u32);
+        section.resize(CLI_HEADER_SIZE as usize, 0);
+        section.extend(metadata);
+
+        let mut bytes = vec![0u8; SECTION_FILE_OFFSET];
+        bytes[..2].copy_from_slice(b"MZ");
+        bytes[0x3C..0x40].copy_from_slice(&0x80u32.to_le_bytes());
+
+        let mut pe_header = b"PE\0\0".to_vec();
+        push_u16(&mut pe_header, 0x14C);
+        push_u16(&mut pe_header, 1);
+        pe_header.extend([0; 12]);
+        push_u16(&mut pe_header, 224);
+        push_u16(&mut pe_header, 0x2102);
+        let mut optional_header = vec![0u8; 224];
+        optional_header[..2].copy_from_slice(&0x10Bu16.to_le_bytes());
+        optional_header[208..212].copy_from_slice(&SECTION_RVA.to_le_bytes());
+        optional_header[212..216].copy_from_slice(&CLI_HEADER_SIZE.to_le_bytes());
+        pe_header.extend(optional_header);
+        pe_header.extend_from_slice(b".text\0\0\0");
+        push_u32(&mut pe_header, section.len() as u32);
+        push_u32(&mut pe_header, SECTION_RVA);
+        push_u32(&mut pe_header, section.len() as u32);
+        push_u32(&mut pe_header, SECTION_FILE_OFFSET as u32);
+        pe_header.extend([0; 16]);
+        bytes[0x80..0x80 + pe_header.len()].copy_from_slice(&pe_header);
+
+        bytes.extend(section);
+        bytes
+    }
+
+    fn read_bepinex_plugins_from_bytes(bytes: &[u8]) -> Vec<BepInPlugin> {
+        Metadata::parse(bytes)
+            .and_then(|metadata| metadata.bepinex_plugins())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn reads_bepinex_plugin_attribute() {
+        let bytes = make_assembly("BepInEx", "com.author.mymod", "My Mod", "1.2.3");
+
+        assert_eq!(
+            read_bepinex_plugins_from_bytes(&bytes),
+            vec![BepInPlugin {
+                guid: "com.author.mymod".to_string(),
+                name: "My Mod".to_string(),
+                version: "1.2.3".to_string(),
+                type_name: "MyMod.Plugin".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn ignores_attributes_from_other_namespaces() {
+        let bytes = make_assembly("NotBepInEx", "com.author.mymod", "My Mod", "1.2.3");
+
+        assert!(read_bepinex_plugins_from_bytes(&bytes).is_empty());
+    }
+
+    #[test]
+    fn ignores_files_that_are_not_assemblies() {
+        assert!(Metadata::parse(b"").is_none());
+        assert!(Metadata::parse(b"MZ not a pe file").is_none());
+        assert!(Metadata::parse(&[0u8; 0x400]).is_none());
+    }
+
+    #[test]
+    fn ignores_sections_pointing_past_the_end_of_the_file() {
+        let mut bytes = make_assembly("BepInEx", "com.author.mymod", "My Mod", "1.2.3");
+        // CLI header data directory, then PointerToRawData of the .text section
+        bytes[0x170..0x174].copy_from_slice(&0x2010u32.to_le_bytes());
+        bytes[0x18C..0x190].copy_from_slice(&0xFFFF_FFF8u32.to_le_bytes());
+
+        assert!(Metadata::parse(&bytes).is_none());
+    }
+}
@@ -0,0 +1,51 @@
+use std::{
+    ffi::OsString,
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+const TEMP_EXTENSION: &str = "tmp";
+const BACKUP_EXTENSION: &str = "bak";
+
+// Write to a temp file next to the target then rename it over the target,
+// a crash mid-write leaves the original file untouched.
+// The previous content of the file is kept in a ".bak" file next to it.
+pub fn write_with_backup(file_full_path: &Path, contents: &[u8]) -> io::Result<()> {
+    let temp_file_full_path = append_extension(file_full_path, TEMP_EXTENSION);
+
+    if let Err(err) = write_and_sync(&temp_file_full_path, contents) {
+        _ = fs::remove_file(&temp_file_full_path);
+        return Err(err);
+    }
+
+    if file_full_path.exists() {
+        if let Err(err) = fs::copy(
+            file_full_path,
+            append_extension(file_full_path, BACKUP_EXTENSION),
+        ) {
+            tracing::warn!("Failed backing up {}: {}", file_full_path.display(), err);
+        }
+    }
+
+    if let Err(err) = fs::rename(&temp_file_full_path, file_full_path) {
+        _ = fs::remove_file(&temp_file_full_path);
+        return Err(err);
+    }
+
+    Ok(())
+}
+
+fn write_and_sync(file_full_path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut file = File::create(file_full_path)?;
+    file.write_all(contents)?;
+    file.sync_all()
+}
+
+// "BepInEx.cfg" -> "BepInEx.cfg.bak"
+fn append_extension(file_full_path: &Path, extension: &str) -> PathBuf {
+    let mut file_full_path: OsString = file_full_path.into();
+    file_full_path.push(".");
+    file_full_path.push(extension);
+    file_full_path.into()
+}
@@ -0,0 +1,67 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crossbeam_channel::Sender;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+// Polls the modified time of the files directly inside the folder and sends the path of the ones
+// that are created or modified. Stops once every receiver is dropped.
+pub fn spawn_thread_watch_folder(
+    folder_full_path: PathBuf,
+    extension: &'static str,
+    changed_file_senders: Vec<Sender<PathBuf>>,
+) {
+    thread::spawn(move || {
+        let mut modified_times = read_modified_times(&folder_full_path, extension);
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+
+            let new_modified_times = read_modified_times(&folder_full_path, extension);
+
+            for (file_full_path, modified_time) in &new_modified_times {
+                if modified_times.get(file_full_path) == Some(modified_time) {
+                    continue;
+                }
+
+                let mut has_receiver = false;
+                for sender in &changed_file_senders {
+                    has_receiver |= sender.send(file_full_path.clone()).is_ok();
+                }
+
+                if !has_receiver {
+                    return;
+                }
+            }
+
+            modified_times = new_modified_times;
+        }
+    });
+}
+
+fn read_modified_times(folder_full_path: &Path, extension: &str) -> HashMap<PathBuf, SystemTime> {
+    let Ok(entries) = fs::read_dir(folder_full_path) else {
+        return HashMap::new();
+    };
+
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|file_extension| file_extension.eq_ignore_ascii_case(extension))
+        })
+        .filter_map(|path| {
+            let modified_time = fs::metadata(&path).and_then(|metadata| metadata.modified());
+            modified_time
+                .ok()
+                .map(|modified_time| (path, modified_time))
+        })
+        .collect()
+}
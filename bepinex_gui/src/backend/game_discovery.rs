@@ -0,0 +1,118 @@
+use std::{
+    ffi::OsStr,
+    fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+    thread,
+};
+
+use crossbeam_channel::Sender;
+
+use crate::{backend::process, config::launch::AppLaunchConfig};
+
+// Written by BepInEx.GUI.Loader for each running game, one file per game process
+fn discovery_folder_full_path() -> PathBuf {
+    std::env::temp_dir().join("bepinex_gui").join("games")
+}
+
+const DISCOVERY_FILE_EXTENSION: &str = "txt";
+
+pub struct DiscoveredGame {
+    // the arguments the loader launches the GUI with, one per line in the discovery file
+    args: Vec<String>,
+    pub app_launch_config: AppLaunchConfig,
+}
+
+impl DiscoveredGame {
+    fn parse(text: &str) -> Option<Self> {
+        // AppLaunchConfig expects the executable path first
+        let args: Vec<String> = std::iter::once(String::new())
+            .chain(text.lines().map(str::to_string))
+            .collect();
+
+        Some(Self {
+            app_launch_config: AppLaunchConfig::from(&args)?,
+            args,
+        })
+    }
+
+    // Another game than the one the GUI shows, it gets its own GUI
+    pub fn launch_new_gui(&self) -> io::Result<()> {
+        let gui_executable_full_path = std::env::current_exe()?;
+
+        let mut command = Command::new(&gui_executable_full_path);
+        command.args(&self.args[1..]);
+        if let Some(gui_folder_full_path) = gui_executable_full_path.parent() {
+            command.current_dir(gui_folder_full_path);
+        }
+        command.spawn()?;
+
+        Ok(())
+    }
+}
+
+pub fn spawn_thread_discover(discovered_games_sender: Sender<Vec<DiscoveredGame>>) {
+    thread::spawn(move || {
+        _ = discovered_games_sender.send(discover());
+    });
+}
+
+fn discover() -> Vec<DiscoveredGame> {
+    let Ok(entries) = fs::read_dir(discovery_folder_full_path()) else {
+        return Vec::new();
+    };
+
+    let mut discovered_games = Vec::new();
+    for entry in entries.flatten() {
+        let discovery_file_full_path = entry.path();
+
+        // the loader writes a .tmp file first
+        if discovery_file_full_path.extension() != Some(OsStr::new(DISCOVERY_FILE_EXTENSION)) {
+            continue;
+        }
+
+        let Some(discovered_game) = fs::read_to_string(&discovery_file_full_path)
+            .ok()
+            .and_then(|text| DiscoveredGame::parse(&text))
+        else {
+            continue;
+        };
+
+        // the loader can't clean up after a crash
+        if !is_running(&discovered_game, &discovery_file_full_path) {
+            tracing::info!(
+                "Removing stale discovery file {:?}",
+                discovery_file_full_path
+            );
+            _ = fs::remove_file(&discovery_file_full_path);
+            continue;
+        }
+
+        discovered_games.push(discovered_game);
+    }
+
+    discovered_games.sort_by(|a, b| {
+        a.app_launch_config
+            .target_name()
+            .cmp(b.app_launch_config.target_name())
+    });
+
+    discovered_games
+}
+
+// The pid may have been reused by another process since the file was written
+fn is_running(discovered_game: &DiscoveredGame, discovery_file_full_path: &Path) -> bool {
+    let target_process_id = discovered_game.app_launch_config.target_process_id();
+    if !process::is_alive(target_process_id) {
+        return false;
+    }
+
+    let Some(start_time) = process::start_time(target_process_id) else {
+        return false;
+    };
+
+    fs::metadata(discovery_file_full_path)
+        .and_then(|metadata| metadata.modified())
+        .map(|modified| modified >= start_time)
+        .unwrap_or_default()
+}
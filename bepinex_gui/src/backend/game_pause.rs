@@ -0,0 +1,303 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crossbeam_channel::{Receiver, Sender};
+use sysinfo::Pid;
+
+use crate::{
+    backend::process,
+    data::bepinex_log::{BepInExLogEntry, LogLevel},
+};
+
+#[derive(Debug, Clone)]
+pub enum PauseCommand {
+    Pause,
+    Resume,
+    // Resumes the game once the duration is over
+    PauseFor(Duration),
+    // Pauses the game again once the duration is over
+    ResumeFor(Duration),
+    SetAutoPauseFilter(Option<AutoPauseFilter>),
+    // The GUI follows a new instance of the game
+    Attach(Pid),
+}
+
+// Pauses the game on the first log matching it, then disarms itself
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutoPauseFilter {
+    // That log level or a more severe one
+    pub max_level: LogLevel,
+    // Matched anywhere in the log, usually the name of a plugin
+    pub text_lowercase: String,
+}
+
+impl AutoPauseFilter {
+    pub fn matches(&self, log: &BepInExLogEntry) -> bool {
+        log.level() <= self.max_level && log.data_lowercase().contains(&self.text_lowercase)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PauseStatus {
+    pub is_paused: bool,
+    // When the game gets resumed or paused again by the pending timer
+    pub timer_deadline: Option<Instant>,
+    pub auto_pause_filter: Option<AutoPauseFilter>,
+    // The log that triggered the last auto pause
+    pub auto_pause_log: Option<String>,
+}
+
+// Runs on its own thread so that the timers are precise
+// and that the auto pause reacts even when the console tab is not the one on screen
+pub struct GamePause {
+    command_sender: Sender<PauseCommand>,
+    status_receiver: Receiver<PauseStatus>,
+    status: PauseStatus,
+    is_paused: Arc<AtomicBool>,
+}
+
+impl GamePause {
+    pub fn new(target_process_id: Pid, log_receiver: Receiver<BepInExLogEntry>) -> Self {
+        let (command_s, command_r) = crossbeam_channel::unbounded();
+        let (status_s, status_r) = crossbeam_channel::unbounded();
+
+        let is_paused = Arc::new(AtomicBool::new(false));
+
+        spawn_thread_pause_controller(
+            target_process_id,
+            command_r,
+            log_receiver,
+            status_s,
+            is_paused.clone(),
+        );
+
+        Self {
+            command_sender: command_s,
+            status_receiver: status_r,
+            status: PauseStatus::default(),
+            is_paused,
+        }
+    }
+
+    // For the threads that must know right away, a suspended game looks hung
+    pub fn is_paused_flag(&self) -> Arc<AtomicBool> {
+        self.is_paused.clone()
+    }
+
+    pub fn send(&self, command: PauseCommand) {
+        _ = self.command_sender.send(command);
+    }
+
+    pub const fn status(&self) -> &PauseStatus {
+        &self.status
+    }
+
+    pub fn update_status_receiver(&mut self) {
+        while let Ok(status) = self.status_receiver.try_recv() {
+            self.status = status;
+        }
+    }
+}
+
+struct PauseController {
+    target_process_id: Pid,
+    status: PauseStatus,
+    status_sender: Sender<PauseStatus>,
+    is_paused: Arc<AtomicBool>,
+}
+
+impl PauseController {
+    // Suspending twice would need two resumes on Windows, where each thread keeps a suspend count
+    fn pause(&mut self) {
+        if self.status.is_paused {
+            return;
+        }
+
+        self.status.is_paused = process::suspend(self.target_process_id);
+    }
+
+    fn resume(&mut self) {
+        if !self.status.is_paused {
+            return;
+        }
+
+        self.status.is_paused = !process::resume(self.target_process_id);
+    }
+
+    fn run_command(&mut self, command: PauseCommand) {
+        self.status.timer_deadline = None;
+
+        match command {
+            PauseCommand::Pause => self.pause(),
+            PauseCommand::Resume => self.resume(),
+            PauseCommand::PauseFor(duration) => {
+                self.pause();
+                if self.status.is_paused {
+                    self.status.timer_deadline = Some(Instant::now() + duration);
+                }
+            }
+            PauseCommand::ResumeFor(duration) => {
+                self.resume();
+                if !self.status.is_paused {
+                    self.status.timer_deadline = Some(Instant::now() + duration);
+                }
+            }
+            PauseCommand::SetAutoPauseFilter(auto_pause_filter) => {
+                self.status.auto_pause_filter = auto_pause_filter;
+                self.status.auto_pause_log = None;
+            }
+            PauseCommand::Attach(target_process_id) => {
+                // the previous instance may still be running, it would stay suspended forever
+                self.resume();
+
+                self.target_process_id = target_process_id;
+                self.status = PauseStatus::default();
+            }
+        }
+    }
+
+    fn on_timer(&mut self) {
+        self.status.timer_deadline = None;
+
+        if self.status.is_paused {
+            self.resume();
+        } else {
+            self.pause();
+        }
+    }
+
+    // Returns true when the log paused the game
+    fn on_log(&mut self, log: &BepInExLogEntry) -> bool {
+        let Some(auto_pause_filter) = &self.status.auto_pause_filter else {
+            return false;
+        };
+
+        if self.status.is_paused || !auto_pause_filter.matches(log) {
+            return false;
+        }
+
+        tracing::info!("Auto pausing the game on log: {}", log.data());
+
+        self.status.timer_deadline = None;
+        self.status.auto_pause_filter = None;
+        self.status.auto_pause_log = Some(log.data().to_string());
+        self.pause();
+
+        true
+    }
+
+    fn send_status(&self) {
+        self.is_paused
+            .store(self.status.is_paused, Ordering::Relaxed);
+        _ = self.status_sender.send(self.status.clone());
+    }
+}
+
+fn spawn_thread_pause_controller(
+    target_process_id: Pid,
+    command_receiver: Receiver<PauseCommand>,
+    mut log_receiver: Receiver<BepInExLogEntry>,
+    status_sender: Sender<PauseStatus>,
+    is_paused: Arc<AtomicBool>,
+) {
+    thread::spawn(move || {
+        let mut controller = PauseController {
+            target_process_id,
+            status: PauseStatus::default(),
+            status_sender,
+            is_paused,
+        };
+
+        loop {
+            let timer = controller
+                .status
+                .timer_deadline
+                .map_or_else(crossbeam_channel::never, crossbeam_channel::at);
+
+            let is_status_changed = crossbeam_channel::select! {
+                recv(command_receiver) -> command => match command {
+                    Ok(command) => {
+                        controller.run_command(command);
+                        true
+                    }
+                    // the GUI is closing
+                    Err(_) => return,
+                },
+                recv(log_receiver) -> log => match log {
+                    // most logs don't change anything
+                    Ok(log) => controller.on_log(&log),
+                    Err(_) => {
+                        log_receiver = crossbeam_channel::never();
+                        false
+                    }
+                },
+                recv(timer) -> _ => {
+                    controller.on_timer();
+                    true
+                }
+            };
+
+            if is_status_changed {
+                controller.send_status();
+            }
+        }
+    });
+}
+
+// Checked against the process state in /proc
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use std::{fs, process::Command};
+
+    use sysinfo::PidExt;
+
+    use super::*;
+
+    fn is_stopped(pid: Pid) -> bool {
+        let stat = fs::read_to_string(format!("/proc/{}/stat", pid.as_u32())).unwrap();
+        stat[stat.rfind(')').unwrap() + 1..]
+            .trim_start()
+            .starts_with('T')
+    }
+
+    #[test]
+    fn double_pause_then_single_resume_runs_the_game_again() {
+        let mut child = Command::new("sleep").arg("30").spawn().unwrap();
+        let pid = Pid::from_u32(child.id());
+
+        let (status_s, _status_r) = crossbeam_channel::unbounded();
+        let mut controller = PauseController {
+            target_process_id: pid,
+            status: PauseStatus::default(),
+            status_sender: status_s,
+            is_paused: Arc::new(AtomicBool::new(false)),
+        };
+
+        controller.run_command(PauseCommand::Pause);
+        controller.run_command(PauseCommand::PauseFor(Duration::from_secs(60)));
+        assert!(controller.status.is_paused);
+
+        controller.run_command(PauseCommand::Resume);
+        assert!(!controller.status.is_paused);
+
+        // signals are delivered asynchronously
+        let start = Instant::now();
+        while is_stopped(pid) && start.elapsed() < Duration::from_secs(5) {
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert!(!is_stopped(pid));
+
+        // resuming a running game does nothing
+        controller.run_command(PauseCommand::Resume);
+        assert!(!controller.status.is_paused);
+
+        _ = child.kill();
+        _ = child.wait();
+    }
+}
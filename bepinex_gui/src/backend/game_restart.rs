@@ -0,0 +1,290 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crossbeam_channel::Sender;
+use eframe::egui::Context;
+use sysinfo::{Pid, ProcessExt, SystemExt};
+
+use crate::{
+    app::BepInExGUI,
+    backend::{
+        game_shutdown,
+        process::{self, resource_monitor},
+    },
+    views::{
+        self,
+        actions::{self, Action},
+    },
+};
+
+// Read by BepInEx.GUI.Loader, when set it writes its pid and log socket port in that file
+// instead of launching a new GUI
+const ATTACH_FILE_ENV_VAR: &str = "BEPINEX_GUI_ATTACH_FILE";
+const GUI_PROCESS_ID_ENV_VAR: &str = "BEPINEX_GUI_PROCESS_ID";
+
+// Set by Doorstop in the game process, it would prevent Doorstop from loading BepInEx again
+const DOORSTOP_INITIALIZED_ENV_VAR: &str = "DOORSTOP_INITIALIZED";
+
+// What the mod manager or the launch script sets up for BepInEx to be injected
+const LAUNCH_ENV_VAR_PREFIXES: [&str; 2] = ["DOORSTOP_", "BEPINEX_"];
+const LAUNCH_ENV_VARS: [&str; 8] = [
+    "LD_PRELOAD",
+    "LD_LIBRARY_PATH",
+    "DYLD_INSERT_LIBRARIES",
+    "DYLD_LIBRARY_PATH",
+    "WINEDLLOVERRIDES",
+    "SteamAppId",
+    "SteamGameId",
+    "SteamOverlayGameId",
+];
+
+const GAME_EXIT_TIMEOUT: Duration = Duration::from_secs(10);
+const GAME_ATTACH_TIMEOUT: Duration = Duration::from_secs(60);
+const ATTACH_FILE_CHECK_INTERVAL: Duration = Duration::from_millis(250);
+
+// How the game was started, captured while it is alive since it can't be read after a crash
+#[derive(Clone)]
+pub struct LaunchCommand {
+    executable_full_path: PathBuf,
+    args: Vec<String>,
+    working_directory: PathBuf,
+    env_vars: Vec<(String, String)>,
+}
+
+impl LaunchCommand {
+    pub fn capture(target_process_id: Pid) -> Option<Self> {
+        let mut sys = sysinfo::System::new();
+        if !sys.refresh_process(target_process_id) {
+            return None;
+        }
+        let proc = sys.process(target_process_id)?;
+
+        let env_vars = proc
+            .environ()
+            .iter()
+            .filter_map(|env_var| env_var.split_once('='))
+            .filter(|(name, _)| is_launch_env_var(name))
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+
+        Some(Self {
+            executable_full_path: proc.exe().to_path_buf(),
+            args: proc.cmd().iter().skip(1).cloned().collect(),
+            working_directory: proc.cwd().to_path_buf(),
+            env_vars,
+        })
+    }
+}
+
+fn is_launch_env_var(name: &str) -> bool {
+    name != DOORSTOP_INITIALIZED_ENV_VAR
+        && name != ATTACH_FILE_ENV_VAR
+        && name != GUI_PROCESS_ID_ENV_VAR
+        && (LAUNCH_ENV_VARS.contains(&name)
+            || LAUNCH_ENV_VAR_PREFIXES
+                .iter()
+                .any(|prefix| name.starts_with(prefix)))
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct GameAttach {
+    pub target_process_id: Pid,
+    pub log_socket_port_receiver: u16,
+}
+
+// Written by the loader as "<pid>\n<port>"
+pub fn parse_attach_file(text: &str) -> Option<GameAttach> {
+    let mut lines = text.lines();
+
+    Some(GameAttach {
+        target_process_id: lines.next()?.trim().parse().ok()?,
+        log_socket_port_receiver: lines.next()?.trim().parse().ok()?,
+    })
+}
+
+fn attach_file_full_path() -> PathBuf {
+    std::env::temp_dir().join(format!("bepinex_gui_attach_{}.txt", std::process::id()))
+}
+
+pub fn spawn_thread_restart(
+    launch_command: LaunchCommand,
+    target_process_id: Pid,
+    shutdown_timeout: Duration,
+    game_attach_sender: Sender<io::Result<GameAttach>>,
+) {
+    thread::spawn(move || {
+        let result = restart(&launch_command, target_process_id, shutdown_timeout);
+        if let Err(err) = &result {
+            tracing::error!("Failed restarting the game: {}", err);
+        }
+
+        _ = game_attach_sender.send(result);
+    });
+}
+
+fn restart(
+    launch_command: &LaunchCommand,
+    target_process_id: Pid,
+    shutdown_timeout: Duration,
+) -> io::Result<GameAttach> {
+    // same as closing the game from the GUI, it gets a chance to save before being killed
+    let shutdown_outcome =
+        game_shutdown::shutdown(target_process_id, shutdown_timeout, &AtomicBool::new(false));
+    tracing::info!(
+        "Game shutdown outcome before restarting it: {:?}",
+        shutdown_outcome
+    );
+
+    // the game may still hold its files and sockets for a bit
+    let shutdown_time = Instant::now();
+    while process::is_alive(target_process_id) {
+        if shutdown_time.elapsed() > GAME_EXIT_TIMEOUT {
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "The game didn't exit in time",
+            ));
+        }
+
+        thread::sleep(ATTACH_FILE_CHECK_INTERVAL);
+    }
+
+    let attach_file_full_path = attach_file_full_path();
+    _ = fs::remove_file(&attach_file_full_path);
+
+    Command::new(&launch_command.executable_full_path)
+        .args(&launch_command.args)
+        .current_dir(&launch_command.working_directory)
+        .env_remove(DOORSTOP_INITIALIZED_ENV_VAR)
+        .envs(launch_command.env_vars.iter().cloned())
+        .env(ATTACH_FILE_ENV_VAR, &attach_file_full_path)
+        .env(GUI_PROCESS_ID_ENV_VAR, std::process::id().to_string())
+        .spawn()?;
+
+    wait_for_attach_file(&attach_file_full_path)
+}
+
+fn wait_for_attach_file(attach_file_full_path: &Path) -> io::Result<GameAttach> {
+    let launch_time = Instant::now();
+
+    loop {
+        if let Ok(text) = fs::read_to_string(attach_file_full_path) {
+            if let Some(game_attach) = parse_attach_file(&text) {
+                _ = fs::remove_file(attach_file_full_path);
+                return Ok(game_attach);
+            }
+        }
+
+        // Steam may have relaunched the game by itself, without our environment
+        if launch_time.elapsed() > GAME_ATTACH_TIMEOUT {
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "The game was relaunched but BepInEx.GUI.Loader never answered",
+            ));
+        }
+
+        thread::sleep(ATTACH_FILE_CHECK_INTERVAL);
+    }
+}
+
+impl BepInExGUI {
+    pub(crate) fn restart_game(&mut self, ctx: &Context) {
+        if self.game_attach_receiver.is_some() {
+            return;
+        }
+
+        let Some(launch_command) = self.launch_command.clone() else {
+            views::notifications::push(
+                ctx,
+                "The game can't be restarted, its launch command couldn't be read.",
+            );
+            return;
+        };
+
+        // the exit of the game is expected, it shouldn't close the GUI
+        if let Some(crash_watch) = &mut self.crash_watch {
+            crash_watch.stop_watching();
+        }
+        self.crash_summary = None;
+
+        let (game_attach_s, game_attach_r) = crossbeam_channel::bounded(1);
+        spawn_thread_restart(
+            launch_command,
+            self.app_launch_config.target_process_id(),
+            Duration::from_secs(self.config.game_shutdown_timeout_secs as u64),
+            game_attach_s,
+        );
+        self.game_attach_receiver = Some(game_attach_r);
+
+        views::notifications::push(ctx, "Restarting the game...");
+    }
+
+    pub(crate) fn update_game_attach_receiver(&mut self, ctx: &Context) {
+        let Some(game_attach_receiver) = &self.game_attach_receiver else {
+            return;
+        };
+
+        let Ok(result) = game_attach_receiver.try_recv() else {
+            return;
+        };
+        self.game_attach_receiver = None;
+
+        match result {
+            Ok(game_attach) => {
+                self.attach_to_game(ctx, game_attach);
+                views::notifications::push(ctx, "The game restarted.");
+            }
+            Err(err) => {
+                views::notifications::push(ctx, format!("Failed restarting the game: {err}"));
+
+                // the crash watch stopped for the restart, the old game may still be running
+                let target_process_id = self.app_launch_config.target_process_id();
+                if process::is_alive(target_process_id) {
+                    self.init_crash_watch_process(target_process_id);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn attach_to_game(&mut self, ctx: &Context, game_attach: GameAttach) {
+        tracing::info!(
+            "Attaching to the game, pid {} port {}",
+            game_attach.target_process_id,
+            game_attach.log_socket_port_receiver
+        );
+
+        self.app_launch_config.reattach(
+            game_attach.target_process_id,
+            game_attach.log_socket_port_receiver,
+        );
+
+        if let Some(log_receiver) = &self.log_receiver_thread {
+            log_receiver.set_log_socket_port_receiver(game_attach.log_socket_port_receiver);
+        }
+
+        self.init_crash_watch_process(game_attach.target_process_id);
+
+        if let Some(game_hang_watch) = &self.game_hang_watch {
+            game_hang_watch.watch(game_attach.target_process_id);
+        }
+
+        // the previous instance may still be running
+        self.should_stop_resource_monitor
+            .store(true, Ordering::Relaxed);
+        self.should_stop_resource_monitor = resource_monitor::spawn_thread_monitor(
+            game_attach.target_process_id,
+            self.resource_sample_senders.clone(),
+        );
+
+        if self.launch_command.is_none() {
+            self.launch_command = LaunchCommand::capture(game_attach.target_process_id);
+        }
+
+        actions::dispatch(ctx, Action::GameAttached);
+    }
+}
@@ -0,0 +1,155 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crossbeam_channel::{Receiver, Sender};
+use sysinfo::Pid;
+
+use crate::{app::BepInExGUI, backend::process, views::shutdown_dialog::ShutdownDialog};
+
+const EXIT_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShutdownOutcome {
+    Exited,
+    Killed,
+}
+
+// The game is first asked to quit so that it doesn't get killed in the middle of writing
+// its save files or configs, it is only killed once the timeout is over
+pub struct GameShutdown {
+    start_time: Instant,
+    timeout: Duration,
+    is_kill_requested: Arc<AtomicBool>,
+    outcome_receiver: Receiver<ShutdownOutcome>,
+}
+
+impl GameShutdown {
+    pub fn start(target_process_id: Pid, timeout: Duration) -> Self {
+        let is_kill_requested = Arc::new(AtomicBool::new(false));
+        let (outcome_s, outcome_r) = crossbeam_channel::bounded(1);
+
+        spawn_thread_shutdown(
+            target_process_id,
+            timeout,
+            is_kill_requested.clone(),
+            outcome_s,
+        );
+
+        Self {
+            start_time: Instant::now(),
+            timeout,
+            is_kill_requested,
+            outcome_receiver: outcome_r,
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.start_time.elapsed().min(self.timeout)
+    }
+
+    pub const fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    // Skips the rest of the timeout
+    pub fn kill_now(&self) {
+        self.is_kill_requested.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_kill_requested(&self) -> bool {
+        self.is_kill_requested.load(Ordering::Relaxed)
+    }
+
+    pub fn outcome(&self) -> Option<ShutdownOutcome> {
+        self.outcome_receiver.try_recv().ok()
+    }
+}
+
+fn spawn_thread_shutdown(
+    target_process_id: Pid,
+    timeout: Duration,
+    is_kill_requested: Arc<AtomicBool>,
+    outcome_sender: Sender<ShutdownOutcome>,
+) {
+    thread::spawn(move || {
+        let outcome = shutdown(target_process_id, timeout, &is_kill_requested);
+        tracing::info!("Game shutdown outcome: {:?}", outcome);
+
+        _ = outcome_sender.send(outcome);
+    });
+}
+
+// Blocks until the game is gone, or killed
+pub fn shutdown(
+    target_process_id: Pid,
+    timeout: Duration,
+    is_kill_requested: &AtomicBool,
+) -> ShutdownOutcome {
+    // a suspended game would never answer
+    process::resume(target_process_id);
+
+    if !process::request_exit(target_process_id) {
+        tracing::info!("Couldn't ask the game to quit, killing it");
+        process::kill(target_process_id, || {});
+        return ShutdownOutcome::Killed;
+    }
+
+    let request_time = Instant::now();
+    while process::is_alive(target_process_id) {
+        if request_time.elapsed() >= timeout || is_kill_requested.load(Ordering::Relaxed) {
+            tracing::info!("The game didn't quit in time, killing it");
+            process::kill(target_process_id, || {});
+            return ShutdownOutcome::Killed;
+        }
+
+        thread::sleep(EXIT_CHECK_INTERVAL);
+    }
+
+    ShutdownOutcome::Exited
+}
+
+impl BepInExGUI {
+    pub(crate) fn kill_game(&mut self) {
+        if self.game_shutdown.is_some() {
+            return;
+        }
+
+        if self.config.confirm_kill_game {
+            self.shutdown_dialog = Some(ShutdownDialog::default());
+        } else {
+            self.start_game_shutdown();
+        }
+    }
+
+    pub(crate) fn start_game_shutdown(&mut self) {
+        // the exit of the game is expected, it's not a crash
+        if let Some(crash_watch) = &mut self.crash_watch {
+            crash_watch.stop_watching();
+        }
+
+        self.game_shutdown = Some(GameShutdown::start(
+            self.app_launch_config.target_process_id(),
+            Duration::from_secs(self.config.game_shutdown_timeout_secs as u64),
+        ));
+    }
+
+    pub(crate) fn update_game_shutdown(&mut self) {
+        let Some(game_shutdown) = &self.game_shutdown else {
+            return;
+        };
+
+        if game_shutdown.outcome().is_none() {
+            return;
+        }
+        self.game_shutdown = None;
+
+        tracing::info!("Exiting because the game got closed from the GUI.");
+        self.should_exit_app.store(true, Ordering::Relaxed);
+    }
+}
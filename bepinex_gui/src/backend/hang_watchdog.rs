@@ -0,0 +1,543 @@
+use std::{
+    collections::VecDeque,
+    env,
+    fmt::Write,
+    fs, io,
+    path::{Path, PathBuf},
+    process::{exit, Command},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crossbeam_channel::{Receiver, Sender};
+use sysinfo::Pid;
+
+use crate::{config, data::bepinex_log::BepInExLogEntry, paths};
+
+use super::process;
+
+const HANG_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+// The OS already waits a few seconds before flagging a window as hung
+const GUI_HANG_CHECK_COUNT: u32 = 4;
+
+// When restarting doesn't fix the GUI there is no point in looping
+const MAX_RESTART_COUNT: usize = 2;
+const RESTART_WINDOW: Duration = Duration::from_secs(10 * 60);
+
+// Kept in the hang report, the header only shows the end of them
+const LAST_LOG_COUNT: usize = 20;
+
+// Same as the delay before Windows considers a window hung
+#[cfg(target_os = "linux")]
+const NO_PROGRESS_HANG_DELAY: Duration = Duration::from_secs(5);
+
+// A main thread stuck in a loop keeps using the cpu, only the silence of the logs tells it apart
+// from a busy game, so it needs a lot longer
+#[cfg(target_os = "linux")]
+const BUSY_LOOP_HANG_DELAY: Duration = Duration::from_secs(30);
+#[cfg(target_os = "linux")]
+const BUSY_LOOP_MIN_CPU_USAGE: f64 = 0.95;
+
+const HANG_REPORTS_FOLDER_NAME: &str = "hang_reports";
+// Oldest hang reports are deleted past that
+const MAX_HANG_REPORT_COUNT: usize = 15;
+const RESTART_HISTORY_FILE_NAME: &str = "hang_restarts.txt";
+
+// Set by the game hang watch, paused included,
+// a GUI stuck at the same time is most likely waiting on the game
+static IS_GAME_NOT_RESPONDING: AtomicBool = AtomicBool::new(false);
+
+// Bad serialized app settings can sometimes make the gui window not respond,
+// the GUI is then restarted with its settings moved aside
+pub fn spawn_thread() {
+    thread::spawn(|| {
+        let current_process_id = Pid::from(std::process::id() as usize);
+
+        let mut hung_check_count = 0;
+        loop {
+            thread::sleep(HANG_CHECK_INTERVAL);
+
+            if !process::is_hung(current_process_id) {
+                hung_check_count = 0;
+                continue;
+            }
+
+            hung_check_count += 1;
+            // only once per hang
+            if hung_check_count == GUI_HANG_CHECK_COUNT {
+                on_gui_hang(current_process_id);
+            }
+        }
+    });
+}
+
+fn on_gui_hang(current_process_id: Pid) {
+    // restarting wouldn't help, the GUI comes back once the game does
+    if IS_GAME_NOT_RESPONDING.load(Ordering::Relaxed) {
+        tracing::warn!("The GUI is not responding while the game isn't either, waiting for it");
+        write_gui_hang_report(
+            current_process_id,
+            "The GUI is not responding while the game isn't either",
+        );
+        return;
+    }
+
+    tracing::error!("The GUI is not responding");
+    write_gui_hang_report(current_process_id, "The GUI is not responding");
+
+    if !try_record_restart() {
+        tracing::error!(
+            "The GUI already restarted {} times in the last {} minutes because of hangs, not restarting it again",
+            MAX_RESTART_COUNT,
+            RESTART_WINDOW.as_secs() / 60
+        );
+        return;
+    }
+
+    backup_app_ron_file();
+    restart_gui();
+}
+
+// The thread dump shows where the process got stuck
+fn write_hang_report(
+    target_process_id: Pid,
+    report_name: &str,
+    cause: &str,
+) -> io::Result<PathBuf> {
+    let unix_timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let hang_reports_folder_full_path = paths::get_app_config_directory()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No app config directory"))?
+        .join(HANG_REPORTS_FOLDER_NAME);
+    let report_folder_full_path =
+        hang_reports_folder_full_path.join(format!("{report_name}_{unix_timestamp}"));
+    fs::create_dir_all(&report_folder_full_path)?;
+
+    delete_old_hang_reports(&hang_reports_folder_full_path);
+
+    let mut summary = String::new();
+    _ = writeln!(summary, "{cause}");
+    _ = writeln!(summary, "GUI version: {}", env!("CARGO_PKG_VERSION"));
+    _ = writeln!(
+        summary,
+        "GUI arguments: {:?}",
+        env::args().collect::<Vec<_>>()
+    );
+    _ = writeln!(
+        summary,
+        "Threads of process {}: {:?}",
+        target_process_id,
+        process::thread_ids(target_process_id)
+    );
+    fs::write(report_folder_full_path.join("hang_summary.txt"), summary)?;
+
+    // the rest of the report is still useful without it
+    if let Err(err) = process::write_thread_dump(
+        target_process_id,
+        &report_folder_full_path.join(format!("threads.{}", process::THREAD_DUMP_EXTENSION)),
+    ) {
+        tracing::error!("Failed writing the thread dump: {}", err);
+    }
+
+    Ok(report_folder_full_path)
+}
+
+// Report names come from the game, so the folders are ordered by their <unix> suffix
+fn delete_old_hang_reports(hang_reports_folder_full_path: &Path) {
+    let Ok(entries) = fs::read_dir(hang_reports_folder_full_path) else {
+        return;
+    };
+
+    let mut hang_reports: Vec<(u64, PathBuf)> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .filter_map(|path| {
+            let unix_timestamp = path
+                .file_name()?
+                .to_str()?
+                .rsplit_once('_')?
+                .1
+                .parse()
+                .ok()?;
+            Some((unix_timestamp, path))
+        })
+        .collect();
+
+    hang_reports.sort_by(|a, b| b.cmp(a));
+
+    for (_, old_hang_report_full_path) in hang_reports.iter().skip(MAX_HANG_REPORT_COUNT) {
+        if let Err(err) = fs::remove_dir_all(old_hang_report_full_path) {
+            tracing::error!("Failed deleting old hang report: {}", err);
+        }
+    }
+}
+
+// The settings are the usual suspect, they are kept as they were when the GUI hung
+fn write_gui_hang_report(current_process_id: Pid, cause: &str) {
+    let result = write_hang_report(current_process_id, "gui_hang", cause).and_then(
+        |report_folder_full_path| {
+            if let Some(app_ron_file_full_path) = config::get_app_ron_file_full_path() {
+                if app_ron_file_full_path.exists() {
+                    fs::copy(
+                        app_ron_file_full_path,
+                        report_folder_full_path.join("app.ron"),
+                    )?;
+                }
+            }
+
+            Ok(report_folder_full_path)
+        },
+    );
+
+    match result {
+        Ok(report_folder_full_path) => {
+            tracing::info!("Hang report written to {:?}", report_folder_full_path);
+        }
+        Err(err) => tracing::error!("Failed writing the hang report: {}", err),
+    }
+}
+
+// Returns false when the GUI already restarted too many times recently
+fn try_record_restart() -> bool {
+    let Some(restart_history_file_full_path) = paths::get_app_config_directory()
+        .map(|directory| directory.join(RESTART_HISTORY_FILE_NAME))
+    else {
+        return true;
+    };
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let mut restart_times: Vec<u64> = fs::read_to_string(&restart_history_file_full_path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| line.trim().parse().ok())
+        .filter(|restart_time| now.saturating_sub(*restart_time) < RESTART_WINDOW.as_secs())
+        .collect();
+
+    if restart_times.len() >= MAX_RESTART_COUNT {
+        return false;
+    }
+
+    restart_times.push(now);
+    let restart_history = restart_times
+        .iter()
+        .map(u64::to_string)
+        .collect::<Vec<_>>()
+        .join("\n");
+    if let Err(err) = fs::write(restart_history_file_full_path, restart_history) {
+        tracing::error!("Failed writing the restart history: {}", err);
+    }
+
+    true
+}
+
+// Moved aside instead of deleted so that the user can get their settings back
+fn backup_app_ron_file() {
+    let Some(app_ron_file_full_path) = config::get_app_ron_file_full_path() else {
+        return;
+    };
+
+    let backup_file_full_path = app_ron_file_full_path.with_extension("ron.hang.bak");
+    match fs::rename(&app_ron_file_full_path, &backup_file_full_path) {
+        Ok(_) => tracing::info!("Settings moved to {:?}", backup_file_full_path),
+        Err(err) => tracing::error!("Failed backing up the settings: {}", err),
+    }
+}
+
+fn restart_gui() {
+    let current_exe = env::current_exe().expect("Failed to retrieve current executable path");
+
+    let args: Vec<String> = env::args().collect();
+
+    let mut command = Command::new(current_exe);
+    command.args(args[1..].iter());
+
+    match command.spawn() {
+        Ok(_) => {
+            exit(0);
+        }
+        Err(err) => {
+            tracing::error!("{}", err);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GameHangStatus {
+    // None while the game responds
+    pub hung_since: Option<Instant>,
+    pub report_folder_full_path: Option<PathBuf>,
+    // The logs right before the freeze, usually from the mod that got stuck
+    pub last_logs: Vec<String>,
+}
+
+// A hung game is only reported, the user knows best whether it's worth waiting for
+pub struct GameHangWatch {
+    target_process_id_sender: Sender<Pid>,
+    status_receiver: Receiver<GameHangStatus>,
+    status: GameHangStatus,
+}
+
+impl GameHangWatch {
+    pub fn new(
+        target_process_id: Pid,
+        is_game_paused: Arc<AtomicBool>,
+        log_receiver: Receiver<BepInExLogEntry>,
+    ) -> Self {
+        let (target_process_id_s, target_process_id_r) = crossbeam_channel::unbounded();
+        let (status_s, status_r) = crossbeam_channel::unbounded();
+
+        spawn_thread_watch_game(
+            GameHangWatcher::new(target_process_id, is_game_paused, status_s),
+            target_process_id_r,
+            log_receiver,
+        );
+
+        Self {
+            target_process_id_sender: target_process_id_s,
+            status_receiver: status_r,
+            status: GameHangStatus::default(),
+        }
+    }
+
+    // The GUI follows a new instance of the game
+    pub fn watch(&self, target_process_id: Pid) {
+        _ = self.target_process_id_sender.send(target_process_id);
+    }
+
+    pub const fn status(&self) -> &GameHangStatus {
+        &self.status
+    }
+
+    pub fn update(&mut self) {
+        while let Ok(status) = self.status_receiver.try_recv() {
+            self.status = status;
+        }
+    }
+}
+
+// Linux has no equivalent to IsHungAppWindow.
+// The main thread of a deadlocked game stops using the cpu and the game stops logging,
+// the main thread of a game stuck in a loop uses a whole core and the game stops logging
+#[cfg(target_os = "linux")]
+struct GameProgress {
+    main_thread_cpu_ticks: Option<u64>,
+    last_check_time: Instant,
+    last_progress_time: Instant,
+    last_log_time: Instant,
+    // since when the main thread uses a whole core
+    busy_since: Option<Instant>,
+}
+
+#[cfg(target_os = "linux")]
+impl GameProgress {
+    fn new() -> Self {
+        Self {
+            main_thread_cpu_ticks: None,
+            last_check_time: Instant::now(),
+            last_progress_time: Instant::now(),
+            last_log_time: Instant::now(),
+            busy_since: None,
+        }
+    }
+
+    // A log, or anything else proving the game isn't stuck
+    fn on_progress(&mut self) {
+        self.last_progress_time = Instant::now();
+        self.last_log_time = Instant::now();
+        self.busy_since = None;
+    }
+
+    fn is_stalled(&mut self, target_process_id: Pid) -> bool {
+        // gone, the crash watch takes it from there
+        let Some(main_thread_cpu_ticks) = process::main_thread_cpu_ticks(target_process_id) else {
+            return false;
+        };
+
+        let check_time = Instant::now();
+        let elapsed_ticks = check_time
+            .duration_since(self.last_check_time)
+            .as_secs_f64()
+            * process::clock_ticks_per_second() as f64;
+        self.last_check_time = check_time;
+
+        if let Some(last_main_thread_cpu_ticks) = self.main_thread_cpu_ticks {
+            let used_ticks = main_thread_cpu_ticks.saturating_sub(last_main_thread_cpu_ticks);
+
+            if used_ticks > 0 {
+                self.last_progress_time = check_time;
+            }
+
+            if used_ticks as f64 >= elapsed_ticks * BUSY_LOOP_MIN_CPU_USAGE {
+                self.busy_since.get_or_insert(check_time);
+            } else {
+                self.busy_since = None;
+            }
+        }
+        self.main_thread_cpu_ticks = Some(main_thread_cpu_ticks);
+
+        let is_deadlocked = self.last_progress_time.elapsed() >= NO_PROGRESS_HANG_DELAY;
+
+        let is_busy_looping = self
+            .busy_since
+            .is_some_and(|busy_since| busy_since.elapsed() >= BUSY_LOOP_HANG_DELAY)
+            && self.last_log_time.elapsed() >= BUSY_LOOP_HANG_DELAY;
+
+        is_deadlocked || is_busy_looping
+    }
+}
+
+struct GameHangWatcher {
+    target_process_id: Pid,
+    is_game_paused: Arc<AtomicBool>,
+    status: GameHangStatus,
+    status_sender: Sender<GameHangStatus>,
+    last_logs: VecDeque<String>,
+    #[cfg(target_os = "linux")]
+    progress: GameProgress,
+}
+
+impl GameHangWatcher {
+    fn new(
+        target_process_id: Pid,
+        is_game_paused: Arc<AtomicBool>,
+        status_sender: Sender<GameHangStatus>,
+    ) -> Self {
+        Self {
+            target_process_id,
+            is_game_paused,
+            status: GameHangStatus::default(),
+            status_sender,
+            last_logs: VecDeque::new(),
+            #[cfg(target_os = "linux")]
+            progress: GameProgress::new(),
+        }
+    }
+
+    fn attach(&mut self, target_process_id: Pid) {
+        self.target_process_id = target_process_id;
+        self.status = GameHangStatus::default();
+        self.last_logs.clear();
+        #[cfg(target_os = "linux")]
+        {
+            self.progress = GameProgress::new();
+        }
+
+        IS_GAME_NOT_RESPONDING.store(false, Ordering::Relaxed);
+        self.send_status();
+    }
+
+    fn on_log(&mut self, log: &BepInExLogEntry) {
+        self.last_logs.push_back(log.data().to_string());
+        if self.last_logs.len() > LAST_LOG_COUNT {
+            self.last_logs.pop_front();
+        }
+
+        #[cfg(target_os = "linux")]
+        self.progress.on_progress();
+    }
+
+    #[cfg(target_os = "linux")]
+    fn is_not_responding(&mut self) -> bool {
+        self.progress.is_stalled(self.target_process_id)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn is_not_responding(&mut self) -> bool {
+        process::is_hung(self.target_process_id)
+    }
+
+    fn check(&mut self) {
+        let is_not_responding = self.is_not_responding();
+        IS_GAME_NOT_RESPONDING.store(is_not_responding, Ordering::Relaxed);
+
+        // a game paused from the GUI doesn't answer either
+        let is_paused = self.is_game_paused.load(Ordering::Relaxed);
+        #[cfg(target_os = "linux")]
+        if is_paused {
+            self.progress.on_progress();
+        }
+
+        let is_hung = is_not_responding && !is_paused;
+        if is_hung == self.status.hung_since.is_some() {
+            return;
+        }
+
+        if !is_hung {
+            tracing::info!("The game responds again");
+            self.status = GameHangStatus::default();
+            self.send_status();
+            return;
+        }
+
+        tracing::warn!("The game is not responding");
+        self.status.hung_since = Some(Instant::now());
+        self.status.last_logs = self.last_logs.iter().cloned().collect();
+        self.send_status();
+
+        // taken before the user decides to kill the game
+        match self.write_report() {
+            Ok(report_folder_full_path) => {
+                tracing::info!("Hang report written to {:?}", report_folder_full_path);
+                self.status.report_folder_full_path = Some(report_folder_full_path);
+                self.send_status();
+            }
+            Err(err) => tracing::error!("Failed writing the hang report: {}", err),
+        }
+    }
+
+    fn write_report(&self) -> io::Result<PathBuf> {
+        let report_folder_full_path = write_hang_report(
+            self.target_process_id,
+            "game_hang",
+            "The game is not responding",
+        )?;
+
+        fs::write(
+            report_folder_full_path.join("last_logs.log"),
+            self.status.last_logs.join("\n"),
+        )?;
+
+        Ok(report_folder_full_path)
+    }
+
+    fn send_status(&self) {
+        _ = self.status_sender.send(self.status.clone());
+    }
+}
+
+fn spawn_thread_watch_game(
+    mut watcher: GameHangWatcher,
+    target_process_id_receiver: Receiver<Pid>,
+    mut log_receiver: Receiver<BepInExLogEntry>,
+) {
+    thread::spawn(move || {
+        let ticker = crossbeam_channel::tick(HANG_CHECK_INTERVAL);
+
+        loop {
+            crossbeam_channel::select! {
+                recv(target_process_id_receiver) -> target_process_id => match target_process_id {
+                    Ok(target_process_id) => watcher.attach(target_process_id),
+                    // the GUI is closing
+                    Err(_) => return,
+                },
+                recv(log_receiver) -> log => match log {
+                    Ok(log) => watcher.on_log(&log),
+                    Err(_) => log_receiver = crossbeam_channel::never(),
+                },
+                recv(ticker) -> _ => watcher.check(),
+            }
+        }
+    });
+}
@@ -2,13 +2,21 @@ use std::sync::atomic::Ordering;
 
 use eframe::Frame;
 
-use crate::app::BepInExGUI;
+use crate::{app::BepInExGUI, views::crash_summary::CrashSummary};
 
+pub mod alert;
+pub mod assembly_metadata;
+pub mod atomic_file;
 pub mod file_explorer_utils;
+pub mod file_watcher;
+pub mod game_discovery;
+pub mod game_pause;
+pub mod game_restart;
+pub mod game_shutdown;
+pub mod hang_watchdog;
 pub mod network;
 mod panic_handler;
 pub mod process;
-mod reset_app_if_window_hang;
 pub mod thunderstore;
 pub mod window;
 
@@ -21,14 +29,66 @@ impl BepInExGUI {
             self.is_window_title_set = true;
         }
 
+        self.update_crash_watch();
+
+        self.update_game_shutdown();
+
+        if let Some(game_hang_watch) = &mut self.game_hang_watch {
+            game_hang_watch.update();
+
+            if game_hang_watch.status().hung_since.is_none() {
+                self.is_game_hang_dialog_dismissed = false;
+            }
+        }
+
         if self.should_exit_app.load(Ordering::Relaxed) {
             frame.close();
         }
     }
+
+    fn update_crash_watch(&mut self) {
+        let Some(crash_watch) = &mut self.crash_watch else {
+            return;
+        };
+
+        if let Some(process_exit) = crash_watch.update() {
+            let (crash_report_s, crash_report_r) = crossbeam_channel::bounded(1);
+            crash_watch.spawn_thread_make_report(
+                process_exit,
+                self.app_launch_config.target_name().to_string(),
+                self.app_launch_config.game_folder_full_path().to_path_buf(),
+                crash_report_s,
+            );
+            self.crash_report_receiver = Some(crash_report_r);
+        }
+
+        if let Some(crash_report_receiver) = &self.crash_report_receiver {
+            if let Ok(crash_report) = crash_report_receiver.try_recv() {
+                if crash_report.is_crash() {
+                    tracing::error!("{}", crash_report.summary());
+                    self.crash_summary = Some(CrashSummary::new(crash_report));
+                }
+
+                self.crash_report_receiver = None;
+            }
+        }
+
+        // the crash summary keeps the window open, so wait for the report before closing it
+        if crash_watch.process_exit().is_some()
+            && self.crash_report_receiver.is_none()
+            && self.crash_summary.is_none()
+            && self
+                .config
+                .close_window_when_game_closes
+                .load(Ordering::Relaxed)
+        {
+            self.should_exit_app.store(true, Ordering::Relaxed);
+        }
+    }
 }
 
 pub fn init() {
     panic_handler::init();
 
-    reset_app_if_window_hang::spawn_thread();
+    hang_watchdog::spawn_thread();
 }
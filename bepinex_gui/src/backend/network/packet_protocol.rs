@@ -1,12 +1,52 @@
-use byteorder::{NativeEndian, ReadBytesExt};
+use byteorder::{NativeEndian, ReadBytesExt, WriteBytesExt};
 
-use std::io::{Cursor, Read};
+use std::io::{Cursor, Read, Write};
 
 use std::mem::size_of;
 use std::net::TcpStream;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use crate::data::bepinex_log::LogLevel;
 
+// Must match BepInEx.GUI.Loader LogPacket.cs.
+// Never a valid packet length, which tells the handshake answer apart
+// from the first packet of a loader that doesn't know about the handshake
+const HANDSHAKE_MARKER: u32 = u32::MAX;
+
+// [length][log level][log string]
+pub const LEGACY_PROTOCOL_VERSION: u32 = 0;
+// [length][log level][unix timestamp ms][log string]
+pub const TIMESTAMP_PROTOCOL_VERSION: u32 = 1;
+
+const PROTOCOL_VERSION: u32 = TIMESTAMP_PROTOCOL_VERSION;
+
+pub enum Handshake {
+    Accepted { protocol_version: u32 },
+    // Older loader, the packets use the legacy protocol and the first one is already being read
+    Ignored { first_packet_length: usize },
+}
+
+// Older loaders never read from the socket, the handshake just sits there
+pub fn handshake(tcp_stream: &mut TcpStream) -> Result<Handshake, std::io::Error> {
+    let mut handshake_bytes = Vec::with_capacity(2 * size_of::<u32>());
+    handshake_bytes.write_u32::<NativeEndian>(HANDSHAKE_MARKER)?;
+    handshake_bytes.write_u32::<NativeEndian>(PROTOCOL_VERSION)?;
+    tcp_stream.write_all(&handshake_bytes)?;
+
+    let mut received_bytes = read_packet_internal(tcp_stream, size_of::<u32>())?;
+    let marker_or_packet_length = Cursor::new(&mut received_bytes).read_u32::<NativeEndian>()?;
+    if marker_or_packet_length != HANDSHAKE_MARKER {
+        return Ok(Handshake::Ignored {
+            first_packet_length: marker_or_packet_length as usize,
+        });
+    }
+
+    let mut received_bytes = read_packet_internal(tcp_stream, size_of::<u32>())?;
+    let protocol_version = Cursor::new(&mut received_bytes).read_u32::<NativeEndian>()?;
+
+    Ok(Handshake::Accepted { protocol_version })
+}
+
 pub fn read_packet_length(tcp_stream: &mut TcpStream) -> Result<usize, std::io::Error> {
     const HEADER_SIZE: usize = size_of::<u32>();
 
@@ -30,6 +70,14 @@ pub fn read_packet_log_level(tcp_stream: &mut TcpStream) -> Result<LogLevel, std
     }
 }
 
+pub fn read_packet_timestamp(tcp_stream: &mut TcpStream) -> Result<SystemTime, std::io::Error> {
+    let mut received_bytes = read_packet_internal(tcp_stream, size_of::<i64>())?;
+
+    let unix_timestamp_ms = Cursor::new(&mut received_bytes).read_i64::<NativeEndian>()?;
+
+    Ok(UNIX_EPOCH + Duration::from_millis(unix_timestamp_ms.max(0) as u64))
+}
+
 pub fn read_packet(
     tcp_stream: &mut TcpStream,
     size_to_read: usize,
@@ -68,3 +116,105 @@ fn read_packet_internal(
 pub fn packet_bytes_to_utf8_string(packet_bytes: &[u8]) -> String {
     unsafe { std::str::from_utf8_unchecked(packet_bytes).to_string() }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::net::{Ipv4Addr, TcpListener};
+    use std::thread;
+
+    use super::*;
+
+    fn legacy_packet(log_level: i32, log_string: &str) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes
+            .write_u32::<NativeEndian>(log_string.len() as u32)
+            .unwrap();
+        bytes.write_i32::<NativeEndian>(log_level).unwrap();
+        bytes.extend_from_slice(log_string.as_bytes());
+        bytes
+    }
+
+    // Plays the loader side of the socket
+    fn connect_to_loader(loader: impl FnOnce(TcpStream) + Send + 'static) -> TcpStream {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || loader(listener.accept().unwrap().0));
+
+        TcpStream::connect(address).unwrap()
+    }
+
+    #[test]
+    fn handshake_with_loader_that_ignores_it() {
+        let mut tcp_stream = connect_to_loader(|mut loader_stream| {
+            loader_stream
+                .write_all(&legacy_packet(
+                    LogLevel::Info as i32,
+                    "[Info   :   BepInEx] Hello",
+                ))
+                .unwrap();
+        });
+
+        let Handshake::Ignored {
+            first_packet_length,
+        } = handshake(&mut tcp_stream).unwrap()
+        else {
+            panic!("the legacy loader never answers the handshake");
+        };
+
+        assert_eq!(
+            read_packet_log_level(&mut tcp_stream).unwrap(),
+            LogLevel::Info
+        );
+        let packet_bytes = read_packet(&mut tcp_stream, first_packet_length).unwrap();
+        assert_eq!(
+            packet_bytes_to_utf8_string(&packet_bytes),
+            "[Info   :   BepInEx] Hello"
+        );
+    }
+
+    #[test]
+    fn handshake_with_loader_that_answers_it() {
+        let mut tcp_stream = connect_to_loader(|mut loader_stream| {
+            let received_bytes = read_packet_internal(&mut loader_stream, 8).unwrap();
+            let mut cursor = Cursor::new(received_bytes);
+            assert_eq!(cursor.read_u32::<NativeEndian>().unwrap(), HANDSHAKE_MARKER);
+            assert_eq!(cursor.read_u32::<NativeEndian>().unwrap(), PROTOCOL_VERSION);
+
+            let mut answer_bytes = Vec::new();
+            answer_bytes
+                .write_u32::<NativeEndian>(HANDSHAKE_MARKER)
+                .unwrap();
+            answer_bytes
+                .write_u32::<NativeEndian>(TIMESTAMP_PROTOCOL_VERSION)
+                .unwrap();
+            answer_bytes
+                .write_u32::<NativeEndian>("Hello".len() as u32)
+                .unwrap();
+            answer_bytes
+                .write_i32::<NativeEndian>(LogLevel::Warning as i32)
+                .unwrap();
+            answer_bytes
+                .write_i64::<NativeEndian>(1_700_000_000_123)
+                .unwrap();
+            answer_bytes.extend_from_slice(b"Hello");
+            loader_stream.write_all(&answer_bytes).unwrap();
+        });
+
+        let Handshake::Accepted { protocol_version } = handshake(&mut tcp_stream).unwrap() else {
+            panic!("the loader answered the handshake");
+        };
+        assert_eq!(protocol_version, TIMESTAMP_PROTOCOL_VERSION);
+
+        let packet_length = read_packet_length(&mut tcp_stream).unwrap();
+        assert_eq!(
+            read_packet_log_level(&mut tcp_stream).unwrap(),
+            LogLevel::Warning
+        );
+        assert_eq!(
+            read_packet_timestamp(&mut tcp_stream).unwrap(),
+            UNIX_EPOCH + Duration::from_millis(1_700_000_000_123)
+        );
+        let packet_bytes = read_packet(&mut tcp_stream, packet_length).unwrap();
+        assert_eq!(packet_bytes_to_utf8_string(&packet_bytes), "Hello");
+    }
+}
@@ -0,0 +1,327 @@
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use sysinfo::Pid;
+use sysinfo::PidExt;
+
+use super::{ExitStatus, ProcessControl};
+
+pub struct LinuxProcessControl;
+
+impl ProcessControl for LinuxProcessControl {
+    // SIGSTOP can't be caught or ignored by the game, unlike SIGTSTP
+    fn suspend(&self, target_process_id: Pid) -> bool {
+        send_signal(target_process_id, libc::SIGSTOP)
+    }
+
+    fn resume(&self, target_process_id: Pid) -> bool {
+        send_signal(target_process_id, libc::SIGCONT)
+    }
+
+    fn thread_ids(&self, target_process_id: Pid) -> Vec<u32> {
+        let Ok(task_dir) = fs::read_dir(format!("/proc/{}/task", target_process_id.as_u32()))
+        else {
+            return Vec::new();
+        };
+
+        task_dir
+            .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse().ok())
+            .collect()
+    }
+
+    fn is_alive(&self, target_process_id: Pid) -> bool {
+        match is_alive_pidfd(target_process_id) {
+            Some(is_alive) => is_alive,
+            None => is_alive_proc_stat(target_process_id),
+        }
+    }
+
+    fn handle_count(&self, target_process_id: Pid) -> Option<u32> {
+        let fd_dir = fs::read_dir(format!("/proc/{}/fd", target_process_id.as_u32())).ok()?;
+
+        Some(fd_dir.count() as u32)
+    }
+
+    fn wait_for_exit(&self, target_process_id: Pid) -> ExitStatus {
+        if !wait_for_exit_pidfd(target_process_id) {
+            return super::wait_for_exit_polling(target_process_id);
+        }
+
+        exit_status_proc_stat(target_process_id)
+    }
+
+    // There is no equivalent to IsHungAppWindow that works for every window manager
+    fn is_hung(&self, _target_process_id: Pid) -> bool {
+        false
+    }
+
+    // Unity saves and quits on SIGTERM, like when its window is closed
+    fn request_exit(&self, target_process_id: Pid) -> bool {
+        send_signal(target_process_id, libc::SIGTERM)
+    }
+
+    // The kernel side of each thread, the stack file is only readable by root
+    fn write_thread_dump(&self, target_process_id: Pid, dump_full_path: &Path) -> io::Result<()> {
+        let mut thread_dump = String::new();
+
+        for thread_id in self.thread_ids(target_process_id) {
+            let task_dir = format!("/proc/{}/task/{}", target_process_id.as_u32(), thread_id);
+            let read = |file_name: &str| {
+                fs::read_to_string(format!("{task_dir}/{file_name}"))
+                    .map(|text| text.trim().to_string())
+                    .unwrap_or_default()
+            };
+
+            let stat = read("stat");
+            let state = stat
+                .rfind(')')
+                .and_then(|i| stat[i + 1..].trim_start().chars().next())
+                .unwrap_or('?');
+
+            _ = writeln!(
+                thread_dump,
+                "Thread {} \"{}\" state {} waiting in {}",
+                thread_id,
+                read("comm"),
+                state,
+                read("wchan")
+            );
+
+            let stack = read("stack");
+            if !stack.is_empty() {
+                _ = writeln!(thread_dump, "{stack}");
+            }
+            thread_dump.push('\n');
+        }
+
+        fs::write(dump_full_path, thread_dump)
+    }
+}
+
+// utime + stime of the main thread in clock ticks, its thread id is the process id
+pub fn main_thread_cpu_ticks(target_process_id: Pid) -> Option<u64> {
+    let stat = fs::read_to_string(format!(
+        "/proc/{0}/task/{0}/stat",
+        target_process_id.as_u32()
+    ))
+    .ok()?;
+
+    // fields after the process name start at the state, the 3rd one, utime and stime are the 14th and 15th
+    let mut fields = stat[stat.rfind(')')? + 1..].split_whitespace().skip(14 - 3);
+    let utime: u64 = fields.next()?.parse().ok()?;
+    let stime: u64 = fields.next()?.parse().ok()?;
+
+    Some(utime + stime)
+}
+
+// Unit of the cpu times in /proc, usually 100
+pub fn clock_ticks_per_second() -> u64 {
+    let clock_ticks_per_second = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    if clock_ticks_per_second <= 0 {
+        return 100;
+    }
+
+    clock_ticks_per_second as u64
+}
+
+fn send_signal(target_process_id: Pid, signal: libc::c_int) -> bool {
+    let result = unsafe { libc::kill(target_process_id.as_u32() as libc::pid_t, signal) };
+    if result != 0 {
+        tracing::error!(
+            "Failed sending signal {} to process {}: {}",
+            signal,
+            target_process_id,
+            std::io::Error::last_os_error()
+        );
+        return false;
+    }
+
+    true
+}
+
+// A pidfd becomes readable once the process exits, and unlike a pid it can't be reused.
+// None when the kernel is too old for pidfd_open (before 5.3)
+fn is_alive_pidfd(target_process_id: Pid) -> Option<bool> {
+    unsafe {
+        let pidfd = libc::syscall(
+            libc::SYS_pidfd_open,
+            target_process_id.as_u32() as libc::pid_t,
+            0,
+        );
+        if pidfd < 0 {
+            return match std::io::Error::last_os_error().raw_os_error() {
+                Some(libc::ESRCH) => Some(false),
+                _ => None,
+            };
+        }
+        let pidfd = pidfd as libc::c_int;
+
+        let mut poll_fd = libc::pollfd {
+            fd: pidfd,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        let ready_count = libc::poll(std::ptr::addr_of_mut!(poll_fd), 1, 0);
+        libc::close(pidfd);
+
+        if ready_count < 0 {
+            return None;
+        }
+
+        Some(ready_count == 0)
+    }
+}
+
+// False when pidfd_open isn't available, or the process is already gone
+fn wait_for_exit_pidfd(target_process_id: Pid) -> bool {
+    unsafe {
+        let pidfd = libc::syscall(
+            libc::SYS_pidfd_open,
+            target_process_id.as_u32() as libc::pid_t,
+            0,
+        );
+        if pidfd < 0 {
+            return false;
+        }
+        let pidfd = pidfd as libc::c_int;
+
+        let mut poll_fd = libc::pollfd {
+            fd: pidfd,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        // interrupted by signals sometimes
+        while libc::poll(std::ptr::addr_of_mut!(poll_fd), 1, -1) < 0 {
+            if std::io::Error::last_os_error().kind() != std::io::ErrorKind::Interrupted {
+                break;
+            }
+        }
+        libc::close(pidfd);
+
+        true
+    }
+}
+
+// Only readable while the process is a zombie, before its parent reaps it.
+// The GUI is rarely that parent, so this is mostly Unknown
+fn exit_status_proc_stat(target_process_id: Pid) -> ExitStatus {
+    let Ok(stat) = fs::read_to_string(format!("/proc/{}/stat", target_process_id.as_u32())) else {
+        return ExitStatus::Unknown;
+    };
+
+    // fields after the process name start at the state, the 3rd one, exit_code is the 52nd
+    let exit_code = stat.rfind(')').and_then(|i| {
+        stat[i + 1..]
+            .split_whitespace()
+            .nth(52 - 3)?
+            .parse::<libc::c_int>()
+            .ok()
+    });
+
+    match exit_code {
+        Some(exit_code) if libc::WIFSIGNALED(exit_code) => {
+            ExitStatus::Signal(libc::WTERMSIG(exit_code))
+        }
+        Some(exit_code) if libc::WIFEXITED(exit_code) => {
+            ExitStatus::Code(libc::WEXITSTATUS(exit_code) as u32)
+        }
+        _ => ExitStatus::Unknown,
+    }
+}
+
+// Zombies still have their /proc entry until the parent reaps them
+fn is_alive_proc_stat(target_process_id: Pid) -> bool {
+    let Ok(stat) = fs::read_to_string(format!("/proc/{}/stat", target_process_id.as_u32())) else {
+        return false;
+    };
+
+    // the process name is in parentheses and can contain spaces, the state comes right after it
+    let state = stat
+        .rfind(')')
+        .and_then(|i| stat[i + 1..].trim_start().chars().next());
+
+    !matches!(state, Some('Z' | 'X') | None)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        process::{Child, Command},
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use super::*;
+
+    fn spawn_sleep() -> (Child, Pid) {
+        let child = Command::new("sleep").arg("30").spawn().unwrap();
+        let pid = Pid::from_u32(child.id());
+        (child, pid)
+    }
+
+    // Signals are delivered asynchronously, so wait a bit for the state to change
+    fn wait_for_state(pid: Pid, expected_state: char) -> bool {
+        let start = Instant::now();
+
+        while start.elapsed() < Duration::from_secs(5) {
+            let stat = fs::read_to_string(format!("/proc/{}/stat", pid.as_u32())).unwrap();
+            let state = stat[stat.rfind(')').unwrap() + 1..]
+                .trim_start()
+                .chars()
+                .next();
+
+            if state == Some(expected_state) {
+                return true;
+            }
+
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        false
+    }
+
+    #[test]
+    fn suspend_and_resume() {
+        let (mut child, pid) = spawn_sleep();
+
+        assert!(LinuxProcessControl.suspend(pid));
+        assert!(wait_for_state(pid, 'T'));
+
+        assert!(LinuxProcessControl.resume(pid));
+        assert!(wait_for_state(pid, 'S'));
+
+        _ = child.kill();
+        _ = child.wait();
+    }
+
+    #[test]
+    fn thread_ids_of_a_single_threaded_process() {
+        let (mut child, pid) = spawn_sleep();
+
+        assert_eq!(LinuxProcessControl.thread_ids(pid), vec![pid.as_u32()]);
+
+        _ = child.kill();
+        _ = child.wait();
+    }
+
+    // The test is the parent of the process, so its zombie is still around for reading the exit status
+    #[test]
+    fn wait_for_exit_of_a_child() {
+        let (mut child, pid) = spawn_sleep();
+
+        assert!(LinuxProcessControl.is_alive(pid));
+
+        child.kill().unwrap();
+
+        assert_eq!(
+            LinuxProcessControl.wait_for_exit(pid),
+            ExitStatus::Signal(libc::SIGKILL)
+        );
+        assert!(!LinuxProcessControl.is_alive(pid));
+        assert!(!is_alive_proc_stat(pid));
+
+        _ = child.wait();
+    }
+}
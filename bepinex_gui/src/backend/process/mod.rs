@@ -1,131 +1,256 @@
 use core::time;
+use std::fmt;
 use std::io;
-use std::mem::size_of;
-use std::sync::atomic::AtomicBool;
-use std::sync::atomic::Ordering;
-use std::sync::Arc;
+use std::path::Path;
 use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crossbeam_channel::Sender;
 
 use sysinfo::Pid;
 use sysinfo::ProcessExt;
 use sysinfo::SystemExt;
-use winapi::um::winnt::HANDLE;
-use winapi::{
-    shared::{
-        minwindef::{BOOL, DWORD, LPARAM},
-        windef::HWND,
-    },
-    um::{
-        processthreadsapi::GetCurrentProcessId,
-        tlhelp32::{TH32CS_SNAPTHREAD, THREADENTRY32},
-        winuser::{EnumWindows, GetWindowThreadProcessId, IsHungAppWindow},
-    },
-};
 
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(target_os = "linux")]
+pub use linux::{clock_ticks_per_second, main_thread_cpu_ticks};
+pub mod resource_monitor;
 #[cfg(windows)]
-pub fn for_each_thread(target_process_id: Pid, callback: impl Fn(HANDLE)) -> bool {
-    use sysinfo::PidExt;
-    use winapi::um::winnt::THREAD_SUSPEND_RESUME;
-
-    unsafe {
-        let sys = sysinfo::System::new_all();
-
-        if sys.process(target_process_id).is_some() {
-            let thread_snapshot =
-                winapi::um::tlhelp32::CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0);
-
-            let mut te32: THREADENTRY32 = THREADENTRY32 {
-                dwSize: size_of::<THREADENTRY32>() as DWORD,
-                ..Default::default()
-            };
-            let te32_ptr = std::ptr::addr_of_mut!(te32);
-
-            if winapi::um::tlhelp32::Thread32First(thread_snapshot, te32_ptr) == 0 {
-                tracing::error!("Thread32First fail");
-                winapi::um::handleapi::CloseHandle(thread_snapshot);
-            }
-
-            loop {
-                if te32.th32OwnerProcessID == target_process_id.as_u32() {
-                    let open_thread_handle = winapi::um::processthreadsapi::OpenThread(
-                        THREAD_SUSPEND_RESUME,
-                        false as i32,
-                        te32.th32ThreadID,
-                    );
-
-                    if open_thread_handle.is_null() {
-                        tracing::error!("OpenThread Failed");
-                        break;
-                    }
-
-                    callback(open_thread_handle);
-
-                    winapi::um::handleapi::CloseHandle(open_thread_handle);
-                }
-
-                if winapi::um::tlhelp32::Thread32Next(thread_snapshot, te32_ptr) == 0 {
-                    break;
-                }
-            }
-
-            winapi::um::handleapi::CloseHandle(thread_snapshot);
-
-            return true;
-        }
+mod windows;
+#[cfg(windows)]
+pub use windows::main_window;
+
+// What the GUI needs from the OS for controlling the game process,
+// each platform implements it with its own native APIs
+pub trait ProcessControl {
+    // Returns false if the process couldn't be found
+    fn suspend(&self, target_process_id: Pid) -> bool;
+
+    fn resume(&self, target_process_id: Pid) -> bool;
+
+    fn thread_ids(&self, target_process_id: Pid) -> Vec<u32>;
+
+    fn is_alive(&self, target_process_id: Pid) -> bool;
+
+    // Open handles on Windows, file descriptors on Linux
+    fn handle_count(&self, target_process_id: Pid) -> Option<u32>;
+
+    // Blocks until the process is gone.
+    // The GUI is not the parent of the game, so the status can't always be known.
+    // On Linux it is only readable while the game is a zombie, which its real parent (Steam, the shell...)
+    // usually reaps before we get to look, so expect Unknown most of the time there
+    fn wait_for_exit(&self, target_process_id: Pid) -> ExitStatus;
+
+    // Whether the OS considers a window of the process as not responding
+    fn is_hung(&self, target_process_id: Pid) -> bool;
+
+    // Asks the process to quit by itself, like closing its window would.
+    // Returns false if the request couldn't be delivered
+    fn request_exit(&self, target_process_id: Pid) -> bool;
+
+    // What every thread of the process is doing, for diagnosing hangs.
+    // A minidump on Windows, a text file on Linux
+    fn write_thread_dump(&self, target_process_id: Pid, dump_full_path: &Path) -> io::Result<()>;
+}
+
+#[cfg(windows)]
+fn platform() -> impl ProcessControl {
+    windows::WindowsProcessControl
+}
+
+#[cfg(target_os = "linux")]
+fn platform() -> impl ProcessControl {
+    linux::LinuxProcessControl
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+fn platform() -> impl ProcessControl {
+    UnsupportedProcessControl
+}
+
+// Only what sysinfo can do on its own
+#[cfg(not(any(windows, target_os = "linux")))]
+struct UnsupportedProcessControl;
+
+#[cfg(not(any(windows, target_os = "linux")))]
+impl ProcessControl for UnsupportedProcessControl {
+    fn suspend(&self, _target_process_id: Pid) -> bool {
+        false
+    }
+
+    fn resume(&self, _target_process_id: Pid) -> bool {
+        false
+    }
+
+    fn thread_ids(&self, _target_process_id: Pid) -> Vec<u32> {
+        Vec::new()
+    }
+
+    fn is_alive(&self, target_process_id: Pid) -> bool {
+        sysinfo::System::new().refresh_process(target_process_id)
+    }
+
+    fn handle_count(&self, _target_process_id: Pid) -> Option<u32> {
+        None
+    }
+
+    fn wait_for_exit(&self, target_process_id: Pid) -> ExitStatus {
+        wait_for_exit_polling(target_process_id)
+    }
 
+    fn is_hung(&self, _target_process_id: Pid) -> bool {
         false
     }
+
+    fn request_exit(&self, _target_process_id: Pid) -> bool {
+        false
+    }
+
+    fn write_thread_dump(&self, _target_process_id: Pid, _dump_full_path: &Path) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "Thread dumps are not supported on this platform",
+        ))
+    }
 }
 
-#[cfg(not(windows))]
-pub fn for_each_thread() {
-    // todo
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExitStatus {
+    Code(u32),
+    // Unix only
+    Signal(i32),
+    Unknown,
 }
 
-#[cfg(windows)]
-pub fn resume(target_process_id: Pid) -> bool {
-    for_each_thread(target_process_id, |thread_handle| unsafe {
-        winapi::um::processthreadsapi::ResumeThread(thread_handle);
-    })
+impl ExitStatus {
+    // None when only the logs and crash files can tell
+    pub fn is_abnormal(&self) -> Option<bool> {
+        match self {
+            Self::Code(code) => Some(*code != 0),
+            // asked to quit, by the user or the system
+            #[cfg(unix)]
+            Self::Signal(libc::SIGTERM | libc::SIGINT | libc::SIGHUP) => Some(false),
+            Self::Signal(_) => Some(true),
+            Self::Unknown => None,
+        }
+    }
+}
+
+impl fmt::Display for ExitStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            // NTSTATUS codes such as 0xC0000005 (access violation) read better in hex
+            Self::Code(code) if *code >= 0x8000_0000 => write!(f, "exit code {code:#010X}"),
+            Self::Code(code) => write!(f, "exit code {code}"),
+            Self::Signal(signal) => write!(f, "killed by signal {}", signal_name(*signal)),
+            Self::Unknown => f.write_str("unknown exit status"),
+        }
+    }
+}
+
+fn signal_name(signal: i32) -> String {
+    #[cfg(unix)]
+    let name = match signal {
+        libc::SIGSEGV => Some("SIGSEGV"),
+        libc::SIGABRT => Some("SIGABRT"),
+        libc::SIGBUS => Some("SIGBUS"),
+        libc::SIGILL => Some("SIGILL"),
+        libc::SIGFPE => Some("SIGFPE"),
+        libc::SIGKILL => Some("SIGKILL"),
+        libc::SIGTERM => Some("SIGTERM"),
+        libc::SIGINT => Some("SIGINT"),
+        libc::SIGHUP => Some("SIGHUP"),
+        _ => None,
+    };
+    #[cfg(not(unix))]
+    let name: Option<&str> = None;
+
+    match name {
+        Some(name) => format!("{name} ({signal})"),
+        None => signal.to_string(),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ProcessExit {
+    pub status: ExitStatus,
+    pub time: SystemTime,
 }
 
-#[cfg(not(windows))]
 pub fn resume(target_process_id: Pid) -> bool {
-    // todo
+    platform().resume(target_process_id)
 }
 
-#[cfg(windows)]
 pub fn suspend(target_process_id: Pid) -> bool {
-    for_each_thread(target_process_id, |thread_handle| unsafe {
-        winapi::um::processthreadsapi::SuspendThread(thread_handle);
-    })
+    platform().suspend(target_process_id)
 }
 
-#[cfg(not(windows))]
-pub fn suspend(target_process_id: Pid) -> bool {
-    // todo
+pub fn thread_ids(target_process_id: Pid) -> Vec<u32> {
+    platform().thread_ids(target_process_id)
+}
+
+pub fn is_alive(target_process_id: Pid) -> bool {
+    platform().is_alive(target_process_id)
+}
+
+pub fn handle_count(target_process_id: Pid) -> Option<u32> {
+    platform().handle_count(target_process_id)
+}
+
+pub fn wait_for_exit(target_process_id: Pid) -> ExitStatus {
+    platform().wait_for_exit(target_process_id)
+}
+
+// For when the OS won't let us wait on the process directly
+fn wait_for_exit_polling(target_process_id: Pid) -> ExitStatus {
+    while is_alive(target_process_id) {
+        thread::sleep(time::Duration::from_millis(2000));
+    }
+
+    ExitStatus::Unknown
+}
+
+pub fn is_hung(target_process_id: Pid) -> bool {
+    platform().is_hung(target_process_id)
+}
+
+pub fn request_exit(target_process_id: Pid) -> bool {
+    platform().request_exit(target_process_id)
 }
 
+pub fn write_thread_dump(target_process_id: Pid, dump_full_path: &Path) -> io::Result<()> {
+    platform().write_thread_dump(target_process_id, dump_full_path)
+}
+
+#[cfg(windows)]
+pub const THREAD_DUMP_EXTENSION: &str = "dmp";
+#[cfg(not(windows))]
+pub const THREAD_DUMP_EXTENSION: &str = "txt";
+
 pub fn spawn_thread_is_process_dead(
     target_process_id: Pid,
-    should_check: Arc<AtomicBool>,
-    out_true_when_process_is_dead: Arc<AtomicBool>,
+    process_exit_sender: Sender<ProcessExit>,
 ) {
-    thread::spawn(move || -> io::Result<()> {
-        let mut sys = sysinfo::System::new_all();
-        loop {
-            if !sys.refresh_process(target_process_id) && should_check.load(Ordering::Relaxed) {
-                break;
-            }
-
-            thread::sleep(time::Duration::from_millis(2000));
-        }
+    thread::spawn(move || {
+        let status = wait_for_exit(target_process_id);
+
+        tracing::info!("Target process is dead, {}", status);
+        _ = process_exit_sender.send(ProcessExit {
+            status,
+            time: SystemTime::now(),
+        });
+    });
+}
 
-        tracing::info!("Target process is dead, setting out_true_when_process_is_dead");
-        out_true_when_process_is_dead.store(true, Ordering::Relaxed);
+pub fn start_time(target_process_id: Pid) -> Option<SystemTime> {
+    let mut sys = sysinfo::System::new();
+    if !sys.refresh_process(target_process_id) {
+        return None;
+    }
 
-        Ok(())
-    });
+    sys.process(target_process_id)
+        .map(|proc| UNIX_EPOCH + time::Duration::from_secs(proc.start_time()))
 }
 
 pub fn kill(target_process_id: Pid, callback: impl FnOnce()) {
@@ -135,59 +260,3 @@ pub fn kill(target_process_id: Pid, callback: impl FnOnce()) {
         callback();
     }
 }
-
-#[cfg(windows)]
-pub fn spawn_thread_check_if_process_is_hung(callback: impl Fn() + std::marker::Send + 'static) {
-    thread::spawn(move || -> io::Result<()> {
-        unsafe {
-            static mut CURRENT_PROCESS_ID: u32 = 0;
-            CURRENT_PROCESS_ID = GetCurrentProcessId();
-
-            static mut GOT_RESULT: bool = false;
-
-            static mut WINDOW_HANDLE: HWND = 0 as _;
-
-            GOT_RESULT = false;
-            extern "system" fn enum_window(window: HWND, _: LPARAM) -> BOOL {
-                unsafe {
-                    if GOT_RESULT {
-                        return true.into();
-                    }
-
-                    let mut proc_id: DWORD = 0 as DWORD;
-                    let _ = GetWindowThreadProcessId(window, std::ptr::addr_of_mut!(proc_id));
-                    if proc_id == CURRENT_PROCESS_ID {
-                        WINDOW_HANDLE = window;
-                    }
-
-                    true.into()
-                }
-            }
-
-            EnumWindows(Some(enum_window), 0 as LPARAM);
-
-            let mut i = 0;
-            loop {
-                if IsHungAppWindow(WINDOW_HANDLE) == 1 {
-                    if i == 3 {
-                        callback();
-                        tracing::info!("callback called!");
-                        return Ok(());
-                    }
-
-                    i += 1;
-                }
-
-                thread::sleep(time::Duration::from_millis(1000));
-            }
-        }
-    });
-}
-
-#[cfg(not(windows))]
-pub(crate) fn spawn_thread_check_if_process_is_hung(
-    target_process_id: Pid,
-    should_check: Arc<AtomicBool>,
-    out_true_when_process_is_dead: Arc<AtomicBool>,
-) {
-}
@@ -0,0 +1,68 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std::thread;
+use std::time::SystemTime;
+
+use crossbeam_channel::Sender;
+use sysinfo::Pid;
+use sysinfo::ProcessExt;
+use sysinfo::SystemExt;
+
+use crate::backend::process;
+use crate::data::resource_usage::{self, ResourceSample};
+
+// Stops once the process is gone, nobody listens anymore or the returned flag is set,
+// so that the samples of two instances of the game don't get mixed when the GUI switches to another one
+pub fn spawn_thread_monitor(
+    target_process_id: Pid,
+    sample_senders: Vec<Sender<ResourceSample>>,
+) -> Arc<AtomicBool> {
+    let should_stop = Arc::new(AtomicBool::new(false));
+    let should_stop_thread = should_stop.clone();
+
+    thread::spawn(move || {
+        // the same System must be kept between refreshes for the cpu usage to be computed
+        let mut sys = sysinfo::System::new();
+
+        loop {
+            thread::sleep(resource_usage::SAMPLE_INTERVAL);
+
+            if should_stop_thread.load(Ordering::Relaxed) {
+                return;
+            }
+
+            if !sys.refresh_process(target_process_id) {
+                tracing::info!("Target process is gone, stopping the resource monitor");
+                return;
+            }
+
+            let Some(proc) = sys.process(target_process_id) else {
+                return;
+            };
+
+            let disk_usage = proc.disk_usage();
+            let sample = ResourceSample {
+                time: SystemTime::now(),
+                cpu_usage: proc.cpu_usage(),
+                memory_bytes: proc.memory(),
+                thread_count: process::thread_ids(target_process_id).len(),
+                handle_count: process::handle_count(target_process_id),
+                disk_read_bytes: disk_usage.read_bytes,
+                disk_written_bytes: disk_usage.written_bytes,
+            };
+
+            let mut is_anyone_listening = false;
+            for sample_sender in &sample_senders {
+                is_anyone_listening |= sample_sender.send(sample).is_ok();
+            }
+
+            if !is_anyone_listening {
+                return;
+            }
+        }
+    });
+
+    should_stop
+}
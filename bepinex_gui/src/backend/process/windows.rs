@@ -0,0 +1,264 @@
+use std::fs::File;
+use std::io;
+use std::mem::size_of;
+use std::os::windows::io::AsRawHandle;
+use std::path::Path;
+
+use sysinfo::Pid;
+use sysinfo::PidExt;
+use sysinfo::SystemExt;
+use winapi::um::winnt::HANDLE;
+use winapi::{
+    shared::{
+        minwindef::{BOOL, DWORD, LPARAM, LPVOID},
+        windef::HWND,
+    },
+    um::{
+        processthreadsapi::{GetExitCodeProcess, GetProcessHandleCount, OpenProcess},
+        synchapi::WaitForSingleObject,
+        tlhelp32::{TH32CS_SNAPTHREAD, THREADENTRY32},
+        winbase::INFINITE,
+        winnt::{
+            PROCESS_QUERY_INFORMATION, PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_VM_READ,
+            SYNCHRONIZE, THREAD_SUSPEND_RESUME,
+        },
+        winuser::{
+            EnumWindows, GetWindowThreadProcessId, IsHungAppWindow, IsWindowVisible, PostMessageW,
+            WM_CLOSE,
+        },
+    },
+};
+
+use super::{ExitStatus, ProcessControl};
+
+// winapi doesn't bind the minidump functions of dbghelp
+#[link(name = "dbghelp")]
+extern "system" {
+    fn MiniDumpWriteDump(
+        process: HANDLE,
+        process_id: DWORD,
+        file: HANDLE,
+        dump_type: u32,
+        exception_param: LPVOID,
+        user_stream_param: LPVOID,
+        callback_param: LPVOID,
+    ) -> BOOL;
+}
+
+// MINIDUMP_TYPE flags, the stacks of every thread are always in the dump
+const MINI_DUMP_WITH_UNLOADED_MODULES: u32 = 0x20;
+const MINI_DUMP_WITH_THREAD_INFO: u32 = 0x1000;
+
+pub struct WindowsProcessControl;
+
+impl ProcessControl for WindowsProcessControl {
+    fn suspend(&self, target_process_id: Pid) -> bool {
+        for_each_thread(target_process_id, |_, thread_handle| unsafe {
+            winapi::um::processthreadsapi::SuspendThread(thread_handle);
+        })
+    }
+
+    fn resume(&self, target_process_id: Pid) -> bool {
+        for_each_thread(target_process_id, |_, thread_handle| unsafe {
+            winapi::um::processthreadsapi::ResumeThread(thread_handle);
+        })
+    }
+
+    fn thread_ids(&self, target_process_id: Pid) -> Vec<u32> {
+        let thread_ids = std::cell::RefCell::new(Vec::new());
+        for_each_thread(target_process_id, |thread_id, _| {
+            thread_ids.borrow_mut().push(thread_id);
+        });
+
+        thread_ids.into_inner()
+    }
+
+    fn is_alive(&self, target_process_id: Pid) -> bool {
+        sysinfo::System::new().refresh_process(target_process_id)
+    }
+
+    fn handle_count(&self, target_process_id: Pid) -> Option<u32> {
+        unsafe {
+            let process_handle = OpenProcess(
+                PROCESS_QUERY_LIMITED_INFORMATION,
+                false as i32,
+                target_process_id.as_u32(),
+            );
+            if process_handle.is_null() {
+                return None;
+            }
+
+            let mut handle_count: DWORD = 0;
+            let result =
+                GetProcessHandleCount(process_handle, std::ptr::addr_of_mut!(handle_count));
+
+            winapi::um::handleapi::CloseHandle(process_handle);
+
+            (result != 0).then_some(handle_count)
+        }
+    }
+
+    fn wait_for_exit(&self, target_process_id: Pid) -> ExitStatus {
+        unsafe {
+            let process_handle = OpenProcess(
+                SYNCHRONIZE | PROCESS_QUERY_LIMITED_INFORMATION,
+                false as i32,
+                target_process_id.as_u32(),
+            );
+            if process_handle.is_null() {
+                return super::wait_for_exit_polling(target_process_id);
+            }
+
+            WaitForSingleObject(process_handle, INFINITE);
+
+            let mut exit_code: DWORD = 0;
+            let result = GetExitCodeProcess(process_handle, std::ptr::addr_of_mut!(exit_code));
+
+            winapi::um::handleapi::CloseHandle(process_handle);
+
+            if result == 0 {
+                ExitStatus::Unknown
+            } else {
+                ExitStatus::Code(exit_code)
+            }
+        }
+    }
+
+    fn is_hung(&self, target_process_id: Pid) -> bool {
+        match main_window(target_process_id) {
+            Some(window) => unsafe { IsHungAppWindow(window) == 1 },
+            None => false,
+        }
+    }
+
+    // Same as the user pressing the close button of the game window
+    fn request_exit(&self, target_process_id: Pid) -> bool {
+        match main_window(target_process_id) {
+            Some(window) => unsafe { PostMessageW(window, WM_CLOSE, 0, 0) != 0 },
+            None => false,
+        }
+    }
+
+    // Opened in Visual Studio or WinDbg for the call stacks
+    fn write_thread_dump(&self, target_process_id: Pid, dump_full_path: &Path) -> io::Result<()> {
+        let file = File::create(dump_full_path)?;
+
+        unsafe {
+            let process_handle = OpenProcess(
+                PROCESS_QUERY_INFORMATION | PROCESS_VM_READ,
+                false as i32,
+                target_process_id.as_u32(),
+            );
+            if process_handle.is_null() {
+                return Err(io::Error::last_os_error());
+            }
+
+            let result = MiniDumpWriteDump(
+                process_handle,
+                target_process_id.as_u32(),
+                file.as_raw_handle() as HANDLE,
+                MINI_DUMP_WITH_UNLOADED_MODULES | MINI_DUMP_WITH_THREAD_INFO,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            );
+            let err = io::Error::last_os_error();
+
+            winapi::um::handleapi::CloseHandle(process_handle);
+
+            if result == 0 {
+                return Err(err);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn for_each_thread(target_process_id: Pid, callback: impl Fn(u32, HANDLE)) -> bool {
+    unsafe {
+        let mut sys = sysinfo::System::new();
+
+        if sys.refresh_process(target_process_id) {
+            let thread_snapshot =
+                winapi::um::tlhelp32::CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0);
+
+            let mut te32: THREADENTRY32 = THREADENTRY32 {
+                dwSize: size_of::<THREADENTRY32>() as DWORD,
+                ..Default::default()
+            };
+            let te32_ptr = std::ptr::addr_of_mut!(te32);
+
+            if winapi::um::tlhelp32::Thread32First(thread_snapshot, te32_ptr) == 0 {
+                tracing::error!("Thread32First fail");
+                winapi::um::handleapi::CloseHandle(thread_snapshot);
+                return false;
+            }
+
+            loop {
+                if te32.th32OwnerProcessID == target_process_id.as_u32() {
+                    let open_thread_handle = winapi::um::processthreadsapi::OpenThread(
+                        THREAD_SUSPEND_RESUME,
+                        false as i32,
+                        te32.th32ThreadID,
+                    );
+
+                    if open_thread_handle.is_null() {
+                        tracing::error!("OpenThread Failed");
+                        break;
+                    }
+
+                    callback(te32.th32ThreadID, open_thread_handle);
+
+                    winapi::um::handleapi::CloseHandle(open_thread_handle);
+                }
+
+                if winapi::um::tlhelp32::Thread32Next(thread_snapshot, te32_ptr) == 0 {
+                    break;
+                }
+            }
+
+            winapi::um::handleapi::CloseHandle(thread_snapshot);
+
+            return true;
+        }
+
+        false
+    }
+}
+
+struct MainWindowSearch {
+    process_id: u32,
+    window: Option<HWND>,
+}
+
+// First visible top level window owned by the process
+pub fn main_window(target_process_id: Pid) -> Option<HWND> {
+    extern "system" fn enum_window(window: HWND, search_ptr: LPARAM) -> BOOL {
+        unsafe {
+            let search = &mut *(search_ptr as *mut MainWindowSearch);
+
+            let mut proc_id: DWORD = 0 as DWORD;
+            let _ = GetWindowThreadProcessId(window, std::ptr::addr_of_mut!(proc_id));
+            if proc_id == search.process_id && IsWindowVisible(window) != 0 {
+                search.window = Some(window);
+
+                // stop enumerating
+                return false.into();
+            }
+
+            true.into()
+        }
+    }
+
+    let mut search = MainWindowSearch {
+        process_id: target_process_id.as_u32(),
+        window: None,
+    };
+
+    unsafe {
+        EnumWindows(Some(enum_window), std::ptr::addr_of_mut!(search) as LPARAM);
+    }
+
+    search.window
+}
@@ -0,0 +1,110 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+pub const FILE_NAME: &str = "manifest.json";
+
+// r2modman installs packages in a "Author-Name" folder,
+// so this is deep enough for both manual and mod manager installs
+const MAX_FOLDER_DEPTH: usize = 2;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    pub name: String,
+    pub version_number: String,
+    #[serde(default)]
+    pub website_url: String,
+    #[serde(default)]
+    pub description: String,
+    // "Author-Name-Version" strings
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct InstalledPackage {
+    // "Author-Name" when the author is known, "Name" otherwise
+    pub id: String,
+    pub manifest: Manifest,
+    pub folder_full_path: PathBuf,
+}
+
+pub fn read(manifest_file_full_path: &Path) -> Option<Manifest> {
+    let json = fs::read_to_string(manifest_file_full_path).ok()?;
+
+    // Some manifests are saved with a BOM by windows text editors
+    let json = json.trim_start_matches('\u{feff}');
+
+    match serde_json::from_str::<Manifest>(json) {
+        Ok(manifest) => Some(manifest),
+        Err(err) => {
+            tracing::error!(
+                "Failed parsing manifest {}: {}",
+                manifest_file_full_path.display(),
+                err
+            );
+            None
+        }
+    }
+}
+
+pub fn find_installed_packages(folder_full_path: &Path) -> Vec<InstalledPackage> {
+    let mut installed_packages = Vec::new();
+
+    find_installed_packages_internal(folder_full_path, 0, &mut installed_packages);
+
+    installed_packages
+}
+
+fn find_installed_packages_internal(
+    folder_full_path: &Path,
+    depth: usize,
+    installed_packages: &mut Vec<InstalledPackage>,
+) {
+    let Ok(entries) = fs::read_dir(folder_full_path) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        if path.is_dir() {
+            if depth < MAX_FOLDER_DEPTH {
+                find_installed_packages_internal(&path, depth + 1, installed_packages);
+            }
+        } else if entry.file_name() == FILE_NAME {
+            if let Some(manifest) = read(&path) {
+                installed_packages.push(InstalledPackage {
+                    id: package_id(folder_full_path, &manifest),
+                    manifest,
+                    folder_full_path: folder_full_path.to_path_buf(),
+                });
+            }
+        }
+    }
+}
+
+fn package_id(package_folder_full_path: &Path, manifest: &Manifest) -> String {
+    let folder_name = package_folder_full_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    match folder_name.split_once('-') {
+        Some((author, name)) if name == manifest.name => format!("{author}-{name}"),
+        _ => manifest.name.clone(),
+    }
+}
+
+// Splits a "Author-Name-Version" dependency string into ("Author-Name", "Version")
+pub fn split_dependency_string(dependency_string: &str) -> (&str, Option<&str>) {
+    match dependency_string.rsplit_once('-') {
+        Some((id, version)) if version.starts_with(|c: char| c.is_ascii_digit()) => {
+            (id, Some(version))
+        }
+        _ => (dependency_string, None),
+    }
+}
@@ -0,0 +1,273 @@
+use std::time::{Duration, Instant};
+
+use regex::{Regex, RegexBuilder};
+use serde::*;
+
+use crate::data::bepinex_log::{BepInExLogEntry, LogLevel};
+
+// A rule matching a flood of logs only triggers once in that time
+const RULE_COOLDOWN: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AlertActions {
+    pub desktop_notification: bool,
+    pub flash_window: bool,
+    pub sound: bool,
+    pub scroll_to_log: bool,
+    pub pause_game: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertRule {
+    pub name: String,
+    pub is_enabled: bool,
+    // That log level or a more severe one
+    pub max_level: LogLevel,
+    // Empty for any source, compared case insensitively
+    pub source: String,
+    // Empty for any log, a case insensitive regex otherwise
+    pub pattern: String,
+    pub actions: AlertActions,
+}
+
+impl Default for AlertRule {
+    fn default() -> Self {
+        Self {
+            name: "New Rule".to_string(),
+            is_enabled: true,
+            max_level: LogLevel::Fatal,
+            source: String::new(),
+            pattern: String::new(),
+            actions: AlertActions {
+                desktop_notification: true,
+                flash_window: true,
+                sound: false,
+                scroll_to_log: false,
+                pause_game: false,
+            },
+        }
+    }
+}
+
+impl AlertRule {
+    pub fn compile_pattern(&self) -> Result<Option<Regex>, regex::Error> {
+        if self.pattern.is_empty() {
+            return Ok(None);
+        }
+
+        RegexBuilder::new(&self.pattern)
+            .case_insensitive(true)
+            .build()
+            .map(Some)
+    }
+}
+
+// A rule along with its compiled pattern and cooldown
+struct MatcherRule {
+    rule: AlertRule,
+    // None when the rule has no pattern or an invalid one
+    regex: Option<Regex>,
+    is_pattern_valid: bool,
+    last_trigger_time: Option<Instant>,
+}
+
+impl MatcherRule {
+    fn new(rule: AlertRule) -> Self {
+        let compiled_pattern = rule.compile_pattern();
+
+        Self {
+            is_pattern_valid: compiled_pattern.is_ok(),
+            regex: compiled_pattern.ok().flatten(),
+            rule,
+            last_trigger_time: None,
+        }
+    }
+
+    fn matches(&self, log: &BepInExLogEntry) -> bool {
+        let rule = &self.rule;
+
+        if !rule.is_enabled || !self.is_pattern_valid || log.level() > rule.max_level {
+            return false;
+        }
+
+        if !rule.source.is_empty()
+            && !log
+                .source()
+                .is_some_and(|source| source.eq_ignore_ascii_case(&rule.source))
+        {
+            return false;
+        }
+
+        match &self.regex {
+            Some(regex) => regex.is_match(log.data()),
+            None => true,
+        }
+    }
+}
+
+// Keeps the compiled regexes around, the rules are checked against every log
+#[derive(Default)]
+pub struct AlertRuleMatcher {
+    rules: Vec<MatcherRule>,
+}
+
+impl AlertRuleMatcher {
+    // Cheap when the rules didn't change.
+    // The rules that didn't change keep their compiled pattern and their cooldown
+    pub fn set_rules(&mut self, rules: &[AlertRule]) {
+        if self
+            .rules
+            .iter()
+            .map(|matcher_rule| &matcher_rule.rule)
+            .eq(rules.iter())
+        {
+            return;
+        }
+
+        let mut previous_rules = std::mem::take(&mut self.rules);
+
+        self.rules = rules
+            .iter()
+            .map(|rule| {
+                match previous_rules
+                    .iter()
+                    .position(|previous_rule| previous_rule.rule == *rule)
+                {
+                    Some(i) => previous_rules.swap_remove(i),
+                    None => MatcherRule::new(rule.clone()),
+                }
+            })
+            .collect();
+    }
+
+    // Returns the rules triggered by the log
+    pub fn check(&mut self, log: &BepInExLogEntry) -> Vec<AlertRule> {
+        let mut triggered_rules = Vec::new();
+
+        for matcher_rule in &mut self.rules {
+            if !matcher_rule.matches(log) {
+                continue;
+            }
+
+            if matcher_rule
+                .last_trigger_time
+                .is_some_and(|last_trigger_time| last_trigger_time.elapsed() < RULE_COOLDOWN)
+            {
+                continue;
+            }
+
+            matcher_rule.last_trigger_time = Some(Instant::now());
+            triggered_rules.push(matcher_rule.rule.clone());
+        }
+
+        triggered_rules
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use super::*;
+
+    fn rule(name: &str, max_level: LogLevel, source: &str, pattern: &str) -> AlertRule {
+        AlertRule {
+            name: name.to_string(),
+            max_level,
+            source: source.to_string(),
+            pattern: pattern.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn log(level: LogLevel, data: &str) -> BepInExLogEntry {
+        BepInExLogEntry::new(level, SystemTime::now(), data)
+    }
+
+    fn triggered_rule_names(matcher: &mut AlertRuleMatcher, log: &BepInExLogEntry) -> Vec<String> {
+        matcher
+            .check(log)
+            .into_iter()
+            .map(|rule| rule.name)
+            .collect()
+    }
+
+    #[test]
+    fn matches_on_level_source_and_pattern() {
+        let mut matcher = AlertRuleMatcher::default();
+        matcher.set_rules(&[
+            rule("errors", LogLevel::Error, "", ""),
+            rule("r2api", LogLevel::Warning, "r2api", ""),
+            rule("nre", LogLevel::All, "", "nullreference"),
+        ]);
+
+        assert_eq!(
+            triggered_rule_names(
+                &mut matcher,
+                &log(
+                    LogLevel::Error,
+                    "[Error  :      R2API] NullReferenceException"
+                )
+            ),
+            ["errors", "r2api", "nre"]
+        );
+        assert!(triggered_rule_names(
+            &mut matcher,
+            &log(LogLevel::Info, "[Info   : Unity Log] Loading scene")
+        )
+        .is_empty());
+
+        let mut matcher = AlertRuleMatcher::default();
+        matcher.set_rules(&[rule("r2api", LogLevel::Warning, "r2api", "")]);
+        assert!(triggered_rule_names(
+            &mut matcher,
+            &log(LogLevel::Warning, "[Warning:   MoreItems] slow")
+        )
+        .is_empty());
+        assert!(triggered_rule_names(
+            &mut matcher,
+            &log(LogLevel::Message, "[Message:      R2API] hello")
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn disabled_rules_and_invalid_patterns_never_trigger() {
+        let mut matcher = AlertRuleMatcher::default();
+        matcher.set_rules(&[
+            AlertRule {
+                is_enabled: false,
+                ..rule("disabled", LogLevel::All, "", "")
+            },
+            rule("invalid", LogLevel::All, "", "(unclosed"),
+        ]);
+
+        assert!(triggered_rule_names(
+            &mut matcher,
+            &log(LogLevel::Fatal, "[Fatal  :   BepInEx] (unclosed")
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn cooldown_only_resets_for_the_changed_rules() {
+        let errors = rule("errors", LogLevel::Error, "", "");
+        let fatals = rule("fatals", LogLevel::Fatal, "", "");
+        let fatal_log = log(LogLevel::Fatal, "[Fatal  :   BepInEx] boom");
+
+        let mut matcher = AlertRuleMatcher::default();
+        matcher.set_rules(&[errors.clone(), fatals.clone()]);
+        assert_eq!(
+            triggered_rule_names(&mut matcher, &fatal_log),
+            ["errors", "fatals"]
+        );
+        assert!(triggered_rule_names(&mut matcher, &fatal_log).is_empty());
+
+        let renamed_fatals = AlertRule {
+            name: "renamed".to_string(),
+            ..fatals
+        };
+        matcher.set_rules(&[errors, renamed_fatals]);
+        assert_eq!(triggered_rule_names(&mut matcher, &fatal_log), ["renamed"]);
+    }
+}
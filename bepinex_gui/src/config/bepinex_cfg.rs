@@ -0,0 +1,913 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    fmt, fs,
+    hash::{Hash, Hasher},
+    io,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::backend::atomic_file;
+
+// Parser / writer for the .cfg files written by BepInEx.Configuration.ConfigFile
+//
+// ## Settings file was created by plugin BepInEx.GUI v3.0.0
+// ## Plugin GUID: BepInEx.GUI
+//
+// [Settings]
+//
+// ## Description of the setting, can span multiple lines
+// # Setting type: Boolean
+// # Default value: true
+// Enable BepInEx GUI = true
+//
+// The raw lines are kept around so that writing the file back only touches the values that changed,
+// comments, ordering, orphaned entries and line endings are all preserved.
+
+pub const CFG_EXTENSION: &str = "cfg";
+
+const BOM: char = '\u{feff}';
+
+const DESCRIPTION_PREFIX: &str = "##";
+const COMMENT_PREFIX: char = '#';
+const SETTING_TYPE_PREFIX: &str = "# Setting type:";
+const DEFAULT_VALUE_PREFIX: &str = "# Default value:";
+const ACCEPTABLE_VALUES_PREFIX: &str = "# Acceptable values:";
+const ACCEPTABLE_VALUE_RANGE_PREFIX: &str = "# Acceptable value range:";
+const MULTIPLE_VALUES_PREFIX: &str = "# Multiple values can be set at the same time";
+
+const FLAGS_SEPARATOR: char = ',';
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    // One of the acceptable values of an enum
+    Enum(String),
+    // [Flags] enums, any combination of the acceptable values
+    Flags(Vec<String>),
+    // KeyboardShortcut, Color, Vector3, custom TypeConverters... kept as written in the file
+    Other(String),
+}
+
+impl Value {
+    pub fn to_raw_string(&self) -> String {
+        match self {
+            Self::Bool(value) => value.to_string(),
+            Self::Integer(value) => value.to_string(),
+            Self::Float(value) => value.to_string(),
+            Self::String(value) => escape(value),
+            Self::Enum(value) | Self::Other(value) => value.clone(),
+            Self::Flags(values) => values.join(", "),
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::String(value) => f.write_str(value),
+            _ => f.write_str(&self.to_raw_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Entry {
+    key: String,
+    raw_value: String,
+    description: String,
+    setting_type: Option<String>,
+    default_value: Option<String>,
+    acceptable_values: Vec<String>,
+    acceptable_value_range: Option<(String, String)>,
+    is_flags: bool,
+    // value when the file was last read or saved, used for merging with changes made on disk
+    saved_raw_value: String,
+
+    line_index: usize,
+    // byte offset of the value inside the line, everything before it is kept as is when writing
+    value_start: usize,
+}
+
+impl Entry {
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn raw_value(&self) -> &str {
+        &self.raw_value
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn setting_type(&self) -> Option<&str> {
+        self.setting_type.as_deref()
+    }
+
+    pub fn default_value(&self) -> Option<&str> {
+        self.default_value.as_deref()
+    }
+
+    pub fn acceptable_values(&self) -> &[String] {
+        &self.acceptable_values
+    }
+
+    pub fn acceptable_value_range(&self) -> Option<(&str, &str)> {
+        self.acceptable_value_range
+            .as_ref()
+            .map(|(min, max)| (min.as_str(), max.as_str()))
+    }
+
+    // The values the declared integer type can hold, UInt64 is capped to what an i64 holds
+    pub fn integer_type_range(&self) -> Option<RangeInclusive<i64>> {
+        match self.setting_type.as_deref()? {
+            "SByte" => Some(i8::MIN.into()..=i8::MAX.into()),
+            "Byte" => Some(u8::MIN.into()..=u8::MAX.into()),
+            "Int16" => Some(i16::MIN.into()..=i16::MAX.into()),
+            "UInt16" => Some(u16::MIN.into()..=u16::MAX.into()),
+            "Int32" => Some(i32::MIN.into()..=i32::MAX.into()),
+            "UInt32" => Some(u32::MIN.into()..=u32::MAX.into()),
+            "Int64" => Some(i64::MIN..=i64::MAX),
+            "UInt64" => Some(0..=i64::MAX),
+            _ => None,
+        }
+    }
+
+    pub fn has_unsaved_change(&self) -> bool {
+        self.raw_value != self.saved_raw_value
+    }
+
+    pub fn value(&self) -> Value {
+        self.parse_value(&self.raw_value)
+    }
+
+    // Why BepInEx would refuse the current value and fall back to the default one
+    pub fn validation_error(&self) -> Option<String> {
+        let is_acceptable_value = |value: &str| {
+            self.acceptable_values
+                .iter()
+                .any(|acceptable_value| acceptable_value.eq_ignore_ascii_case(value))
+        };
+
+        match self.value() {
+            Value::Enum(value) if !is_acceptable_value(&value) => {
+                Some(format!("{value} is not one of the acceptable values"))
+            }
+            Value::Flags(flags) => flags
+                .iter()
+                .find(|flag| !is_acceptable_value(flag))
+                .map(|flag| format!("{flag} is not one of the acceptable values")),
+            Value::Integer(value)
+                if self
+                    .integer_type_range()
+                    .is_some_and(|type_range| !type_range.contains(&value)) =>
+            {
+                Some(format!(
+                    "{value} doesn't fit in a {}",
+                    self.setting_type.as_deref().unwrap_or_default()
+                ))
+            }
+            Value::Integer(value) => {
+                let (min, max) = self.acceptable_value_range()?;
+                let (min, max) = (min.parse::<i64>().ok()?, max.parse::<i64>().ok()?);
+                (value < min || value > max)
+                    .then(|| format!("{value} is outside of the range {min} to {max}"))
+            }
+            Value::Float(value) => {
+                let (min, max) = self.acceptable_value_range()?;
+                let (min, max) = (min.parse::<f64>().ok()?, max.parse::<f64>().ok()?);
+                (value < min || value > max)
+                    .then(|| format!("{value} is outside of the range {min} to {max}"))
+            }
+            // parse_value only falls back to Other for those types when the value didn't parse
+            Value::Other(value) => match self.setting_type.as_deref() {
+                Some(
+                    setting_type @ ("Boolean" | "SByte" | "Byte" | "Int16" | "UInt16" | "Int32"
+                    | "UInt32" | "Int64" | "UInt64" | "Single" | "Double"
+                    | "Decimal"),
+                ) => Some(format!("{value} is not a valid {setting_type}")),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn parse_value(&self, raw_value: &str) -> Value {
+        if !self.acceptable_values.is_empty() {
+            if self.is_flags {
+                return Value::Flags(
+                    raw_value
+                        .split(FLAGS_SEPARATOR)
+                        .map(str::trim)
+                        .filter(|flag| !flag.is_empty())
+                        .map(str::to_string)
+                        .collect(),
+                );
+            }
+
+            return Value::Enum(raw_value.to_string());
+        }
+
+        let other = || Value::Other(raw_value.to_string());
+
+        match self.setting_type.as_deref() {
+            Some("Boolean") => raw_value
+                .to_ascii_lowercase()
+                .parse()
+                .map_or_else(|_| other(), Value::Bool),
+            Some(
+                "SByte" | "Byte" | "Int16" | "UInt16" | "Int32" | "UInt32" | "Int64" | "UInt64",
+            ) => raw_value.parse().map_or_else(|_| other(), Value::Integer),
+            Some("Single" | "Double" | "Decimal") => {
+                raw_value.parse().map_or_else(|_| other(), Value::Float)
+            }
+            Some("String") => Value::String(unescape(raw_value)),
+            _ => other(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Section {
+    name: String,
+    entries: Vec<Entry>,
+}
+
+impl Section {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    pub fn entry(&self, key: &str) -> Option<&Entry> {
+        self.entries.iter().find(|entry| entry.key == key)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DiskState {
+    modified: Option<SystemTime>,
+    hash: u64,
+}
+
+impl DiskState {
+    fn new(modified: Option<SystemTime>, text: &str) -> Self {
+        let mut hasher = DefaultHasher::new();
+        text.hash(&mut hasher);
+
+        Self {
+            modified,
+            hash: hasher.finish(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(io::Error),
+    // BepInEx or a mod wrote to the file since it was read, the caller decides between merging or reloading
+    ChangedOnDisk,
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => err.fmt(f),
+            Self::ChangedOnDisk => f.write_str("the file was changed on disk since it was read"),
+        }
+    }
+}
+
+impl From<io::Error> for SaveError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CfgFile {
+    // Every line of the file, line terminator included
+    lines: Vec<String>,
+    // "## " lines before the first section, usually the plugin name, version and guid
+    header: Vec<String>,
+    sections: Vec<Section>,
+    // None if the file was not read from disk
+    disk_state: Option<DiskState>,
+}
+
+#[derive(Default)]
+struct PendingMetadata {
+    description: Vec<String>,
+    setting_type: Option<String>,
+    default_value: Option<String>,
+    acceptable_values: Vec<String>,
+    acceptable_value_range: Option<(String, String)>,
+    is_flags: bool,
+}
+
+impl CfgFile {
+    pub fn read(file_full_path: &Path) -> io::Result<Self> {
+        let text = fs::read_to_string(file_full_path)?;
+
+        let mut cfg_file = Self::parse(&text);
+        cfg_file.disk_state = Some(DiskState::new(modified_time(file_full_path), &text));
+
+        Ok(cfg_file)
+    }
+
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.sections
+            .iter()
+            .flat_map(|section| section.entries.iter())
+            .any(Entry::has_unsaved_change)
+    }
+
+    pub fn has_changed_on_disk(&self, file_full_path: &Path) -> io::Result<bool> {
+        let Some(disk_state) = self.disk_state else {
+            return Ok(file_full_path.exists());
+        };
+
+        let modified = modified_time(file_full_path);
+        if modified.is_some() && modified == disk_state.modified {
+            return Ok(false);
+        }
+
+        // the modified time alone is not reliable, some tools touch files without changing them
+        let text = fs::read_to_string(file_full_path)?;
+        Ok(DiskState::new(modified, &text).hash != disk_state.hash)
+    }
+
+    // Refuses to overwrite changes made on disk since the file was read, see merge_with_disk
+    pub fn save(&mut self, file_full_path: &Path) -> Result<(), SaveError> {
+        if !self.has_unsaved_changes() {
+            return Ok(());
+        }
+
+        if self.has_changed_on_disk(file_full_path)? {
+            return Err(SaveError::ChangedOnDisk);
+        }
+
+        Ok(self.force_save(file_full_path)?)
+    }
+
+    // For callers that only touch a few entries and re-read the file right before, like the GUI own settings
+    pub fn save_merging_changes_on_disk(&mut self, file_full_path: &Path) -> io::Result<()> {
+        match self.save(file_full_path) {
+            Ok(()) => Ok(()),
+            Err(SaveError::Io(err)) => Err(err),
+            Err(SaveError::ChangedOnDisk) => {
+                self.merge_with_disk(file_full_path)?;
+                self.force_save(file_full_path)
+            }
+        }
+    }
+
+    pub fn force_save(&mut self, file_full_path: &Path) -> io::Result<()> {
+        let text = self.to_string();
+
+        atomic_file::write_with_backup(file_full_path, text.as_bytes())?;
+
+        self.disk_state = Some(DiskState::new(modified_time(file_full_path), &text));
+        for entry in self
+            .sections
+            .iter_mut()
+            .flat_map(|section| section.entries.iter_mut())
+        {
+            entry.saved_raw_value = entry.raw_value.clone();
+        }
+
+        Ok(())
+    }
+
+    // Re-read the file from disk and apply the unsaved changes on top of it,
+    // the unsaved changes win over the ones made on disk for the same entry
+    pub fn merge_with_disk(&mut self, file_full_path: &Path) -> io::Result<()> {
+        let mut merged_cfg_file = Self::read(file_full_path)?;
+
+        for section in &self.sections {
+            for entry in section
+                .entries
+                .iter()
+                .filter(|entry| entry.has_unsaved_change())
+            {
+                merged_cfg_file.set_raw(&section.name, &entry.key, &entry.raw_value);
+            }
+        }
+
+        *self = merged_cfg_file;
+
+        Ok(())
+    }
+
+    pub fn parse(text: &str) -> Self {
+        let mut cfg_file = Self {
+            lines: text.split_inclusive('\n').map(str::to_string).collect(),
+            ..Default::default()
+        };
+
+        let mut pending_metadata = PendingMetadata::default();
+
+        for (line_index, raw_line) in cfg_file.lines.iter().enumerate() {
+            let line = raw_line.trim_start_matches(BOM).trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            if line.starts_with('[') && line.ends_with(']') {
+                cfg_file.sections.push(Section {
+                    name: line[1..line.len() - 1].trim().to_string(),
+                    entries: Vec::new(),
+                });
+                pending_metadata = PendingMetadata::default();
+            } else if let Some(description) = line.strip_prefix(DESCRIPTION_PREFIX) {
+                let description = description.strip_prefix(' ').unwrap_or(description);
+                if cfg_file.sections.is_empty() {
+                    cfg_file.header.push(description.to_string());
+                } else {
+                    pending_metadata.description.push(description.to_string());
+                }
+            } else if line.starts_with(COMMENT_PREFIX) {
+                parse_metadata_comment(line, &mut pending_metadata);
+            } else if let Some((key, _)) = line.split_once('=') {
+                let Some(section) = cfg_file.sections.last_mut() else {
+                    // BepInEx ignores entries outside of a section, they are only kept in the raw lines
+                    continue;
+                };
+
+                let metadata = std::mem::take(&mut pending_metadata);
+
+                let after_equal_sign = raw_line.find('=').unwrap_or_default() + 1;
+                let value_start =
+                    after_equal_sign + count_leading_spaces(&raw_line[after_equal_sign..]);
+
+                let raw_value = raw_line[value_start..].trim_end().to_string();
+
+                section.entries.push(Entry {
+                    key: key.trim().to_string(),
+                    saved_raw_value: raw_value.clone(),
+                    raw_value,
+                    description: metadata.description.join("\n"),
+                    setting_type: metadata.setting_type,
+                    default_value: metadata.default_value,
+                    acceptable_values: metadata.acceptable_values,
+                    acceptable_value_range: metadata.acceptable_value_range,
+                    is_flags: metadata.is_flags,
+                    line_index,
+                    value_start,
+                });
+            }
+        }
+
+        cfg_file
+    }
+
+    pub fn header(&self) -> &[String] {
+        &self.header
+    }
+
+    pub fn sections(&self) -> &[Section] {
+        &self.sections
+    }
+
+    pub fn section(&self, name: &str) -> Option<&Section> {
+        self.sections.iter().find(|section| section.name == name)
+    }
+
+    pub fn entry(&self, section_name: &str, key: &str) -> Option<&Entry> {
+        self.section(section_name)
+            .and_then(|section| section.entry(key))
+    }
+
+    pub fn get(&self, section_name: &str, key: &str) -> Option<Value> {
+        self.entry(section_name, key).map(Entry::value)
+    }
+
+    // Returns false if the entry doesn't exist, BepInEx is the one creating entries and their metadata
+    pub fn set(&mut self, section_name: &str, key: &str, value: &Value) -> bool {
+        self.set_raw(section_name, key, &value.to_raw_string())
+    }
+
+    pub fn set_raw(&mut self, section_name: &str, key: &str, raw_value: &str) -> bool {
+        let Some(entry) = self
+            .sections
+            .iter_mut()
+            .find(|section| section.name == section_name)
+            .and_then(|section| section.entries.iter_mut().find(|entry| entry.key == key))
+        else {
+            return false;
+        };
+
+        // a line break in the value would corrupt the file
+        let raw_value = raw_value.replace(['\r', '\n'], " ");
+
+        let line = &mut self.lines[entry.line_index];
+        let line_terminator = &line[line.trim_end_matches(['\r', '\n']).len()..];
+        *line = format!(
+            "{}{}{}",
+            &line[..entry.value_start],
+            raw_value,
+            line_terminator
+        );
+
+        entry.raw_value = raw_value;
+
+        true
+    }
+}
+
+impl fmt::Display for CfgFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            f.write_str(line)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_metadata_comment(line: &str, pending_metadata: &mut PendingMetadata) {
+    if let Some(setting_type) = line.strip_prefix(SETTING_TYPE_PREFIX) {
+        pending_metadata.setting_type = Some(setting_type.trim().to_string());
+    } else if let Some(default_value) = line.strip_prefix(DEFAULT_VALUE_PREFIX) {
+        pending_metadata.default_value = Some(default_value.trim().to_string());
+    } else if let Some(acceptable_values) = line.strip_prefix(ACCEPTABLE_VALUES_PREFIX) {
+        pending_metadata.acceptable_values = acceptable_values
+            .split(FLAGS_SEPARATOR)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+            .collect();
+    } else if let Some(range) = line.strip_prefix(ACCEPTABLE_VALUE_RANGE_PREFIX) {
+        // "From 0 to 100"
+        pending_metadata.acceptable_value_range = range
+            .trim()
+            .strip_prefix("From ")
+            .and_then(|range| range.split_once(" to "))
+            .map(|(min, max)| (min.trim().to_string(), max.trim().to_string()));
+    } else if line.starts_with(MULTIPLE_VALUES_PREFIX) {
+        pending_metadata.is_flags = true;
+    }
+}
+
+fn modified_time(file_full_path: &Path) -> Option<SystemTime> {
+    fs::metadata(file_full_path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+fn count_leading_spaces(text: &str) -> usize {
+    text.len() - text.trim_start_matches([' ', '\t']).len()
+}
+
+// Same escaping as BepInEx.Configuration.TomlTypeConverter
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len() + 2);
+
+    for c in text.chars() {
+        match c {
+            '\0' => escaped.push_str(r"\0"),
+            '\u{7}' => escaped.push_str(r"\a"),
+            '\u{8}' => escaped.push_str(r"\b"),
+            '\t' => escaped.push_str(r"\t"),
+            '\n' => escaped.push_str(r"\n"),
+            '\u{b}' => escaped.push_str(r"\v"),
+            '\u{c}' => escaped.push_str(r"\f"),
+            '\r' => escaped.push_str(r"\r"),
+            '\'' => escaped.push_str(r"\'"),
+            '\\' => escaped.push_str(r"\\"),
+            '"' => escaped.push_str(r#"\""#),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+fn unescape(text: &str) -> String {
+    // BepInEx doesn't unescape what looks like a windows path, "C:\..."
+    if looks_like_windows_path(text) {
+        return text.to_string();
+    }
+
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('0') => unescaped.push('\0'),
+            Some('a') => unescaped.push('\u{7}'),
+            Some('b') => unescaped.push('\u{8}'),
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some('v') => unescaped.push('\u{b}'),
+            Some('f') => unescaped.push('\u{c}'),
+            Some('r') => unescaped.push('\r'),
+            Some(c @ ('\'' | '\\' | '"')) => unescaped.push(c),
+            Some(c) => {
+                unescaped.push('\\');
+                unescaped.push(c);
+            }
+            None => unescaped.push('\\'),
+        }
+    }
+
+    unescaped
+}
+
+fn looks_like_windows_path(text: &str) -> bool {
+    let mut chars = text.trim_start_matches('"').chars();
+
+    matches!(
+        (chars.next(), chars.next(), chars.next()),
+        (Some(drive), Some(':'), Some('\\' | '/')) if drive.is_alphanumeric() || drive == '_'
+    )
+}
+
+// All the .cfg files directly inside the BepInEx/config folder, sorted by file name
+pub fn find_cfg_files(config_folder_full_path: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(config_folder_full_path) else {
+        return Vec::new();
+    };
+
+    let mut cfg_files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|extension| extension.eq_ignore_ascii_case(CFG_EXTENSION))
+        })
+        .collect();
+
+    cfg_files.sort_by_key(|path| path.file_name().map(|name| name.to_ascii_lowercase()));
+
+    cfg_files
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    // Trimmed down BepInEx.cfg as written by BepInEx 5.4, CRLF line endings
+    const BEPINEX_CFG: &str = "\u{feff}[Caching]\r
+\r
+## Enable/disable assembly metadata cache\r
+## Enabling this will speed up discovery of plugins and patchers by caching the metadata of all types BepInEx discovers.\r
+# Setting type: Boolean\r
+# Default value: true\r
+EnableAssemblyCache = true\r
+\r
+[Chainloader]\r
+\r
+## If enabled, hides BepInEx Manager GameObject from Unity.\r
+## This can fix loading issues in some games that attempt to prevent BepInEx from being loaded.\r
+## Use this only if you know what this option means, as it can affect functionality of some older plugins.\r
+## \r
+# Setting type: Boolean\r
+# Default value: false\r
+HideManagerGameObject = false\r
+\r
+[Logging.Console]\r
+\r
+## Which log levels to show in the console output.\r
+# Setting type: LogLevel\r
+# Default value: Fatal, Error, Warning, Message, Info\r
+# Acceptable values: None, Fatal, Error, Warning, Message, Info, Debug, All\r
+# Multiple values can be set at the same time by separating them with , (e.g. Debug, Warning)\r
+LogLevels = Fatal, Error, Warning, Message, Info\r
+\r
+[Preloader.Entrypoint]\r
+\r
+## The local filename of the assembly to target.\r
+# Setting type: String\r
+# Default value: UnityEngine.dll\r
+Assembly = UnityEngine.dll\r
+\r
+## The name of the type in the entrypoint assembly to search for the entrypoint method.\r
+# Setting type: String\r
+# Default value: Application\r
+Type = Camera\r
+";
+
+    // BepInEx.GUI.cfg, LF line endings and no trailing line break
+    const BEPINEX_GUI_CFG: &str = "## Settings file was created by plugin BepInEx.GUI v3.0.2
+## Plugin GUID: BepInEx.GUI
+
+[Settings]
+
+## Enable the custom BepInEx GUI
+# Setting type: Boolean
+# Default value: true
+Enable BepInEx GUI = true
+
+## Close the graphic user interface window when the game is loaded
+# Setting type: Boolean
+# Default value: false
+Close Window When Game Loaded = false
+
+## Close the graphic user interface window when the game closes
+# Setting type: Boolean
+# Default value: true
+Close Window When Game Closes = true";
+
+    fn temp_cfg_file_full_path() -> PathBuf {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let folder_full_path = std::env::temp_dir().join(format!(
+            "bepinex_gui_cfg_test_{}_{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&folder_full_path).unwrap();
+
+        folder_full_path.join("BepInEx.GUI.cfg")
+    }
+
+    #[test]
+    fn writes_back_byte_identical() {
+        for text in [BEPINEX_CFG, BEPINEX_GUI_CFG] {
+            assert_eq!(CfgFile::parse(text).to_string(), text);
+        }
+    }
+
+    #[test]
+    fn writes_back_byte_identical_after_setting_same_values() {
+        for text in [BEPINEX_CFG, BEPINEX_GUI_CFG] {
+            let mut cfg_file = CfgFile::parse(text);
+
+            let entries: Vec<(String, String, String)> = cfg_file
+                .sections()
+                .iter()
+                .flat_map(|section| {
+                    section.entries().iter().map(|entry| {
+                        (
+                            section.name().to_string(),
+                            entry.key().to_string(),
+                            entry.raw_value().to_string(),
+                        )
+                    })
+                })
+                .collect();
+            assert!(!entries.is_empty());
+
+            for (section_name, key, raw_value) in entries {
+                assert!(cfg_file.set_raw(&section_name, &key, &raw_value));
+            }
+
+            assert_eq!(cfg_file.to_string(), text);
+            assert!(!cfg_file.has_unsaved_changes());
+        }
+    }
+
+    #[test]
+    fn parses_entries_and_metadata() {
+        let cfg_file = CfgFile::parse(BEPINEX_CFG);
+
+        assert_eq!(
+            cfg_file.get("Caching", "EnableAssemblyCache"),
+            Some(Value::Bool(true))
+        );
+        assert_eq!(
+            cfg_file.get("Preloader.Entrypoint", "Type"),
+            Some(Value::String("Camera".to_string()))
+        );
+
+        let log_levels = cfg_file.entry("Logging.Console", "LogLevels").unwrap();
+        assert_eq!(
+            log_levels.default_value(),
+            Some("Fatal, Error, Warning, Message, Info")
+        );
+        assert_eq!(log_levels.acceptable_values().len(), 8);
+        assert_eq!(
+            log_levels.value(),
+            Value::Flags(
+                ["Fatal", "Error", "Warning", "Message", "Info"]
+                    .map(str::to_string)
+                    .to_vec()
+            )
+        );
+
+        let gui_cfg_file = CfgFile::parse(BEPINEX_GUI_CFG);
+        assert_eq!(
+            gui_cfg_file.header(),
+            [
+                "Settings file was created by plugin BepInEx.GUI v3.0.2",
+                "Plugin GUID: BepInEx.GUI"
+            ]
+        );
+        assert_eq!(
+            gui_cfg_file
+                .entry("Settings", "Close Window When Game Loaded")
+                .unwrap()
+                .description(),
+            "Close the graphic user interface window when the game is loaded"
+        );
+    }
+
+    #[test]
+    fn integer_values_must_fit_in_the_declared_type() {
+        let cfg_file = CfgFile::parse(
+            "[Limits]
+
+# Setting type: Byte
+# Default value: 0
+Byte = -1
+
+# Setting type: UInt16
+# Default value: 0
+UInt16 = 65535
+
+# Setting type: Int32
+# Default value: 0
+Int32 = -2147483648
+",
+        );
+
+        let byte = cfg_file.entry("Limits", "Byte").unwrap();
+        assert_eq!(byte.integer_type_range(), Some(0..=255));
+        assert_eq!(
+            byte.validation_error(),
+            Some("-1 doesn't fit in a Byte".to_string())
+        );
+
+        for key in ["UInt16", "Int32"] {
+            assert_eq!(
+                cfg_file.entry("Limits", key).unwrap().validation_error(),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn only_touches_the_changed_value() {
+        let mut cfg_file = CfgFile::parse(BEPINEX_CFG);
+
+        assert!(cfg_file.set("Chainloader", "HideManagerGameObject", &Value::Bool(true)));
+
+        assert_eq!(
+            cfg_file.to_string(),
+            BEPINEX_CFG.replace(
+                "HideManagerGameObject = false\r\n",
+                "HideManagerGameObject = true\r\n"
+            )
+        );
+    }
+
+    #[test]
+    fn merge_with_disk_keeps_concurrent_edits() {
+        let file_full_path = temp_cfg_file_full_path();
+        fs::write(&file_full_path, BEPINEX_GUI_CFG).unwrap();
+
+        let mut cfg_file = CfgFile::read(&file_full_path).unwrap();
+        cfg_file.set(
+            "Settings",
+            "Close Window When Game Loaded",
+            &Value::Bool(true),
+        );
+
+        // BepInEx or the user edits another entry meanwhile
+        let text_changed_on_disk =
+            BEPINEX_GUI_CFG.replace("Enable BepInEx GUI = true", "Enable BepInEx GUI = false");
+        fs::write(&file_full_path, &text_changed_on_disk).unwrap();
+
+        assert!(matches!(
+            cfg_file.save(&file_full_path),
+            Err(SaveError::ChangedOnDisk)
+        ));
+
+        cfg_file
+            .save_merging_changes_on_disk(&file_full_path)
+            .unwrap();
+
+        let saved_text = fs::read_to_string(&file_full_path).unwrap();
+        assert_eq!(
+            saved_text,
+            text_changed_on_disk.replace(
+                "Close Window When Game Loaded = false",
+                "Close Window When Game Loaded = true"
+            )
+        );
+        assert!(!cfg_file.has_unsaved_changes());
+        assert!(!cfg_file.has_changed_on_disk(&file_full_path).unwrap());
+
+        _ = fs::remove_dir_all(file_full_path.parent().unwrap());
+    }
+}
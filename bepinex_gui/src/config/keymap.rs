@@ -0,0 +1,176 @@
+use std::collections::BTreeMap;
+
+use eframe::egui::{Key, Modifiers};
+use serde::*;
+use strum::{Display, EnumIter, IntoEnumIterator};
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Display, EnumIter, Serialize, Deserialize,
+)]
+pub enum KeyCommand {
+    #[strum(serialize = "Focus Search")]
+    FocusSearch,
+    #[strum(serialize = "Select Next Error")]
+    NextError,
+    #[strum(serialize = "Select Previous Error")]
+    PreviousError,
+    #[strum(serialize = "Select All Logs")]
+    SelectAllLogs,
+    #[strum(serialize = "Clear Log Selection")]
+    ClearSelection,
+    #[strum(serialize = "Pause / Resume Game")]
+    PauseGame,
+    #[strum(serialize = "Scroll Console to Bottom")]
+    ScrollToBottom,
+    #[strum(serialize = "Next Tab")]
+    NextTab,
+    #[strum(serialize = "Previous Tab")]
+    PreviousTab,
+}
+
+impl KeyCommand {
+    const fn default_key_binding(self) -> KeyBinding {
+        match self {
+            Self::FocusSearch => KeyBinding::new(Key::F, true, false),
+            Self::NextError => KeyBinding::new(Key::F8, false, false),
+            Self::PreviousError => KeyBinding::new(Key::F8, false, true),
+            Self::SelectAllLogs => KeyBinding::new(Key::A, true, false),
+            Self::ClearSelection => KeyBinding::new(Key::Escape, false, false),
+            Self::PauseGame => KeyBinding::new(Key::F9, false, false),
+            Self::ScrollToBottom => KeyBinding::new(Key::End, false, false),
+            Self::NextTab => KeyBinding::new(Key::Tab, true, false),
+            Self::PreviousTab => KeyBinding::new(Key::Tab, true, true),
+        }
+    }
+}
+
+// Shortcuts that are handled outside of the keymap and can't be rebound
+const RESERVED_KEY_BINDINGS: [(KeyBinding, &str); 4] = [
+    (KeyBinding::new(Key::P, true, false), "Command Palette"),
+    (KeyBinding::new(Key::P, true, true), "Command Palette"),
+    (KeyBinding::new(Key::C, true, false), "Copy Selected Logs"),
+    (KeyBinding::new(Key::F5, true, false), "Kill GUI & Game"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyBinding {
+    pub key: Key,
+    // Ctrl, or Cmd on mac
+    pub command: bool,
+    pub shift: bool,
+    #[serde(default)]
+    pub alt: bool,
+}
+
+impl KeyBinding {
+    pub const fn new(key: Key, command: bool, shift: bool) -> Self {
+        Self {
+            key,
+            command,
+            shift,
+            alt: false,
+        }
+    }
+
+    pub fn from_modifiers(key: Key, modifiers: Modifiers) -> Self {
+        Self {
+            key,
+            command: modifiers.command,
+            shift: modifiers.shift,
+            alt: modifiers.alt,
+        }
+    }
+
+    pub fn modifiers(&self) -> Modifiers {
+        let mut modifiers = Modifiers::NONE;
+        if self.command {
+            modifiers = modifiers | Modifiers::COMMAND;
+        }
+        if self.shift {
+            modifiers = modifiers | Modifiers::SHIFT;
+        }
+        if self.alt {
+            modifiers = modifiers | Modifiers::ALT;
+        }
+        modifiers
+    }
+}
+
+impl std::fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.command {
+            f.write_str("Ctrl + ")?;
+        }
+        if self.shift {
+            f.write_str("Shift + ")?;
+        }
+        if self.alt {
+            f.write_str("Alt + ")?;
+        }
+        f.write_str(self.key.name())
+    }
+}
+
+// Only the bindings the user changed are stored, so that new commands get their default binding
+#[derive(Default, Serialize, Deserialize)]
+pub struct Keymap {
+    // None when the user removed the binding
+    overrides: BTreeMap<KeyCommand, Option<KeyBinding>>,
+}
+
+impl Keymap {
+    pub fn key_binding(&self, key_command: KeyCommand) -> Option<KeyBinding> {
+        self.overrides
+            .get(&key_command)
+            .copied()
+            .unwrap_or(Some(key_command.default_key_binding()))
+    }
+
+    pub fn set_key_binding(&mut self, key_command: KeyCommand, key_binding: Option<KeyBinding>) {
+        if key_binding == Some(key_command.default_key_binding()) {
+            self.overrides.remove(&key_command);
+        } else {
+            self.overrides.insert(key_command, key_binding);
+        }
+    }
+
+    pub fn is_default(&self, key_command: KeyCommand) -> bool {
+        !self.overrides.contains_key(&key_command)
+    }
+
+    pub fn reset(&mut self, key_command: KeyCommand) {
+        self.overrides.remove(&key_command);
+    }
+
+    // What else the key binding of that command triggers, if anything
+    pub fn conflict(&self, key_command: KeyCommand) -> Option<String> {
+        let key_binding = self.key_binding(key_command)?;
+
+        if let Some((_, reserved_name)) = RESERVED_KEY_BINDINGS
+            .iter()
+            .find(|(reserved_key_binding, _)| *reserved_key_binding == key_binding)
+        {
+            return Some(format!("{reserved_name} (can't be changed)"));
+        }
+
+        KeyCommand::iter()
+            .filter(|other_key_command| *other_key_command != key_command)
+            .find(|other_key_command| self.key_binding(*other_key_command) == Some(key_binding))
+            .map(|other_key_command| other_key_command.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alt_is_part_of_the_key_binding() {
+        let modifiers = Modifiers::COMMAND | Modifiers::ALT;
+        let key_binding = KeyBinding::from_modifiers(Key::K, modifiers);
+
+        assert_eq!(key_binding.modifiers(), modifiers);
+        assert_eq!(key_binding.to_string(), "Ctrl + Alt + K");
+        assert_ne!(key_binding, KeyBinding::new(Key::K, true, false));
+    }
+}
@@ -1,9 +1,11 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use sysinfo::Pid;
 
 use crate::app;
 
+const BEPINEX_CFG_FILE_NAME: &str = "BepInEx.cfg";
+
 pub struct AppLaunchConfig {
     target_name: String,
     game_folder_full_path: PathBuf,
@@ -29,8 +31,8 @@ impl AppLaunchConfig {
                 game_folder_full_path: (&args[3]).into(),
                 bepinex_log_output_file_full_path: (&args[4]).into(),
                 bepinex_gui_csharp_cfg_full_path: (&args[5]).into(),
-                target_process_id: args[6].parse::<Pid>().unwrap(),
-                log_socket_port_receiver: args[7].parse::<u16>().unwrap(),
+                target_process_id: args[6].parse::<Pid>().ok()?,
+                log_socket_port_receiver: args[7].parse::<u16>().ok()?,
                 window_title,
             })
         } else {
@@ -71,6 +73,51 @@ impl AppLaunchConfig {
         &self.bepinex_gui_csharp_cfg_full_path
     }
 
+    // r2modman and the Thunderstore Mod Manager keep each modpack in
+    // <data folder>/<game>/profiles/<modpack name>/BepInEx
+    pub fn modpack_name(&self) -> Option<String> {
+        let modpack_folder_full_path = self
+            .bepinex_gui_csharp_cfg_full_path
+            .parent()? // config
+            .parent()? // BepInEx
+            .parent()?;
+
+        if modpack_folder_full_path.parent()?.file_name()? != "profiles" {
+            return None;
+        }
+
+        Some(
+            modpack_folder_full_path
+                .file_name()?
+                .to_string_lossy()
+                .to_string(),
+        )
+    }
+
+    // Next to BepInEx.GUI.cfg and not in the game folder,
+    // mod managers like r2modman have a BepInEx folder per profile
+    pub fn bepinex_config_folder_full_path(&self) -> PathBuf {
+        self.bepinex_gui_csharp_cfg_full_path.parent().map_or_else(
+            || self.game_folder_full_path.join("BepInEx").join("config"),
+            Path::to_path_buf,
+        )
+    }
+
+    // Holds the plugins, patchers and config folders
+    pub fn bepinex_folder_full_path(&self) -> PathBuf {
+        let config_folder_full_path = self.bepinex_config_folder_full_path();
+
+        config_folder_full_path
+            .parent()
+            .map_or(config_folder_full_path.clone(), Path::to_path_buf)
+    }
+
+    // Next to BepInEx.GUI.cfg, which is also right when a mod manager redirects the BepInEx folder
+    pub fn bepinex_cfg_full_path(&self) -> PathBuf {
+        self.bepinex_gui_csharp_cfg_full_path
+            .with_file_name(BEPINEX_CFG_FILE_NAME)
+    }
+
     pub const fn target_process_id(&self) -> Pid {
         self.target_process_id
     }
@@ -79,7 +126,68 @@ impl AppLaunchConfig {
         self.log_socket_port_receiver
     }
 
+    // When the GUI follows a new instance of the game
+    pub fn reattach(&mut self, target_process_id: Pid, log_socket_port_receiver: u16) {
+        self.target_process_id = target_process_id;
+        self.log_socket_port_receiver = log_socket_port_receiver;
+    }
+
     pub fn window_title(&self) -> &str {
         self.window_title.as_ref()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn launch_config(bepinex_folder_full_path: &str) -> AppLaunchConfig {
+        let args: Vec<String> = [
+            "bepinex_gui",
+            "5.4.21",
+            "Risk of Rain 2",
+            "/games/Risk of Rain 2",
+            &format!("{bepinex_folder_full_path}/LogOutput.log"),
+            &format!("{bepinex_folder_full_path}/config/BepInEx.GUI.cfg"),
+            "1234",
+            "27090",
+        ]
+        .map(str::to_string)
+        .to_vec();
+
+        AppLaunchConfig::from(&args).unwrap()
+    }
+
+    #[test]
+    fn config_folder_is_in_the_game_folder_without_mod_manager() {
+        let launch_config = launch_config("/games/Risk of Rain 2/BepInEx");
+
+        assert_eq!(
+            launch_config.bepinex_config_folder_full_path(),
+            PathBuf::from("/games/Risk of Rain 2/BepInEx/config")
+        );
+        assert_eq!(launch_config.modpack_name(), None);
+    }
+
+    #[test]
+    fn config_folder_follows_mod_manager_profiles() {
+        let launch_config =
+            launch_config("/data/r2modmanPlus-local/RiskOfRain2/profiles/Modpack/BepInEx");
+
+        assert_eq!(
+            launch_config.bepinex_config_folder_full_path(),
+            PathBuf::from("/data/r2modmanPlus-local/RiskOfRain2/profiles/Modpack/BepInEx/config")
+        );
+        assert_eq!(
+            launch_config.bepinex_cfg_full_path(),
+            PathBuf::from(
+                "/data/r2modmanPlus-local/RiskOfRain2/profiles/Modpack/BepInEx/config/BepInEx.cfg"
+            )
+        );
+        assert_eq!(
+            launch_config.bepinex_folder_full_path(),
+            PathBuf::from("/data/r2modmanPlus-local/RiskOfRain2/profiles/Modpack/BepInEx")
+        );
+        assert_eq!(launch_config.modpack_name().as_deref(), Some("Modpack"));
+    }
+}
@@ -1,6 +1,5 @@
 use std::{
-    fs::File,
-    io::{self, BufRead, BufReader, Seek, SeekFrom, Write},
+    io,
     path::PathBuf,
     sync::{
         atomic::{AtomicBool, Ordering},
@@ -12,7 +11,25 @@ use serde::*;
 
 use crate::{app, data::bepinex_log::LogLevel};
 
+use self::{
+    alert_rules::AlertRule,
+    bepinex_cfg::{CfgFile, Value},
+    keymap::Keymap,
+    profiles::{ConfigProfile, ConfigProfiles},
+};
+
+pub mod alert_rules;
+pub mod bepinex_cfg;
+pub mod keymap;
 pub mod launch;
+pub mod profiles;
+pub mod schema;
+pub mod snapshots;
+
+// Must match BepInEx.GUI.Loader Config.cs
+const BEPINEX_GUI_CFG_SECTION: &str = "Settings";
+const CLOSE_WINDOW_WHEN_GAME_LOADED_CFG_KEY: &str = "Close Window When Game Loaded";
+const CLOSE_WINDOW_WHEN_GAME_CLOSES_CFG_KEY: &str = "Close Window When Game Closes";
 
 #[derive(Serialize, Deserialize)]
 pub struct Config {
@@ -45,6 +62,40 @@ pub struct Config {
 
     #[serde(skip)]
     pub bepinex_gui_csharp_cfg_full_path: PathBuf,
+
+    // Named settings profiles, per game and optionally per modpack
+    #[serde(default)]
+    pub profiles: ConfigProfiles,
+
+    #[serde(skip)]
+    pub profile_target_name: String,
+
+    #[serde(skip)]
+    pub profile_modpack_name: Option<String>,
+
+    // Rebindable keyboard shortcuts, shared by all the profiles
+    #[serde(default)]
+    pub keymap: Keymap,
+
+    // For asking before closing the game from the GUI
+    #[serde(default = "default_confirm_kill_game")]
+    pub confirm_kill_game: bool,
+
+    // How long the game gets for quitting by itself before being killed
+    #[serde(default = "default_game_shutdown_timeout_secs")]
+    pub game_shutdown_timeout_secs: u32,
+
+    // Checked against every log the console receives
+    #[serde(default)]
+    pub alert_rules: Vec<AlertRule>,
+}
+
+const fn default_confirm_kill_game() -> bool {
+    true
+}
+
+const fn default_game_shutdown_timeout_secs() -> u32 {
+    10
 }
 
 impl Default for Config {
@@ -60,96 +111,167 @@ impl Default for Config {
             close_window_when_game_loaded: false,
             close_window_when_game_closes: Arc::new(AtomicBool::new(true)),
             bepinex_gui_csharp_cfg_full_path: Default::default(),
+            profiles: Default::default(),
+            profile_target_name: Default::default(),
+            profile_modpack_name: None,
+            keymap: Default::default(),
+            confirm_kill_game: default_confirm_kill_game(),
+            game_shutdown_timeout_secs: default_game_shutdown_timeout_secs(),
+            alert_rules: Vec::new(),
         }
     }
 }
 
 impl Config {
     pub fn read_bepinex_toml_cfg_file(&mut self) -> io::Result<()> {
-        let file = File::open(&self.bepinex_gui_csharp_cfg_full_path)?;
-        let reader = BufReader::new(file);
-
-        let mut current_settings_category_name: &str;
-
-        for line in reader.lines().flatten() {
-            if line.starts_with('[') {
-                current_settings_category_name = line.split('[').collect::<Vec<&str>>()[1]
-                    .split(']')
-                    .collect::<Vec<&str>>()[0];
-                tracing::info!(
-                    "current_settings_category_name: {}",
-                    current_settings_category_name
-                );
-            } else if line.starts_with("##") || line.starts_with("# ") {
-            } else if line.contains('=') {
-                let setting = line.split('=').collect::<Vec<&str>>();
-                let setting_name = setting[0].trim();
-                let settings_current_value = setting[1].trim();
-
-                let bool_setting = settings_current_value.parse::<bool>();
-                if let Ok(bool_value) = bool_setting {
-                    tracing::info!("{:?}: {:?}", setting_name, bool_value);
-                    if setting_name == "Close Window When Game Loaded" {
-                        self.close_window_when_game_loaded = bool_value;
-                    } else if setting_name == "Close Window When Game Closes" {
-                        self.close_window_when_game_closes
-                            .store(bool_value, Ordering::Relaxed);
-                    }
-                }
-            }
+        let cfg_file = CfgFile::read(&self.bepinex_gui_csharp_cfg_full_path)?;
+
+        if let Some(Value::Bool(value)) = cfg_file.get(
+            BEPINEX_GUI_CFG_SECTION,
+            CLOSE_WINDOW_WHEN_GAME_LOADED_CFG_KEY,
+        ) {
+            self.close_window_when_game_loaded = value;
+        }
+
+        if let Some(Value::Bool(value)) = cfg_file.get(
+            BEPINEX_GUI_CFG_SECTION,
+            CLOSE_WINDOW_WHEN_GAME_CLOSES_CFG_KEY,
+        ) {
+            self.close_window_when_game_closes
+                .store(value, Ordering::Relaxed);
         }
 
         Ok(())
     }
 
     pub fn save_bepinex_toml_cfg_file(&self) -> io::Result<()> {
-        let mut file = std::fs::OpenOptions::new()
-            .read(true)
-            .write(true)
-            .open(&self.bepinex_gui_csharp_cfg_full_path)?;
-        let reader = BufReader::new(&file);
-
-        let mut lines: Vec<String> = Vec::new();
-
-        for res in reader.lines() {
-            match res {
-                Ok(line_) => {
-                    let mut line = line_.to_string();
-                    if line_.contains('=') {
-                        let setting = line_.split('=').collect::<Vec<&str>>();
-                        let setting_name = setting[0].trim();
-
-                        if setting_name == "Close Window When Game Loaded" {
-                            line = format!(
-                                "Close Window When Game Loaded = {}",
-                                self.close_window_when_game_loaded
-                            );
-                        } else if setting_name == "Close Window When Game Closes" {
-                            line = format!(
-                                "Close Window When Game Closes = {}",
-                                self.close_window_when_game_closes.load(Ordering::Relaxed)
-                            );
-                        }
-                    }
-
-                    line += "\n";
-                    lines.push(line);
-                }
-                Err(err) => return Err(err),
+        let mut cfg_file = CfgFile::read(&self.bepinex_gui_csharp_cfg_full_path)?;
+
+        cfg_file.set(
+            BEPINEX_GUI_CFG_SECTION,
+            CLOSE_WINDOW_WHEN_GAME_LOADED_CFG_KEY,
+            &Value::Bool(self.close_window_when_game_loaded),
+        );
+
+        cfg_file.set(
+            BEPINEX_GUI_CFG_SECTION,
+            CLOSE_WINDOW_WHEN_GAME_CLOSES_CFG_KEY,
+            &Value::Bool(self.close_window_when_game_closes.load(Ordering::Relaxed)),
+        );
+
+        cfg_file.save_merging_changes_on_disk(&self.bepinex_gui_csharp_cfg_full_path)
+    }
+
+    // Must be called once the BepInEx.GUI.cfg file is read.
+    // The file may have been edited while the GUI was closed, so at startup its window behaviour
+    // goes into the selected profile instead of the other way around
+    pub fn init_profiles(&mut self, target_name: &str, modpack_name: Option<String>) {
+        self.profile_target_name = target_name.to_string();
+        self.profile_modpack_name = modpack_name;
+
+        let scope_key = self.profile_scope_key();
+        match self.profiles.selected_profile(&scope_key).cloned() {
+            Some(mut profile) => {
+                profile.close_window_when_game_loaded = self.close_window_when_game_loaded;
+                profile.close_window_when_game_closes =
+                    self.close_window_when_game_closes.load(Ordering::Relaxed);
+                self.apply_profile(&profile);
             }
+            None => self.store_current_profile(),
         }
+    }
 
-        file.seek(SeekFrom::Start(0))?;
-        file.set_len(0)?;
+    pub fn profile_scope_key(&self) -> String {
+        self.profiles.scope_key(
+            &self.profile_target_name,
+            self.profile_modpack_name.as_deref(),
+        )
+    }
 
-        for line in &lines {
-            match file.write(line.as_bytes()) {
-                Ok(_) => {}
-                Err(err) => return Err(err),
-            }
+    fn make_profile(&self) -> ConfigProfile {
+        ConfigProfile {
+            dark_mode: self.dark_mode,
+            log_level_filter: self.log_level_filter,
+            log_auto_scroll_to_bottom: self.log_auto_scroll_to_bottom,
+            close_window_when_game_loaded: self.close_window_when_game_loaded,
+            close_window_when_game_closes: self
+                .close_window_when_game_closes
+                .load(Ordering::Relaxed),
         }
+    }
 
-        Ok(())
+    fn apply_profile(&mut self, profile: &ConfigProfile) {
+        self.theme_just_changed |= self.dark_mode != profile.dark_mode;
+        self.dark_mode = profile.dark_mode;
+        self.log_level_filter = profile.log_level_filter;
+        self.log_auto_scroll_to_bottom = profile.log_auto_scroll_to_bottom;
+
+        // only touch the file when the profile changes what it says
+        let is_window_behaviour_changed = self.close_window_when_game_loaded
+            != profile.close_window_when_game_loaded
+            || self.close_window_when_game_closes.load(Ordering::Relaxed)
+                != profile.close_window_when_game_closes;
+        if !is_window_behaviour_changed {
+            return;
+        }
+
+        self.close_window_when_game_loaded = profile.close_window_when_game_loaded;
+        self.close_window_when_game_closes
+            .store(profile.close_window_when_game_closes, Ordering::Relaxed);
+
+        if let Err(err) = self.save_bepinex_toml_cfg_file() {
+            tracing::error!("Failed saving the GUI config file: {}", err);
+        }
+    }
+
+    // Applies the selected profile of the current scope, or creates it from the current settings
+    fn load_selected_profile(&mut self) {
+        let scope_key = self.profile_scope_key();
+
+        match self.profiles.selected_profile(&scope_key).cloned() {
+            Some(profile) => self.apply_profile(&profile),
+            None => self.store_current_profile(),
+        }
+    }
+
+    pub fn store_current_profile(&mut self) {
+        let scope_key = self.profile_scope_key();
+        let profile = self.make_profile();
+        self.profiles.store_selected_profile(&scope_key, profile);
+    }
+
+    pub fn switch_profile(&mut self, profile_name: &str) {
+        self.store_current_profile();
+
+        self.profiles
+            .select(&self.profile_scope_key(), profile_name);
+        self.load_selected_profile();
+    }
+
+    // The new profile starts as a copy of the current settings
+    pub fn create_profile(&mut self, profile_name: &str) {
+        self.store_current_profile();
+
+        let scope_key = self.profile_scope_key();
+        let profile = self.make_profile();
+        self.profiles
+            .insert_and_select(&scope_key, profile_name, profile);
+    }
+
+    pub fn remove_selected_profile(&mut self) {
+        let scope_key = self.profile_scope_key();
+        let selected_profile_name = self.profiles.selected_profile_name(&scope_key).to_string();
+
+        self.profiles.remove(&scope_key, &selected_profile_name);
+        self.load_selected_profile();
+    }
+
+    pub fn set_profiles_per_modpack(&mut self, is_per_modpack: bool) {
+        self.store_current_profile();
+
+        self.profiles
+            .set_per_modpack(&self.profile_target_name, is_per_modpack);
+        self.load_selected_profile();
     }
 }
 
@@ -159,3 +281,97 @@ pub fn get_app_ron_file_full_path() -> Option<PathBuf> {
         data_dir.join("app.ron")
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    const BEPINEX_GUI_CFG: &str = "[Settings]
+
+# Setting type: Boolean
+# Default value: false
+Close Window When Game Loaded = false
+
+# Setting type: Boolean
+# Default value: true
+Close Window When Game Closes = true
+";
+
+    fn config_with_gui_cfg_file(test_name: &str, text: &str) -> Config {
+        let folder_full_path = std::env::temp_dir().join(format!(
+            "bepinex_gui_profiles_test_{}_{}",
+            test_name,
+            std::process::id()
+        ));
+        fs::create_dir_all(&folder_full_path).unwrap();
+
+        let mut config = Config {
+            bepinex_gui_csharp_cfg_full_path: folder_full_path.join("BepInEx.GUI.cfg"),
+            ..Default::default()
+        };
+        fs::write(&config.bepinex_gui_csharp_cfg_full_path, text).unwrap();
+        config.read_bepinex_toml_cfg_file().unwrap();
+
+        config
+    }
+
+    fn read_close_window_when_game_loaded(config: &Config) -> Option<Value> {
+        CfgFile::read(&config.bepinex_gui_csharp_cfg_full_path)
+            .unwrap()
+            .get(
+                BEPINEX_GUI_CFG_SECTION,
+                CLOSE_WINDOW_WHEN_GAME_LOADED_CFG_KEY,
+            )
+    }
+
+    #[test]
+    fn switching_profile_writes_the_window_behaviour_to_the_gui_cfg_file() {
+        let mut config = config_with_gui_cfg_file("switch", BEPINEX_GUI_CFG);
+        config.init_profiles("Risk of Rain 2", None);
+
+        config.create_profile("Streaming");
+        config.close_window_when_game_loaded = true;
+        config.switch_profile(profiles::DEFAULT_PROFILE_NAME);
+
+        assert!(!config.close_window_when_game_loaded);
+        assert_eq!(
+            read_close_window_when_game_loaded(&config),
+            Some(Value::Bool(false))
+        );
+
+        config.switch_profile("Streaming");
+
+        assert!(config.close_window_when_game_loaded);
+        assert_eq!(
+            read_close_window_when_game_loaded(&config),
+            Some(Value::Bool(true))
+        );
+    }
+
+    // The file can be edited while the GUI is closed
+    #[test]
+    fn gui_cfg_file_wins_over_the_profile_at_startup() {
+        let mut config = config_with_gui_cfg_file("startup", BEPINEX_GUI_CFG);
+        config.init_profiles("Risk of Rain 2", None);
+
+        let mut config = Config {
+            profiles: std::mem::take(&mut config.profiles),
+            ..config_with_gui_cfg_file(
+                "startup",
+                &BEPINEX_GUI_CFG.replace(
+                    "Close Window When Game Loaded = false",
+                    "Close Window When Game Loaded = true",
+                ),
+            )
+        };
+        config.init_profiles("Risk of Rain 2", None);
+
+        assert!(config.close_window_when_game_loaded);
+        assert_eq!(
+            read_close_window_when_game_loaded(&config),
+            Some(Value::Bool(true))
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::*;
+
+use crate::data::bepinex_log::LogLevel;
+
+pub const DEFAULT_PROFILE_NAME: &str = "Default";
+
+// The user facing settings of the GUI, the one time disclaimers and the selected tab stay global
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ConfigProfile {
+    pub dark_mode: bool,
+    pub log_level_filter: LogLevel,
+    pub log_auto_scroll_to_bottom: bool,
+    // Window behaviour, written to BepInEx.GUI.cfg when the profile is applied
+    #[serde(default)]
+    pub close_window_when_game_loaded: bool,
+    #[serde(default = "default_close_window_when_game_closes")]
+    pub close_window_when_game_closes: bool,
+}
+
+const fn default_close_window_when_game_closes() -> bool {
+    true
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct ProfileScope {
+    selected_profile_name: String,
+    profiles: BTreeMap<String, ConfigProfile>,
+}
+
+// Profiles are scoped per game, and optionally per modpack of that game
+#[derive(Default, Serialize, Deserialize)]
+pub struct ConfigProfiles {
+    // key is the game name, or "game name/modpack name"
+    scopes: BTreeMap<String, ProfileScope>,
+    // games for which each modpack gets its own set of profiles
+    per_modpack_games: BTreeSet<String>,
+}
+
+impl ConfigProfiles {
+    pub fn scope_key(&self, target_name: &str, modpack_name: Option<&str>) -> String {
+        match modpack_name {
+            Some(modpack_name) if self.per_modpack_games.contains(target_name) => {
+                format!("{target_name}/{modpack_name}")
+            }
+            _ => target_name.to_string(),
+        }
+    }
+
+    pub fn is_per_modpack(&self, target_name: &str) -> bool {
+        self.per_modpack_games.contains(target_name)
+    }
+
+    pub fn set_per_modpack(&mut self, target_name: &str, is_per_modpack: bool) {
+        if is_per_modpack {
+            self.per_modpack_games.insert(target_name.to_string());
+        } else {
+            self.per_modpack_games.remove(target_name);
+        }
+    }
+
+    pub fn profile_names(&self, scope_key: &str) -> Vec<String> {
+        self.scopes
+            .get(scope_key)
+            .map(|scope| scope.profiles.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn selected_profile_name(&self, scope_key: &str) -> &str {
+        self.scopes
+            .get(scope_key)
+            .map_or(DEFAULT_PROFILE_NAME, |scope| &scope.selected_profile_name)
+    }
+
+    pub fn selected_profile(&self, scope_key: &str) -> Option<&ConfigProfile> {
+        self.scopes
+            .get(scope_key)
+            .and_then(|scope| scope.profiles.get(&scope.selected_profile_name))
+    }
+
+    pub fn store_selected_profile(&mut self, scope_key: &str, profile: ConfigProfile) {
+        let scope = self.scope_mut(scope_key);
+        let selected_profile_name = scope.selected_profile_name.clone();
+        scope.profiles.insert(selected_profile_name, profile);
+    }
+
+    pub fn insert_and_select(&mut self, scope_key: &str, name: &str, profile: ConfigProfile) {
+        let scope = self.scope_mut(scope_key);
+        scope.profiles.insert(name.to_string(), profile);
+        scope.selected_profile_name = name.to_string();
+    }
+
+    pub fn select(&mut self, scope_key: &str, name: &str) {
+        let scope = self.scope_mut(scope_key);
+        if scope.profiles.contains_key(name) {
+            scope.selected_profile_name = name.to_string();
+        }
+    }
+
+    // The default profile can't be removed, it is where the selection falls back to
+    pub fn remove(&mut self, scope_key: &str, name: &str) {
+        if name == DEFAULT_PROFILE_NAME {
+            return;
+        }
+
+        let scope = self.scope_mut(scope_key);
+        scope.profiles.remove(name);
+        if scope.selected_profile_name == name {
+            scope.selected_profile_name = DEFAULT_PROFILE_NAME.to_string();
+        }
+    }
+
+    fn scope_mut(&mut self, scope_key: &str) -> &mut ProfileScope {
+        self.scopes
+            .entry(scope_key.to_string())
+            .or_insert_with(|| ProfileScope {
+                selected_profile_name: DEFAULT_PROFILE_NAME.to_string(),
+                profiles: BTreeMap::new(),
+            })
+    }
+}
@@ -0,0 +1,267 @@
+use serde::*;
+use serde_json::{Map, Value};
+
+use crate::{app, data::bepinex_log::LogLevel};
+
+use super::Config;
+
+// Version 0 is the unversioned RON blob that eframe stored under app::NAME
+const SETTINGS_STORAGE_KEY: &str = "settings";
+
+pub const CURRENT_VERSION: u32 = 2;
+
+type Migration = fn(&mut Map<String, Value>);
+
+// MIGRATIONS[n] migrates the settings from version n to version n + 1
+const MIGRATIONS: [Migration; CURRENT_VERSION as usize] = [migrate_v0_to_v1, migrate_v1_to_v2];
+
+#[derive(Serialize, Deserialize)]
+struct VersionedSettings {
+    version: u32,
+    settings: Value,
+}
+
+// Untyped RON loses the variant names of unit enums, so those v0 fields are also read typed
+#[derive(Deserialize)]
+struct V0EnumSettings {
+    log_level_filter: LogLevel,
+}
+
+pub fn load(storage: &dyn eframe::Storage) -> Config {
+    let (version, settings) = match read_versioned_settings(storage) {
+        Some(versioned_settings) => (versioned_settings.version, versioned_settings.settings),
+        None => match read_v0_settings(storage) {
+            Some(legacy_settings) => (0, legacy_settings),
+            None => return Config::default(),
+        },
+    };
+
+    let Value::Object(mut settings) = settings else {
+        tracing::error!("Settings are not an object, resetting them");
+        return Config::default();
+    };
+
+    if version > CURRENT_VERSION {
+        // downgraded GUI, the fields it knows about are still loaded below
+        tracing::warn!(
+            "Settings version {} is newer than the supported {}",
+            version,
+            CURRENT_VERSION
+        );
+    }
+
+    migrate(version, &mut settings);
+
+    deserialize_repairing_invalid_fields(settings)
+}
+
+pub fn save(storage: &mut dyn eframe::Storage, config: &Config) {
+    let settings = match serde_json::to_value(config) {
+        Ok(settings) => settings,
+        Err(err) => {
+            tracing::error!("Failed serializing settings: {}", err);
+            return;
+        }
+    };
+
+    let versioned_settings = VersionedSettings {
+        version: CURRENT_VERSION,
+        settings,
+    };
+
+    match serde_json::to_string(&versioned_settings) {
+        Ok(json) => storage.set_string(SETTINGS_STORAGE_KEY, json),
+        Err(err) => tracing::error!("Failed serializing settings: {}", err),
+    }
+}
+
+// Read untyped, the v0 blob doesn't match the current Config anymore
+fn read_v0_settings(storage: &dyn eframe::Storage) -> Option<Value> {
+    let mut settings = eframe::get_value::<Value>(storage, app::NAME)?;
+
+    if let (Value::Object(settings), Some(enum_settings)) = (
+        &mut settings,
+        eframe::get_value::<V0EnumSettings>(storage, app::NAME),
+    ) {
+        if let Ok(log_level_filter) = serde_json::to_value(enum_settings.log_level_filter) {
+            settings.insert("log_level_filter".to_string(), log_level_filter);
+        }
+    }
+
+    Some(settings)
+}
+
+fn read_versioned_settings(storage: &dyn eframe::Storage) -> Option<VersionedSettings> {
+    let json = storage.get_string(SETTINGS_STORAGE_KEY)?;
+
+    match serde_json::from_str(&json) {
+        Ok(versioned_settings) => Some(versioned_settings),
+        Err(err) => {
+            tracing::error!("Failed reading versioned settings: {}", err);
+            None
+        }
+    }
+}
+
+fn migrate(version: u32, settings: &mut Map<String, Value>) {
+    for (from_version, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        tracing::info!(
+            "Migrating settings from version {} to {}",
+            from_version,
+            from_version + 1
+        );
+        migration(settings);
+    }
+}
+
+// The Timeline and Configs tabs were inserted before the Settings tab
+fn migrate_v0_to_v1(settings: &mut Map<String, Value>) {
+    const V0_SETTINGS_TAB_INDEX: u64 = 2;
+    const V1_SETTINGS_TAB_INDEX: u64 = 4;
+
+    if settings.get("selected_tab_index").and_then(Value::as_u64) == Some(V0_SETTINGS_TAB_INDEX) {
+        settings.insert(
+            "selected_tab_index".to_string(),
+            V1_SETTINGS_TAB_INDEX.into(),
+        );
+    }
+}
+
+// The Resources tab was inserted before the Configs tab
+fn migrate_v1_to_v2(settings: &mut Map<String, Value>) {
+    const V1_CONFIGS_TAB_INDEX: u64 = 3;
+
+    if let Some(selected_tab_index) = settings
+        .get("selected_tab_index")
+        .and_then(Value::as_u64)
+        .filter(|selected_tab_index| *selected_tab_index >= V1_CONFIGS_TAB_INDEX)
+    {
+        settings.insert(
+            "selected_tab_index".to_string(),
+            (selected_tab_index + 1).into(),
+        );
+    }
+}
+
+// A field that doesn't deserialize is reset to its default value instead of the whole settings
+fn deserialize_repairing_invalid_fields(settings: Map<String, Value>) -> Config {
+    let Ok(Value::Object(default_settings)) = serde_json::to_value(Config::default()) else {
+        return Config::default();
+    };
+
+    let mut repaired_settings = default_settings.clone();
+
+    for (field_name, default_value) in default_settings {
+        let Some(value) = settings.get(&field_name) else {
+            continue;
+        };
+
+        repaired_settings.insert(field_name.clone(), value.clone());
+
+        if let Err(err) = serde_json::from_value::<Config>(Value::Object(repaired_settings.clone()))
+        {
+            tracing::warn!("Resetting invalid setting {}: {}", field_name, err);
+            repaired_settings.insert(field_name, default_value);
+        }
+    }
+
+    serde_json::from_value(Value::Object(repaired_settings)).unwrap_or_default()
+}
+
+// Checks that can't be expressed through the types
+pub fn validate(config: &mut Config, tab_count: usize) {
+    if config.selected_tab_index >= tab_count {
+        tracing::warn!(
+            "Resetting invalid setting selected_tab_index: {} >= {}",
+            config.selected_tab_index,
+            tab_count
+        );
+        config.selected_tab_index = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use eframe::Storage;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct MemoryStorage(HashMap<String, String>);
+
+    impl Storage for MemoryStorage {
+        fn get_string(&self, key: &str) -> Option<String> {
+            self.0.get(key).cloned()
+        }
+
+        fn set_string(&mut self, key: &str, value: String) {
+            self.0.insert(key.to_string(), value);
+        }
+
+        fn flush(&mut self) {}
+    }
+
+    // As written by eframe before the settings were versioned
+    const V0_RON_BLOB: &str = "(dark_mode:false,first_time:false,first_time_console_disclaimer:false,selected_tab_index:2,log_level_filter:Warning,log_auto_scroll_to_bottom:false)";
+
+    #[test]
+    fn v0_ron_blob_is_migrated() {
+        let mut storage = MemoryStorage::default();
+        storage.set_string(app::NAME, V0_RON_BLOB.to_string());
+
+        let config = load(&storage);
+
+        assert!(!config.dark_mode);
+        assert!(!config.first_time);
+        assert!(!config.first_time_console_disclaimer);
+        assert_eq!(config.selected_tab_index, 5);
+        assert_eq!(config.log_level_filter, LogLevel::Warning);
+        assert!(!config.log_auto_scroll_to_bottom);
+        // fields that didn't exist in v0 get their default value
+        assert!(config.confirm_kill_game);
+    }
+
+    #[test]
+    fn migrate_v0_to_v1_only_moves_the_settings_tab() {
+        let mut settings = Map::new();
+        settings.insert("selected_tab_index".to_string(), 2.into());
+        migrate_v0_to_v1(&mut settings);
+        assert_eq!(settings["selected_tab_index"], 4);
+
+        let mut settings = Map::new();
+        settings.insert("selected_tab_index".to_string(), 1.into());
+        migrate_v0_to_v1(&mut settings);
+        assert_eq!(settings["selected_tab_index"], 1);
+    }
+
+    #[test]
+    fn migrate_v1_to_v2_moves_the_configs_and_settings_tabs() {
+        for (v1_selected_tab_index, v2_selected_tab_index) in [(2, 2), (3, 4), (4, 5)] {
+            let mut settings = Map::new();
+            settings.insert(
+                "selected_tab_index".to_string(),
+                v1_selected_tab_index.into(),
+            );
+            migrate_v1_to_v2(&mut settings);
+            assert_eq!(settings["selected_tab_index"], v2_selected_tab_index);
+        }
+    }
+
+    #[test]
+    fn invalid_field_is_reset_to_its_default() {
+        let mut storage = MemoryStorage::default();
+        storage.set_string(
+            SETTINGS_STORAGE_KEY,
+            r#"{"version":1,"settings":{"dark_mode":false,"log_level_filter":"NotALogLevel","game_shutdown_timeout_secs":-3}}"#
+                .to_string(),
+        );
+
+        let config = load(&storage);
+
+        assert!(!config.dark_mode);
+        assert_eq!(config.log_level_filter, LogLevel::All);
+        assert_eq!(config.game_shutdown_timeout_secs, 10);
+    }
+}
@@ -0,0 +1,319 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    backend::atomic_file,
+    config::bepinex_cfg::{self, CfgFile},
+    paths,
+};
+
+const SNAPSHOTS_FOLDER_NAME: &str = "config_snapshots";
+const PROFILES_FOLDER_NAME: &str = "profiles";
+
+// Oldest snapshots are deleted past that
+const MAX_SNAPSHOT_COUNT: usize = 15;
+
+// A copy of every .cfg file of BepInEx/config, taken each time the GUI launches
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub folder_full_path: PathBuf,
+    pub unix_timestamp: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct EntryDiff {
+    pub section_name: String,
+    pub key: String,
+    pub snapshot_value: Option<String>,
+    pub current_value: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum FileDiff {
+    // Only in the current config folder
+    Added {
+        file_name: String,
+    },
+    // Only in the snapshot
+    Removed {
+        file_name: String,
+    },
+    Changed {
+        file_name: String,
+        entries: Vec<EntryDiff>,
+    },
+}
+
+impl FileDiff {
+    pub fn file_name(&self) -> &str {
+        match self {
+            Self::Added { file_name }
+            | Self::Removed { file_name }
+            | Self::Changed { file_name, .. } => file_name,
+        }
+    }
+}
+
+// Each mod manager profile has its own config folder, so its own snapshot history
+fn snapshots_folder(target_name: &str, modpack_name: Option<&str>) -> Option<PathBuf> {
+    paths::get_app_config_directory().map(|directory| {
+        let snapshots_folder_full_path = directory.join(SNAPSHOTS_FOLDER_NAME).join(target_name);
+
+        match modpack_name {
+            Some(modpack_name) => snapshots_folder_full_path
+                .join(PROFILES_FOLDER_NAME)
+                .join(modpack_name),
+            None => snapshots_folder_full_path,
+        }
+    })
+}
+
+pub fn spawn_thread_take_snapshot(
+    config_folder_full_path: PathBuf,
+    target_name: String,
+    modpack_name: Option<String>,
+) {
+    thread::spawn(move || {
+        if let Err(err) = take_snapshot(
+            &config_folder_full_path,
+            &target_name,
+            modpack_name.as_deref(),
+        ) {
+            tracing::error!("Failed taking config snapshot: {}", err);
+        }
+    });
+}
+
+pub fn take_snapshot(
+    config_folder_full_path: &Path,
+    target_name: &str,
+    modpack_name: Option<&str>,
+) -> io::Result<()> {
+    let snapshots_folder_full_path = snapshots_folder(target_name, modpack_name)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No app config directory"))?;
+
+    let cfg_files = bepinex_cfg::find_cfg_files(config_folder_full_path);
+    if cfg_files.is_empty() {
+        return Ok(());
+    }
+
+    // launching the game multiple times without touching anything shouldn't push the older snapshots out
+    if let Some(latest_snapshot) = find_snapshots(target_name, modpack_name).first() {
+        if is_same_as_snapshot(&cfg_files, latest_snapshot) {
+            return Ok(());
+        }
+    }
+
+    let unix_timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let snapshot_folder_full_path = snapshots_folder_full_path.join(unix_timestamp.to_string());
+    fs::create_dir_all(&snapshot_folder_full_path)?;
+
+    for cfg_file_full_path in &cfg_files {
+        if let Some(file_name) = cfg_file_full_path.file_name() {
+            fs::copy(
+                cfg_file_full_path,
+                snapshot_folder_full_path.join(file_name),
+            )?;
+        }
+    }
+
+    tracing::info!(
+        "Took config snapshot of {} files in {}",
+        cfg_files.len(),
+        snapshot_folder_full_path.display()
+    );
+
+    for old_snapshot in find_snapshots(target_name, modpack_name)
+        .iter()
+        .skip(MAX_SNAPSHOT_COUNT)
+    {
+        if let Err(err) = fs::remove_dir_all(&old_snapshot.folder_full_path) {
+            tracing::error!("Failed deleting old config snapshot: {}", err);
+        }
+    }
+
+    Ok(())
+}
+
+fn is_same_as_snapshot(cfg_files: &[PathBuf], snapshot: &Snapshot) -> bool {
+    let snapshot_cfg_files = bepinex_cfg::find_cfg_files(&snapshot.folder_full_path);
+
+    cfg_files.len() == snapshot_cfg_files.len()
+        && cfg_files
+            .iter()
+            .zip(snapshot_cfg_files.iter())
+            .all(|(cfg_file, snapshot_cfg_file)| {
+                cfg_file.file_name() == snapshot_cfg_file.file_name()
+                    && fs::read(cfg_file).ok() == fs::read(snapshot_cfg_file).ok()
+            })
+}
+
+// Most recent first
+pub fn find_snapshots(target_name: &str, modpack_name: Option<&str>) -> Vec<Snapshot> {
+    let Some(snapshots_folder_full_path) = snapshots_folder(target_name, modpack_name) else {
+        return Vec::new();
+    };
+
+    let Ok(entries) = fs::read_dir(snapshots_folder_full_path) else {
+        return Vec::new();
+    };
+
+    let mut snapshots: Vec<Snapshot> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .filter_map(|folder_full_path| {
+            let unix_timestamp = folder_full_path.file_name()?.to_str()?.parse().ok()?;
+
+            Some(Snapshot {
+                folder_full_path,
+                unix_timestamp,
+            })
+        })
+        .collect();
+
+    snapshots.sort_by_key(|snapshot| std::cmp::Reverse(snapshot.unix_timestamp));
+
+    snapshots
+}
+
+pub fn diff(snapshot: &Snapshot, config_folder_full_path: &Path) -> Vec<FileDiff> {
+    let snapshot_cfg_files = bepinex_cfg::find_cfg_files(&snapshot.folder_full_path);
+    let current_cfg_files = bepinex_cfg::find_cfg_files(config_folder_full_path);
+
+    let file_name = |path: &Path| {
+        path.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default()
+    };
+
+    let mut file_diffs = Vec::new();
+
+    for snapshot_cfg_file in &snapshot_cfg_files {
+        let snapshot_file_name = file_name(snapshot_cfg_file);
+
+        let Some(current_cfg_file) = current_cfg_files
+            .iter()
+            .find(|current_cfg_file| file_name(current_cfg_file) == snapshot_file_name)
+        else {
+            file_diffs.push(FileDiff::Removed {
+                file_name: snapshot_file_name,
+            });
+            continue;
+        };
+
+        let (Ok(snapshot_cfg), Ok(current_cfg)) = (
+            CfgFile::read(snapshot_cfg_file),
+            CfgFile::read(current_cfg_file),
+        ) else {
+            continue;
+        };
+
+        let entries = diff_entries(&snapshot_cfg, &current_cfg);
+        if !entries.is_empty() {
+            file_diffs.push(FileDiff::Changed {
+                file_name: snapshot_file_name,
+                entries,
+            });
+        }
+    }
+
+    for current_cfg_file in &current_cfg_files {
+        let current_file_name = file_name(current_cfg_file);
+
+        if !snapshot_cfg_files
+            .iter()
+            .any(|snapshot_cfg_file| file_name(snapshot_cfg_file) == current_file_name)
+        {
+            file_diffs.push(FileDiff::Added {
+                file_name: current_file_name,
+            });
+        }
+    }
+
+    file_diffs
+}
+
+fn diff_entries(snapshot_cfg: &CfgFile, current_cfg: &CfgFile) -> Vec<EntryDiff> {
+    let mut entry_diffs = Vec::new();
+
+    for section in snapshot_cfg.sections() {
+        for entry in section.entries() {
+            let current_value = current_cfg
+                .entry(section.name(), entry.key())
+                .map(|current_entry| current_entry.raw_value().to_string());
+
+            if current_value.as_deref() != Some(entry.raw_value()) {
+                entry_diffs.push(EntryDiff {
+                    section_name: section.name().to_string(),
+                    key: entry.key().to_string(),
+                    snapshot_value: Some(entry.raw_value().to_string()),
+                    current_value,
+                });
+            }
+        }
+    }
+
+    for section in current_cfg.sections() {
+        for entry in section.entries() {
+            if snapshot_cfg.entry(section.name(), entry.key()).is_none() {
+                entry_diffs.push(EntryDiff {
+                    section_name: section.name().to_string(),
+                    key: entry.key().to_string(),
+                    snapshot_value: None,
+                    current_value: Some(entry.raw_value().to_string()),
+                });
+            }
+        }
+    }
+
+    entry_diffs
+}
+
+pub fn restore_file(
+    snapshot: &Snapshot,
+    config_folder_full_path: &Path,
+    file_name: &str,
+) -> io::Result<()> {
+    let snapshot_file_contents = fs::read(snapshot.folder_full_path.join(file_name))?;
+
+    atomic_file::write_with_backup(
+        &config_folder_full_path.join(file_name),
+        &snapshot_file_contents,
+    )
+}
+
+// Only entries that still exist in the current file can be restored,
+// BepInEx is the one writing the descriptions and metadata of new entries
+pub fn restore_entry(
+    config_folder_full_path: &Path,
+    file_name: &str,
+    entry_diff: &EntryDiff,
+) -> io::Result<()> {
+    let Some(snapshot_value) = &entry_diff.snapshot_value else {
+        return Ok(());
+    };
+
+    let cfg_file_full_path = config_folder_full_path.join(file_name);
+    let mut cfg_file = CfgFile::read(&cfg_file_full_path)?;
+
+    if !cfg_file.set_raw(&entry_diff.section_name, &entry_diff.key, snapshot_value) {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!(
+                "[{}] {} no longer exists in {}",
+                entry_diff.section_name, entry_diff.key, file_name
+            ),
+        ));
+    }
+
+    cfg_file.save_merging_changes_on_disk(&cfg_file_full_path)
+}
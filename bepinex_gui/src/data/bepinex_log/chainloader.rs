@@ -0,0 +1,325 @@
+use std::time::SystemTime;
+
+use crate::data::bepinex_mod::BepInExMod;
+
+// Log entries coming from the socket are formatted by BepInEx as
+// "[Level  :    Source] Message", chainloader messages are the ones logged by the "BepInEx" source
+const CHAINLOADER_LOG_SOURCE: &str = "BepInEx";
+
+const STARTUP_COMPLETE_MESSAGE: &str = "Chainloader startup complete";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingDependency {
+    pub guid: String,
+    // "v1.2.3 or newer" on BepInEx 5, a version range like "^1.2.3" on BepInEx 6
+    pub version_requirement: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainloaderEvent {
+    Loading(BepInExMod),
+    Skipped {
+        mod_: BepInExMod,
+        reason: String,
+    },
+    Incompatible {
+        mod_: BepInExMod,
+        incompatible_with: Vec<String>,
+    },
+    MissingDependencies {
+        mod_: BepInExMod,
+        dependencies: Vec<MissingDependency>,
+    },
+    StartupComplete,
+}
+
+// Along with the time of the log it was parsed from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimedChainloaderEvent {
+    pub time: SystemTime,
+    pub event: ChainloaderEvent,
+}
+
+pub fn parse(log_line: &str) -> Option<ChainloaderEvent> {
+    let message = strip_chainloader_log_header(log_line)?.trim_end();
+
+    if message == STARTUP_COMPLETE_MESSAGE {
+        return Some(ChainloaderEvent::StartupComplete);
+    }
+
+    if let Some(plugin_info) = message.strip_prefix("Loading [") {
+        let plugin_info = plugin_info.strip_suffix(']')?;
+
+        return Some(ChainloaderEvent::Loading(parse_plugin_info(plugin_info)));
+    }
+
+    if let Some(rest) = message.strip_prefix("Could not load [") {
+        let (plugin_info, reason) = split_plugin_info_and_reason(rest, "] because ")?;
+        let mod_ = parse_plugin_info(plugin_info);
+
+        if let Some(incompatible_with) = reason.strip_prefix("it is incompatible with:") {
+            return Some(ChainloaderEvent::Incompatible {
+                mod_,
+                incompatible_with: split_list(incompatible_with)
+                    .map(ToString::to_string)
+                    .collect(),
+            });
+        }
+
+        if let Some(dependencies) = reason.strip_prefix("it has missing dependencies:") {
+            return Some(ChainloaderEvent::MissingDependencies {
+                mod_,
+                dependencies: split_list(dependencies)
+                    .map(parse_missing_dependency)
+                    .collect(),
+            });
+        }
+
+        return Some(ChainloaderEvent::Skipped {
+            mod_,
+            reason: reason.to_string(),
+        });
+    }
+
+    if let Some(rest) = message.strip_prefix("Skipping [") {
+        let (plugin_info, reason) = split_plugin_info_and_reason(rest, "] because ")?;
+
+        return Some(ChainloaderEvent::Skipped {
+            mod_: parse_plugin_info(plugin_info),
+            reason: reason.to_string(),
+        });
+    }
+
+    if let Some(rest) = message.strip_prefix("Error loading [") {
+        let (plugin_info, reason) = split_plugin_info_and_reason(rest, "] :")?;
+
+        return Some(ChainloaderEvent::Skipped {
+            mod_: parse_plugin_info(plugin_info),
+            reason: reason.trim().to_string(),
+        });
+    }
+
+    None
+}
+
+// Returns the message part of the log line if it was logged by the chainloader.
+// Lines without any header are assumed to already be the raw message.
+fn strip_chainloader_log_header(log_line: &str) -> Option<&str> {
+    let Some(header_and_message) = log_line.strip_prefix('[') else {
+        return Some(log_line);
+    };
+
+    let header_end_index = header_and_message.find(']')?;
+    let (header, message) = header_and_message.split_at(header_end_index);
+
+    let (_level, source) = header.split_once(':')?;
+    if source.trim() != CHAINLOADER_LOG_SOURCE {
+        return None;
+    }
+
+    // skip the ']' and the space following it
+    Some(message[1..].trim_start())
+}
+
+fn split_plugin_info_and_reason<'a>(text: &'a str, separator: &str) -> Option<(&'a str, &'a str)> {
+    let (plugin_info, reason) = text.split_once(separator)?;
+
+    Some((plugin_info, reason.trim()))
+}
+
+// Plugin info is formatted by BepInEx as "{Name} {Version}"
+fn parse_plugin_info(plugin_info: &str) -> BepInExMod {
+    let plugin_info = plugin_info.trim();
+
+    match plugin_info.rsplit_once(' ') {
+        Some((name, version)) if looks_like_a_version(version) => {
+            BepInExMod::new(name.trim_end(), version)
+        }
+        _ => BepInExMod::new(plugin_info, ""),
+    }
+}
+
+fn looks_like_a_version(text: &str) -> bool {
+    let text = text.strip_prefix('v').unwrap_or(text);
+
+    text.starts_with(|c: char| c.is_ascii_digit())
+}
+
+fn split_list(text: &str) -> impl Iterator<Item = &str> {
+    text.split(", ")
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+}
+
+// "GUID", "GUID (v1.0.0 or newer)" or "GUID (^1.0.0)"
+fn parse_missing_dependency(text: &str) -> MissingDependency {
+    match text.split_once(" (") {
+        Some((guid, version_requirement)) => MissingDependency {
+            guid: guid.trim().to_string(),
+            version_requirement: Some(
+                version_requirement
+                    .strip_suffix(')')
+                    .unwrap_or(version_requirement)
+                    .trim()
+                    .to_string(),
+            ),
+        },
+        None => MissingDependency {
+            guid: text.trim().to_string(),
+            version_requirement: None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn missing_dependency(guid: &str, version_requirement: Option<&str>) -> MissingDependency {
+        MissingDependency {
+            guid: guid.to_string(),
+            version_requirement: version_requirement.map(ToString::to_string),
+        }
+    }
+
+    #[test]
+    fn parses_loading() {
+        // BepInEx 5
+        assert_eq!(
+            parse("[Info   :   BepInEx] Loading [R2API 4.4.1]"),
+            Some(ChainloaderEvent::Loading(BepInExMod::new("R2API", "4.4.1")))
+        );
+        // BepInEx 6, plugin names can contain spaces
+        assert_eq!(
+            parse("[Info   :   BepInEx] Loading [Unity Explorer 4.9.0]"),
+            Some(ChainloaderEvent::Loading(BepInExMod::new(
+                "Unity Explorer",
+                "4.9.0"
+            )))
+        );
+        assert_eq!(
+            parse("[Info   :   BepInEx] Loading [NoVersion]"),
+            Some(ChainloaderEvent::Loading(BepInExMod::new("NoVersion", "")))
+        );
+        // raw message without header
+        assert_eq!(
+            parse("Loading [HookGenPatcher 1.2.3]\r"),
+            Some(ChainloaderEvent::Loading(BepInExMod::new(
+                "HookGenPatcher",
+                "1.2.3"
+            )))
+        );
+    }
+
+    #[test]
+    fn ignores_loaded_lines() {
+        // BepInEx 5 and 6 log patchers being loaded, not plugins
+        assert_eq!(
+            parse("[Info   :   Preloader] Loaded 1 patcher method from [BepInEx.MultiFolderLoader 1.3.1.0]"),
+            None
+        );
+        assert_eq!(
+            parse(
+                "[Info   :   BepInEx] Loaded 2 patcher methods from [BepInEx.GUI.Loader 3.0.2.0]"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn parses_missing_dependencies() {
+        // BepInEx 5
+        assert_eq!(
+            parse("[Error  :   BepInEx] Could not load [MoreItems 1.0.0] because it has missing dependencies: com.bepis.r2api (v4.0.0 or newer), com.rune580.riskofoptions"),
+            Some(ChainloaderEvent::MissingDependencies {
+                mod_: BepInExMod::new("MoreItems", "1.0.0"),
+                dependencies: vec![
+                    missing_dependency("com.bepis.r2api", Some("v4.0.0 or newer")),
+                    missing_dependency("com.rune580.riskofoptions", None),
+                ],
+            })
+        );
+        // BepInEx 6
+        assert_eq!(
+            parse("[Error  :   BepInEx] Could not load [Mod 2.1.0] because it has missing dependencies: com.example.lib (^1.2.0)"),
+            Some(ChainloaderEvent::MissingDependencies {
+                mod_: BepInExMod::new("Mod", "2.1.0"),
+                dependencies: vec![missing_dependency("com.example.lib", Some("^1.2.0"))],
+            })
+        );
+    }
+
+    #[test]
+    fn parses_incompatibilities() {
+        assert_eq!(
+            parse("[Error  :   BepInEx] Could not load [ItemStats 2.0.0] because it is incompatible with: com.old.itemstats, com.other.stats"),
+            Some(ChainloaderEvent::Incompatible {
+                mod_: BepInExMod::new("ItemStats", "2.0.0"),
+                incompatible_with: vec![
+                    "com.old.itemstats".to_string(),
+                    "com.other.stats".to_string()
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn parses_failed_to_load() {
+        assert_eq!(
+            parse("[Error  :   BepInEx] Error loading [BrokenMod 0.1.0] : Could not load type 'RoR2.Old' from assembly 'RoR2'."),
+            Some(ChainloaderEvent::Skipped {
+                mod_: BepInExMod::new("BrokenMod", "0.1.0"),
+                reason: "Could not load type 'RoR2.Old' from assembly 'RoR2'.".to_string(),
+            })
+        );
+        assert_eq!(
+            parse("[Warning:   BepInEx] Skipping [Mod 1.0.0] because a newer version exists (Mod 1.1.0)"),
+            Some(ChainloaderEvent::Skipped {
+                mod_: BepInExMod::new("Mod", "1.0.0"),
+                reason: "a newer version exists (Mod 1.1.0)".to_string(),
+            })
+        );
+        assert_eq!(
+            parse("[Error  :   BepInEx] Could not load [OldMod 1.0.0] because it targets a newer BepInEx version"),
+            Some(ChainloaderEvent::Skipped {
+                mod_: BepInExMod::new("OldMod", "1.0.0"),
+                reason: "it targets a newer BepInEx version".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn parses_startup_complete() {
+        assert_eq!(
+            parse("[Message:   BepInEx] Chainloader startup complete"),
+            Some(ChainloaderEvent::StartupComplete)
+        );
+    }
+
+    #[test]
+    fn ignores_non_chainloader_lines() {
+        for log_line in [
+            "",
+            "[",
+            "]",
+            "[]",
+            "[Info",
+            "[Info   :   BepInEx",
+            "[Info   :   BepInEx]",
+            "[Info   :   BepInEx] Loading [",
+            "[Info   :   BepInEx] Could not load [Mod 1.0.0",
+            "[Info   :   BepInEx] Error loading [Mod 1.0.0]",
+            "[Info   :   BepInEx] Skipping [Mod 1.0.0]",
+            "[Message:   BepInEx] BepInEx 5.4.21.0 - RiskOfRain2 (10/06/2022 08:36:18)",
+            "[Info   :   BepInEx] 42 plugins to load",
+            "[Message:   BepInEx] Chainloader initialized",
+            "[Info   :   BepInEx] Chainloader started",
+            // other plugins can log the same words
+            "[Info   :   MyMod] Loading [Fake 1.0.0]",
+            "[Info   :Unity Log] Could not load [asset] because it is missing",
+            "]][[ Loading [ ]] :",
+        ] {
+            assert_eq!(parse(log_line), None, "{log_line}");
+        }
+    }
+}
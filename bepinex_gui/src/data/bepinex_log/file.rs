@@ -1,12 +1,17 @@
 use std::{
     fs::{self, File},
-    io::{BufReader, BufWriter},
+    io::{self, BufReader, BufWriter},
     path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
 };
 use zip::write::FileOptions;
 
 use crate::{backend::file_explorer_utils, paths};
 
+use super::BepInExLogEntry;
+
+const EXPORTED_LOGS_FOLDER_NAME: &str = "exported_logs";
+
 pub fn open_file_explorer_to_file_and_zip_it_if_needed(
     file_full_path: &PathBuf,
     zip_file_name: &str,
@@ -63,3 +68,24 @@ pub fn full_path() -> Option<PathBuf> {
 
     None
 }
+
+// The logs received by the console, each export goes to a new file
+pub fn export(target_name: &str, logs: &[BepInExLogEntry]) -> io::Result<PathBuf> {
+    let exported_logs_folder_full_path = paths::get_app_config_directory()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No app config directory"))?
+        .join(EXPORTED_LOGS_FOLDER_NAME);
+    fs::create_dir_all(&exported_logs_folder_full_path)?;
+
+    let unix_timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let exported_logs_file_full_path =
+        exported_logs_folder_full_path.join(format!("{target_name}_{unix_timestamp}.log"));
+
+    let text: Vec<&str> = logs.iter().map(BepInExLogEntry::data).collect();
+    fs::write(&exported_logs_file_full_path, text.join("\n"))?;
+
+    Ok(exported_logs_file_full_path)
+}
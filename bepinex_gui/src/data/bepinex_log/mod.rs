@@ -1,8 +1,11 @@
+use std::time::SystemTime;
+
 use eframe::emath::Numeric;
 
 use serde::{Deserialize, Serialize};
 use strum::{Display, EnumCount, EnumIter};
 
+pub mod chainloader;
 pub mod file;
 pub mod receiver;
 
@@ -62,15 +65,17 @@ impl Numeric for LogLevel {
 #[derive(Clone)]
 pub struct BepInExLogEntry {
     level: LogLevel,
+    time: SystemTime,
     data: String,
     data_lowercase: String,
     pub is_selected: bool,
 }
 
 impl BepInExLogEntry {
-    pub fn new(level: LogLevel, data: &str) -> Self {
+    pub fn new(level: LogLevel, time: SystemTime, data: &str) -> Self {
         Self {
             level,
+            time,
             data: data.to_string(),
             data_lowercase: data.to_lowercase(),
             is_selected: false,
@@ -81,6 +86,10 @@ impl BepInExLogEntry {
         self.level
     }
 
+    pub const fn time(&self) -> SystemTime {
+        self.time
+    }
+
     pub fn data(&self) -> &str {
         self.data.as_ref()
     }
@@ -88,4 +97,12 @@ impl BepInExLogEntry {
     pub fn data_lowercase(&self) -> &str {
         self.data_lowercase.as_ref()
     }
+
+    // BepInEx formats the logs as "[Level  :     Source] Data"
+    pub fn source(&self) -> Option<&str> {
+        let (header, _) = self.data.strip_prefix('[')?.split_once(']')?;
+        let (_, source) = header.split_once(':')?;
+
+        Some(source.trim())
+    }
 }
@@ -5,88 +5,61 @@ use std::net::SocketAddr;
 use std::net::TcpStream;
 
 use std::io;
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::sync::Arc;
 
 use std::thread;
+use std::time::SystemTime;
 
 use crossbeam_channel::Sender;
 
-use crate::backend::network::packet_protocol;
-use crate::data::bepinex_mod::BepInExMod;
+use crate::backend::network::packet_protocol::{self, Handshake};
 
+use super::chainloader::{self, TimedChainloaderEvent};
 use super::BepInExLogEntry;
 use super::LogLevel;
 
 #[derive(Clone)]
 pub struct LogReceiver {
-    log_socket_port_receiver: u16,
+    // changes when the GUI reattaches to a restarted game
+    log_socket_port_receiver: Arc<AtomicU16>,
     log_senders: Vec<Sender<BepInExLogEntry>>,
-    mod_senders: Vec<Sender<BepInExMod>>,
+    mod_senders: Vec<Sender<TimedChainloaderEvent>>,
 }
 
 impl LogReceiver {
     pub fn new(
         log_socket_port_receiver: u16,
         log_senders: Vec<Sender<BepInExLogEntry>>,
-        mod_senders: Vec<Sender<BepInExMod>>,
+        mod_senders: Vec<Sender<TimedChainloaderEvent>>,
     ) -> Self {
         Self {
-            log_socket_port_receiver,
+            log_socket_port_receiver: Arc::new(AtomicU16::new(log_socket_port_receiver)),
             log_senders,
             mod_senders,
         }
     }
 
+    // Picked up the next time the connection is lost
+    pub fn set_log_socket_port_receiver(&self, log_socket_port_receiver: u16) {
+        self.log_socket_port_receiver
+            .store(log_socket_port_receiver, Ordering::Relaxed);
+    }
+
     pub fn start_thread_loop(&self) {
-        let server_address = SocketAddr::new(
-            IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
-            self.log_socket_port_receiver,
-        );
         let inst = self.clone();
         thread::spawn(move || -> io::Result<()> {
             loop {
+                let server_address = SocketAddr::new(
+                    IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
+                    inst.log_socket_port_receiver.load(Ordering::Relaxed),
+                );
                 match TcpStream::connect(server_address) {
-                    Ok(mut tcp_stream) => loop {
-                        match packet_protocol::read_packet_length(&mut tcp_stream) {
-                            Ok(packet_length) => {
-                                match packet_protocol::read_packet_log_level(&mut tcp_stream) {
-                                    Ok(log_level) => {
-                                        match packet_protocol::read_packet(
-                                            &mut tcp_stream,
-                                            packet_length,
-                                        ) {
-                                            Ok(packet_bytes) => {
-                                                inst.make_log_entry_from_packet_data(
-                                                    log_level,
-                                                    &packet_bytes,
-                                                );
-                                            }
-                                            Err(err) => {
-                                                tracing::error!(
-                                                    "Error reading packet: {}\nDisconnecting socket",
-                                                    err
-                                                );
-                                                break;
-                                            }
-                                        }
-                                    }
-                                    Err(err) => {
-                                        tracing::error!(
-                                            "Error reading packet log level: {}\nDisconnecting socket",
-                                            err
-                                        );
-                                        break;
-                                    }
-                                }
-                            }
-                            Err(err) => {
-                                tracing::error!(
-                                    "Error reading packet length: {}\nDisconnecting socket",
-                                    err
-                                );
-                                break;
-                            }
+                    Ok(mut tcp_stream) => {
+                        if let Err(err) = inst.receive_logs_until_disconnected(&mut tcp_stream) {
+                            tracing::error!("Error reading packet: {}\nDisconnecting socket", err);
                         }
-                    },
+                    }
                     Err(err) => tracing::error!("Failed connecting: {}", err),
                 }
 
@@ -98,25 +71,56 @@ impl LogReceiver {
         });
     }
 
-    fn make_log_entry_from_packet_data(&self, log_level: LogLevel, string_packet_bytes: &[u8]) {
+    fn receive_logs_until_disconnected(&self, tcp_stream: &mut TcpStream) -> io::Result<()> {
+        let (protocol_version, mut first_packet_length) =
+            match packet_protocol::handshake(tcp_stream)? {
+                Handshake::Accepted { protocol_version } => (protocol_version, None),
+                Handshake::Ignored {
+                    first_packet_length,
+                } => (
+                    packet_protocol::LEGACY_PROTOCOL_VERSION,
+                    Some(first_packet_length),
+                ),
+            };
+
+        loop {
+            let packet_length = match first_packet_length.take() {
+                Some(packet_length) => packet_length,
+                None => packet_protocol::read_packet_length(tcp_stream)?,
+            };
+
+            let log_level = packet_protocol::read_packet_log_level(tcp_stream)?;
+
+            // older loaders don't send it, logs queued before the connection all get the same time
+            let log_time = if protocol_version >= packet_protocol::TIMESTAMP_PROTOCOL_VERSION {
+                packet_protocol::read_packet_timestamp(tcp_stream)?
+            } else {
+                SystemTime::now()
+            };
+
+            let packet_bytes = packet_protocol::read_packet(tcp_stream, packet_length)?;
+
+            self.make_log_entry_from_packet_data(log_level, log_time, &packet_bytes);
+        }
+    }
+
+    fn make_log_entry_from_packet_data(
+        &self,
+        log_level: LogLevel,
+        log_time: SystemTime,
+        string_packet_bytes: &[u8],
+    ) {
         let log_string = packet_protocol::packet_bytes_to_utf8_string(string_packet_bytes);
 
-        let log = BepInExLogEntry::new(log_level, &log_string);
-
-        if log.data().contains("Loading [") {
-            let split: Vec<&str> = log.data().split('[').collect();
-            let mod_info_text = split[2];
-            let mod_version_start_index_ = mod_info_text.rfind(' ');
-            if let Some(mod_version_start_index) = mod_version_start_index_ {
-                let mod_name = &mod_info_text[0..mod_version_start_index];
-                let mod_version =
-                    &mod_info_text[mod_version_start_index + 1..mod_info_text.len() - 1];
-
-                for mod_sender in &self.mod_senders {
-                    mod_sender
-                        .send(BepInExMod::new(mod_name, mod_version))
-                        .unwrap();
-                }
+        let log = BepInExLogEntry::new(log_level, log_time, &log_string);
+
+        if let Some(chainloader_event) = chainloader::parse(log.data()) {
+            let timed_chainloader_event = TimedChainloaderEvent {
+                time: log.time(),
+                event: chainloader_event,
+            };
+            for mod_sender in &self.mod_senders {
+                mod_sender.send(timed_chainloader_event.clone()).unwrap();
             }
         }
 
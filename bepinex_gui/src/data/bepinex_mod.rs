@@ -1,3 +1,6 @@
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BepInExMod {
     name: String,
     version: String,
@@ -14,6 +17,10 @@ impl BepInExMod {
     pub fn name(&self) -> &str {
         self.name.as_ref()
     }
+
+    pub fn version(&self) -> &str {
+        self.version.as_ref()
+    }
 }
 
 impl ToString for BepInExMod {
@@ -21,3 +28,32 @@ impl ToString for BepInExMod {
         format!("{} {}", self.name, self.version)
     }
 }
+
+// Compares dot separated version numbers ("1.10.0" > "1.9.2"),
+// non numeric parts (like a "v" prefix) are ignored
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let parse = |version: &str| -> Vec<u64> {
+        version
+            .trim_start_matches('v')
+            .split('.')
+            .map(|part| {
+                part.chars()
+                    .take_while(char::is_ascii_digit)
+                    .collect::<String>()
+                    .parse()
+                    .unwrap_or(0)
+            })
+            .collect()
+    };
+
+    let (a, b) = (parse(a), parse(b));
+
+    for i in 0..a.len().max(b.len()) {
+        let ordering = a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0));
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    Ordering::Equal
+}
@@ -0,0 +1,493 @@
+use std::{
+    collections::VecDeque,
+    fs::{self, File},
+    io::{self, BufReader, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crossbeam_channel::{Receiver, Sender};
+use zip::write::FileOptions;
+
+use crate::{
+    backend::process::{ExitStatus, ProcessExit},
+    data::bepinex_log::BepInExLogEntry,
+    paths,
+};
+
+const LAST_LOG_COUNT: usize = 100;
+
+const CRASH_REPORTS_FOLDER_NAME: &str = "crash_reports";
+
+// What Unity writes in the Player.log when the game goes down
+const PLAYER_LOG_CRASH_MARKERS: [&str; 4] = [
+    "Crash!!!",
+    "Native Crash Reporting",
+    "Received signal SIGSEGV",
+    "Caught fatal signal",
+];
+
+// Keeps the last logs around until the game exits, so that they can go in the report
+pub struct CrashWatch {
+    process_exit_receiver: Receiver<ProcessExit>,
+    log_receiver: Receiver<BepInExLogEntry>,
+    last_logs: VecDeque<String>,
+    process_start_time: Option<SystemTime>,
+    process_exit: Option<ProcessExit>,
+}
+
+impl CrashWatch {
+    pub fn new(log_receiver: Receiver<BepInExLogEntry>) -> Self {
+        Self {
+            process_exit_receiver: crossbeam_channel::never(),
+            log_receiver,
+            last_logs: VecDeque::new(),
+            process_start_time: None,
+            process_exit: None,
+        }
+    }
+
+    pub fn watch(
+        &mut self,
+        process_exit_receiver: Receiver<ProcessExit>,
+        process_start_time: Option<SystemTime>,
+    ) {
+        self.process_exit_receiver = process_exit_receiver;
+        self.process_start_time = process_start_time;
+        self.process_exit = None;
+    }
+
+    // For when the game is about to exit on purpose
+    pub fn stop_watching(&mut self) {
+        self.process_exit_receiver = crossbeam_channel::never();
+    }
+
+    pub const fn process_exit(&self) -> Option<&ProcessExit> {
+        self.process_exit.as_ref()
+    }
+
+    // Returns the exit of the game on the frame it is received
+    pub fn update(&mut self) -> Option<ProcessExit> {
+        while let Ok(log) = self.log_receiver.try_recv() {
+            self.last_logs.push_back(log.data().to_string());
+            if self.last_logs.len() > LAST_LOG_COUNT {
+                self.last_logs.pop_front();
+            }
+        }
+
+        if self.process_exit.is_some() {
+            return None;
+        }
+
+        self.process_exit = self.process_exit_receiver.try_recv().ok();
+        self.process_exit
+    }
+
+    // Looking for the Player.log and the crash dumps walks the game folder, so it gets its own thread
+    pub fn spawn_thread_make_report(
+        &self,
+        process_exit: ProcessExit,
+        target_name: String,
+        game_folder_full_path: PathBuf,
+        report_sender: Sender<CrashReport>,
+    ) {
+        let last_logs = self.last_logs.iter().cloned().collect();
+        // anything older belongs to a previous run of the game
+        let since = self.process_start_time.unwrap_or(UNIX_EPOCH);
+
+        thread::spawn(move || {
+            let crash_report = make_report(
+                process_exit,
+                target_name,
+                &game_folder_full_path,
+                last_logs,
+                since,
+            );
+
+            _ = report_sender.send(crash_report);
+        });
+    }
+}
+
+fn make_report(
+    process_exit: ProcessExit,
+    target_name: String,
+    game_folder_full_path: &Path,
+    last_logs: Vec<String>,
+    since: SystemTime,
+) -> CrashReport {
+    let player_log_full_path = find_player_log(game_folder_full_path, &target_name, since);
+    let player_log_has_crash_marker = player_log_full_path
+        .as_ref()
+        .and_then(|player_log_full_path| fs::read(player_log_full_path).ok())
+        .map(|player_log| {
+            let player_log = String::from_utf8_lossy(&player_log);
+            PLAYER_LOG_CRASH_MARKERS
+                .iter()
+                .any(|marker| player_log.contains(marker))
+        })
+        .unwrap_or_default();
+
+    let mut crash_dump_full_paths = Vec::new();
+    find_crash_dumps(game_folder_full_path, 2, since, &mut crash_dump_full_paths);
+    for unity_temp_crash_folder_full_path in unity_temp_crash_folders(&target_name) {
+        find_crash_dumps(
+            &unity_temp_crash_folder_full_path,
+            2,
+            since,
+            &mut crash_dump_full_paths,
+        );
+    }
+
+    CrashReport {
+        target_name,
+        process_exit,
+        last_logs,
+        player_log_full_path,
+        player_log_has_crash_marker,
+        crash_dump_full_paths,
+    }
+}
+
+#[derive(Clone)]
+pub struct CrashReport {
+    pub target_name: String,
+    pub process_exit: ProcessExit,
+    pub last_logs: Vec<String>,
+    pub player_log_full_path: Option<PathBuf>,
+    player_log_has_crash_marker: bool,
+    pub crash_dump_full_paths: Vec<PathBuf>,
+}
+
+impl CrashReport {
+    pub fn is_crash(&self) -> bool {
+        match self.process_exit.status.is_abnormal() {
+            Some(is_abnormal) => is_abnormal,
+            None => self.player_log_has_crash_marker || !self.crash_dump_full_paths.is_empty(),
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "{} exited with {}",
+            self.target_name, self.process_exit.status
+        );
+
+        if self.process_exit.status == ExitStatus::Unknown {
+            if self.player_log_has_crash_marker {
+                summary += ", the Player.log reports a crash";
+            } else if !self.crash_dump_full_paths.is_empty() {
+                summary += ", crash dumps were written";
+            }
+        }
+
+        summary
+    }
+
+    fn summary_file_text(&self) -> String {
+        let unix_timestamp = self
+            .process_exit
+            .time
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        let mut text = format!("{}\nUnix time: {}\n", self.summary(), unix_timestamp);
+
+        if let Some(player_log_full_path) = &self.player_log_full_path {
+            text += &format!("Player.log: {}\n", player_log_full_path.display());
+        }
+        for crash_dump_full_path in &self.crash_dump_full_paths {
+            text += &format!("Crash dump: {}\n", crash_dump_full_path.display());
+        }
+
+        text
+    }
+}
+
+// Zips everything useful for the mod developers, on its own thread since crash dumps can be big
+pub fn spawn_thread_build_bundle(
+    crash_report: CrashReport,
+    bepinex_log_output_file_full_path: PathBuf,
+    bundle_sender: Sender<io::Result<PathBuf>>,
+) {
+    thread::spawn(move || {
+        let result = build_bundle(&crash_report, &bepinex_log_output_file_full_path);
+        if let Err(err) = &result {
+            tracing::error!("Failed building the crash report bundle: {}", err);
+        }
+
+        _ = bundle_sender.send(result);
+    });
+}
+
+fn build_bundle(
+    crash_report: &CrashReport,
+    bepinex_log_output_file_full_path: &Path,
+) -> io::Result<PathBuf> {
+    let crash_reports_folder_full_path = paths::get_app_config_directory()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No app config directory"))?
+        .join(CRASH_REPORTS_FOLDER_NAME);
+    fs::create_dir_all(&crash_reports_folder_full_path)?;
+
+    let unix_timestamp = crash_report
+        .process_exit
+        .time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let bundle_full_path = crash_reports_folder_full_path.join(format!(
+        "{}_crash_{}.zip",
+        crash_report.target_name, unix_timestamp
+    ));
+
+    let mut zip = zip::ZipWriter::new(File::create(&bundle_full_path)?);
+    let options = FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    zip.start_file("crash_summary.txt", options)?;
+    zip.write_all(crash_report.summary_file_text().as_bytes())?;
+
+    zip.start_file("last_logs.log", options)?;
+    zip.write_all(crash_report.last_logs.join("\n").as_bytes())?;
+
+    let mut files_to_bundle = vec![(
+        "LogOutput.log".to_string(),
+        bepinex_log_output_file_full_path.to_path_buf(),
+    )];
+    if let Some(player_log_full_path) = &crash_report.player_log_full_path {
+        files_to_bundle.push(("Player.log".to_string(), player_log_full_path.clone()));
+    }
+    for (i, crash_dump_full_path) in crash_report.crash_dump_full_paths.iter().enumerate() {
+        let file_name = crash_dump_full_path
+            .file_name()
+            .map(|file_name| file_name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        // Unity names all of them crash.dmp
+        files_to_bundle.push((
+            format!("crash_dumps/{i}_{file_name}"),
+            crash_dump_full_path.clone(),
+        ));
+    }
+
+    for (name_in_bundle, file_full_path) in files_to_bundle {
+        let Ok(file) = File::open(&file_full_path) else {
+            tracing::error!("Missing file for the crash report: {:?}", file_full_path);
+            continue;
+        };
+
+        zip.start_file(name_in_bundle, options)?;
+        io::copy(&mut BufReader::new(file), &mut zip)?;
+    }
+
+    zip.finish()?;
+
+    Ok(bundle_full_path)
+}
+
+fn is_modified_since(file_full_path: &Path, since: SystemTime) -> bool {
+    fs::metadata(file_full_path)
+        .and_then(|metadata| metadata.modified())
+        .map(|modified| modified >= since)
+        .unwrap_or_default()
+}
+
+// Unity writes it in the game folder for old versions,
+// in the user folder under <company name>/<product name> for the others
+fn find_player_log(
+    game_folder_full_path: &Path,
+    target_name: &str,
+    since: SystemTime,
+) -> Option<PathBuf> {
+    let mut candidates = vec![game_folder_full_path.join("Player.log")];
+
+    if let Ok(entries) = fs::read_dir(game_folder_full_path) {
+        for entry in entries.flatten() {
+            if entry.file_name().to_string_lossy().ends_with("_Data") {
+                candidates.push(entry.path().join("output_log.txt"));
+            }
+        }
+    }
+
+    if let Some(unity_user_folder_full_path) = unity_user_folder() {
+        if let Ok(company_folders) = fs::read_dir(unity_user_folder_full_path) {
+            for company_folder in company_folders.flatten() {
+                candidates.push(company_folder.path().join(target_name).join("Player.log"));
+            }
+        }
+    }
+
+    candidates
+        .into_iter()
+        .filter(|candidate| is_modified_since(candidate, since))
+        .max_by_key(|candidate| {
+            fs::metadata(candidate)
+                .and_then(|metadata| metadata.modified())
+                .unwrap_or(UNIX_EPOCH)
+        })
+}
+
+fn unity_user_folder() -> Option<PathBuf> {
+    let base_dirs = directories_next::BaseDirs::new()?;
+
+    if cfg!(windows) {
+        Some(base_dirs.home_dir().join("AppData").join("LocalLow"))
+    } else {
+        Some(base_dirs.config_dir().join("unity3d"))
+    }
+}
+
+// %TEMP%/<company name>/<product name>/Crashes
+fn unity_temp_crash_folders(target_name: &str) -> Vec<PathBuf> {
+    let Ok(company_folders) = fs::read_dir(std::env::temp_dir()) else {
+        return Vec::new();
+    };
+
+    company_folders
+        .flatten()
+        .map(|company_folder| company_folder.path().join(target_name).join("Crashes"))
+        .filter(|crash_folder| crash_folder.is_dir())
+        .collect()
+}
+
+fn find_crash_dumps(
+    folder_full_path: &Path,
+    max_depth: usize,
+    since: SystemTime,
+    out_crash_dump_full_paths: &mut Vec<PathBuf>,
+) {
+    let Ok(entries) = fs::read_dir(folder_full_path) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let entry_full_path = entry.path();
+
+        if entry_full_path.is_dir() {
+            if max_depth > 0 {
+                find_crash_dumps(
+                    &entry_full_path,
+                    max_depth - 1,
+                    since,
+                    out_crash_dump_full_paths,
+                );
+            }
+        } else if entry_full_path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("dmp"))
+            && is_modified_since(&entry_full_path, since)
+        {
+            out_crash_dump_full_paths.push(entry_full_path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn crash_report(
+        status: ExitStatus,
+        player_log_has_crash_marker: bool,
+        crash_dump_full_paths: Vec<PathBuf>,
+    ) -> CrashReport {
+        CrashReport {
+            target_name: "RiskOfRain2".to_string(),
+            process_exit: ProcessExit {
+                status,
+                time: UNIX_EPOCH,
+            },
+            last_logs: Vec::new(),
+            player_log_full_path: None,
+            player_log_has_crash_marker,
+            crash_dump_full_paths,
+        }
+    }
+
+    #[test]
+    fn abnormal_exit_status_decides() {
+        assert!(crash_report(ExitStatus::Code(0xC000_0005), false, Vec::new()).is_crash());
+        assert!(!crash_report(ExitStatus::Code(0), true, vec!["crash.dmp".into()]).is_crash());
+
+        #[cfg(unix)]
+        {
+            assert!(crash_report(ExitStatus::Signal(libc::SIGSEGV), false, Vec::new()).is_crash());
+            assert!(!crash_report(ExitStatus::Signal(libc::SIGTERM), false, Vec::new()).is_crash());
+        }
+    }
+
+    #[test]
+    fn unknown_exit_status_looks_at_the_player_log_and_crash_dumps() {
+        let clean_exit = crash_report(ExitStatus::Unknown, false, Vec::new());
+        assert!(!clean_exit.is_crash());
+        assert_eq!(
+            clean_exit.summary(),
+            "RiskOfRain2 exited with unknown exit status"
+        );
+
+        let player_log_crash = crash_report(ExitStatus::Unknown, true, vec!["crash.dmp".into()]);
+        assert!(player_log_crash.is_crash());
+        assert_eq!(
+            player_log_crash.summary(),
+            "RiskOfRain2 exited with unknown exit status, the Player.log reports a crash"
+        );
+
+        let crash_dumps = crash_report(ExitStatus::Unknown, false, vec!["crash.dmp".into()]);
+        assert!(crash_dumps.is_crash());
+        assert_eq!(
+            crash_dumps.summary(),
+            "RiskOfRain2 exited with unknown exit status, crash dumps were written"
+        );
+    }
+
+    #[test]
+    fn summary_only_explains_unknown_exit_statuses() {
+        assert_eq!(
+            crash_report(ExitStatus::Code(0xC000_0005), true, Vec::new()).summary(),
+            "RiskOfRain2 exited with exit code 0xC0000005"
+        );
+    }
+
+    #[test]
+    fn finds_recent_crash_dumps_up_to_max_depth() {
+        let folder_full_path = std::env::temp_dir().join(format!(
+            "bepinex_gui_crash_dumps_test_{}",
+            std::process::id()
+        ));
+        _ = fs::remove_dir_all(&folder_full_path);
+
+        let nested_folder_full_path = folder_full_path.join("Crashes").join("Crash_2024");
+        let too_deep_folder_full_path = nested_folder_full_path.join("a").join("b");
+        fs::create_dir_all(&too_deep_folder_full_path).unwrap();
+
+        fs::write(folder_full_path.join("game.DMP"), "").unwrap();
+        fs::write(nested_folder_full_path.join("crash.dmp"), "").unwrap();
+        fs::write(nested_folder_full_path.join("error.log"), "").unwrap();
+        fs::write(too_deep_folder_full_path.join("crash.dmp"), "").unwrap();
+
+        let mut crash_dump_full_paths = Vec::new();
+        find_crash_dumps(&folder_full_path, 2, UNIX_EPOCH, &mut crash_dump_full_paths);
+        crash_dump_full_paths.sort();
+        assert_eq!(
+            crash_dump_full_paths,
+            [
+                nested_folder_full_path.join("crash.dmp"),
+                folder_full_path.join("game.DMP"),
+            ]
+        );
+
+        // the dumps of a previous run of the game are left out
+        let mut crash_dump_full_paths = Vec::new();
+        find_crash_dumps(
+            &folder_full_path,
+            2,
+            SystemTime::now() + Duration::from_secs(60),
+            &mut crash_dump_full_paths,
+        );
+        assert!(crash_dump_full_paths.is_empty());
+
+        _ = fs::remove_dir_all(&folder_full_path);
+    }
+}
@@ -0,0 +1,684 @@
+use std::{cmp::Ordering, collections::HashMap, fmt};
+
+use crate::{
+    backend::thunderstore::manifest::{self, InstalledPackage},
+    data::{
+        bepinex_log::chainloader::{ChainloaderEvent, MissingDependency},
+        bepinex_mod::{self, BepInExMod},
+        installed_plugins::InstalledPlugins,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeStatus {
+    Loaded,
+    FailedToLoad,
+    Missing,
+    // Known from a manifest or another plugin message, but not seen loading (yet)
+    Installed,
+}
+
+#[derive(Debug, Clone)]
+pub struct DependencyNode {
+    // The BepInPlugin GUID, or the package id for packages without any plugin
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    pub status: NodeStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EdgeKind {
+    Requires { version_requirement: Option<String> },
+    IncompatibleWith,
+}
+
+#[derive(Debug, Clone)]
+pub struct DependencyEdge {
+    pub from: usize,
+    pub to: usize,
+    pub kind: EdgeKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DependencyIssueKind {
+    // Not installed at all, BepInEx also reports too old dependencies as missing
+    MissingDependency(MissingDependency),
+    VersionMismatch {
+        dependency_id: String,
+        required_version: String,
+        installed_version: String,
+    },
+    Incompatible {
+        incompatible_with: Vec<String>,
+    },
+    Skipped {
+        reason: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyIssue {
+    pub mod_name: String,
+    pub kind: DependencyIssueKind,
+}
+
+impl fmt::Display for DependencyIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            DependencyIssueKind::MissingDependency(dependency) => {
+                match &dependency.version_requirement {
+                    Some(version_requirement) => write!(
+                        f,
+                        "{} is missing dependency {} ({})",
+                        self.mod_name, dependency.guid, version_requirement
+                    ),
+                    None => write!(
+                        f,
+                        "{} is missing dependency {}",
+                        self.mod_name, dependency.guid
+                    ),
+                }
+            }
+            DependencyIssueKind::VersionMismatch {
+                dependency_id,
+                required_version,
+                installed_version,
+            } => write!(
+                f,
+                "{} requires {} ({}) but {} is installed",
+                self.mod_name, dependency_id, required_version, installed_version
+            ),
+            DependencyIssueKind::Incompatible { incompatible_with } => write!(
+                f,
+                "{} is incompatible with {}",
+                self.mod_name,
+                incompatible_with.join(", ")
+            ),
+            DependencyIssueKind::Skipped { reason } => {
+                write!(f, "{} was skipped because {}", self.mod_name, reason)
+            }
+        }
+    }
+}
+
+// The BepInExPack is installed in the game root folder and not as a plugin,
+// it's always there since the GUI is launched by BepInEx
+const BEPINEX_PACK_NAME: &str = "BepInExPack";
+
+struct InstalledBepInPlugin {
+    name: String,
+    version: String,
+}
+
+// Nodes are keyed on the BepInPlugin GUID, which is what the chainloader uses for dependencies.
+// Loading messages only have the plugin name and manifests only have package ids,
+// both are mapped to GUIDs through the metadata of the installed assemblies
+#[derive(Default)]
+pub struct DependencyGraph {
+    nodes: Vec<DependencyNode>,
+    edges: Vec<DependencyEdge>,
+    node_indices: HashMap<String, usize>,
+    issues: Vec<DependencyIssue>,
+
+    installed_bepinex_plugins: HashMap<String, InstalledBepInPlugin>,
+    guid_by_plugin_name: HashMap<String, String>,
+    guids_by_package_id: HashMap<String, Vec<String>>,
+    installed_packages: Vec<InstalledPackage>,
+    // Replayed when the installed plugins are known
+    chainloader_events: Vec<ChainloaderEvent>,
+}
+
+impl DependencyGraph {
+    pub fn nodes(&self) -> &[DependencyNode] {
+        self.nodes.as_ref()
+    }
+
+    pub fn edges(&self) -> &[DependencyEdge] {
+        self.edges.as_ref()
+    }
+
+    pub fn issues(&self) -> &[DependencyIssue] {
+        self.issues.as_ref()
+    }
+
+    pub fn on_chainloader_event(&mut self, chainloader_event: &ChainloaderEvent) {
+        self.chainloader_events.push(chainloader_event.clone());
+
+        self.add_chainloader_event(chainloader_event);
+    }
+
+    // Rebuilds the whole graph, the chainloader events may have been keyed on plugin names until now
+    pub fn set_installed_plugins(&mut self, installed_plugins: &InstalledPlugins) {
+        self.installed_bepinex_plugins.clear();
+        self.guid_by_plugin_name.clear();
+        self.guids_by_package_id.clear();
+
+        for plugin in &installed_plugins.plugins {
+            for bepinex_plugin in &plugin.bepinex_plugins {
+                self.installed_bepinex_plugins.insert(
+                    bepinex_plugin.guid.clone(),
+                    InstalledBepInPlugin {
+                        name: bepinex_plugin.name.clone(),
+                        version: bepinex_plugin.version.clone(),
+                    },
+                );
+                self.guid_by_plugin_name
+                    .insert(bepinex_plugin.name.clone(), bepinex_plugin.guid.clone());
+
+                if let Some(package_id) = &plugin.package_id {
+                    self.guids_by_package_id
+                        .entry(package_id.clone())
+                        .or_default()
+                        .push(bepinex_plugin.guid.clone());
+                }
+            }
+        }
+
+        self.installed_packages = installed_plugins.packages.clone();
+
+        self.rebuild();
+    }
+
+    // Keeps the installed plugins, for when the game restarts
+    pub fn clear_chainloader_events(&mut self) {
+        self.chainloader_events.clear();
+
+        self.rebuild();
+    }
+
+    fn rebuild(&mut self) {
+        self.nodes.clear();
+        self.edges.clear();
+        self.node_indices.clear();
+        self.issues.clear();
+
+        let mut installed_bepinex_plugins: Vec<(String, String, String)> = self
+            .installed_bepinex_plugins
+            .iter()
+            .map(|(guid, plugin)| (guid.clone(), plugin.name.clone(), plugin.version.clone()))
+            .collect();
+        installed_bepinex_plugins.sort_unstable();
+        for (guid, name, version) in installed_bepinex_plugins {
+            self.add_node(&guid, &name, &version, NodeStatus::Installed);
+        }
+
+        self.add_installed_packages();
+
+        for chainloader_event in std::mem::take(&mut self.chainloader_events) {
+            self.add_chainloader_event(&chainloader_event);
+            self.chainloader_events.push(chainloader_event);
+        }
+    }
+
+    fn mod_node_id(&self, mod_: &BepInExMod) -> String {
+        self.guid_by_plugin_name
+            .get(mod_.name())
+            .cloned()
+            .unwrap_or_else(|| mod_.name().to_string())
+    }
+
+    fn add_mod_node(&mut self, mod_: &BepInExMod, status: NodeStatus) -> usize {
+        let id = self.mod_node_id(mod_);
+
+        self.add_node(&id, mod_.name(), mod_.version(), status)
+    }
+
+    fn add_guid_node(&mut self, guid: &str, status: NodeStatus) -> usize {
+        match self.installed_bepinex_plugins.get(guid) {
+            Some(plugin) => {
+                let (name, version) = (plugin.name.clone(), plugin.version.clone());
+                self.add_node(guid, &name, &version, NodeStatus::Installed)
+            }
+            None => self.add_node(guid, guid, "", status),
+        }
+    }
+
+    fn add_chainloader_event(&mut self, chainloader_event: &ChainloaderEvent) {
+        match chainloader_event {
+            ChainloaderEvent::Loading(mod_) => {
+                self.add_mod_node(mod_, NodeStatus::Loaded);
+            }
+            ChainloaderEvent::Skipped { mod_, reason } => {
+                self.add_mod_node(mod_, NodeStatus::FailedToLoad);
+
+                self.issues.push(DependencyIssue {
+                    mod_name: mod_.to_string(),
+                    kind: DependencyIssueKind::Skipped {
+                        reason: reason.clone(),
+                    },
+                });
+            }
+            ChainloaderEvent::Incompatible {
+                mod_,
+                incompatible_with,
+            } => {
+                let from = self.add_mod_node(mod_, NodeStatus::FailedToLoad);
+
+                for guid in incompatible_with {
+                    let to = self.add_guid_node(guid, NodeStatus::Installed);
+                    self.add_edge(from, to, EdgeKind::IncompatibleWith);
+                }
+
+                self.issues.push(DependencyIssue {
+                    mod_name: mod_.to_string(),
+                    kind: DependencyIssueKind::Incompatible {
+                        incompatible_with: incompatible_with.clone(),
+                    },
+                });
+            }
+            ChainloaderEvent::MissingDependencies { mod_, dependencies } => {
+                let from = self.add_mod_node(mod_, NodeStatus::FailedToLoad);
+
+                for dependency in dependencies {
+                    let to = self.add_guid_node(&dependency.guid, NodeStatus::Missing);
+                    self.add_edge(
+                        from,
+                        to,
+                        EdgeKind::Requires {
+                            version_requirement: dependency.version_requirement.clone(),
+                        },
+                    );
+
+                    // BepInEx also reports installed dependencies that are too old
+                    // or that failed to load themselves as missing
+                    let kind = match (
+                        self.installed_bepinex_plugins.get(&dependency.guid),
+                        &dependency.version_requirement,
+                    ) {
+                        (None, _) => DependencyIssueKind::MissingDependency(dependency.clone()),
+                        (Some(installed_plugin), Some(version_requirement)) => {
+                            let is_version_satisfied = minimum_version(version_requirement)
+                                .is_some_and(|minimum_version| {
+                                    bepinex_mod::compare_versions(
+                                        &installed_plugin.version,
+                                        minimum_version,
+                                    ) != Ordering::Less
+                                });
+
+                            // recent enough, it failed to load by itself
+                            if is_version_satisfied {
+                                continue;
+                            }
+
+                            DependencyIssueKind::VersionMismatch {
+                                dependency_id: dependency.guid.clone(),
+                                required_version: version_requirement.clone(),
+                                installed_version: installed_plugin.version.clone(),
+                            }
+                        }
+                        // the dependency has its own issue explaining why it didn't load
+                        (Some(_), None) => continue,
+                    };
+
+                    self.issues.push(DependencyIssue {
+                        mod_name: mod_.to_string(),
+                        kind,
+                    });
+                }
+            }
+            ChainloaderEvent::StartupComplete => {}
+        }
+    }
+
+    // Matches on the "Author-Name" id, or on the name alone for packages installed by hand
+    fn find_installed_package(&self, package_id: &str) -> Option<&InstalledPackage> {
+        let package_name = package_id
+            .split_once('-')
+            .map_or(package_id, |(_, name)| name);
+
+        self.installed_packages
+            .iter()
+            .find(|package| package.id == package_id)
+            .or_else(|| {
+                self.installed_packages
+                    .iter()
+                    .find(|package| package.manifest.name == package_name)
+            })
+    }
+
+    // The plugins of the package, or the package itself when it has none
+    fn add_package_nodes(&mut self, installed_package: &InstalledPackage) -> Vec<usize> {
+        match self.guids_by_package_id.get(&installed_package.id).cloned() {
+            Some(guids) => guids
+                .iter()
+                .map(|guid| self.add_guid_node(guid, NodeStatus::Installed))
+                .collect(),
+            None => vec![self.add_node(
+                &installed_package.id,
+                &installed_package.manifest.name,
+                &installed_package.manifest.version_number,
+                NodeStatus::Installed,
+            )],
+        }
+    }
+
+    fn add_installed_packages(&mut self) {
+        let installed_packages = self.installed_packages.clone();
+
+        for installed_package in &installed_packages {
+            let from_nodes = self.add_package_nodes(installed_package);
+
+            for dependency_string in &installed_package.manifest.dependencies {
+                let (dependency_id, required_version) =
+                    manifest::split_dependency_string(dependency_string);
+
+                if dependency_id.ends_with(BEPINEX_PACK_NAME) {
+                    continue;
+                }
+
+                let version_requirement =
+                    required_version.map(|version| format!("{version} or newer"));
+
+                let dependency_package = self.find_installed_package(dependency_id).cloned();
+                let to_nodes = match &dependency_package {
+                    Some(dependency_package) => self.add_package_nodes(dependency_package),
+                    None => {
+                        vec![self.add_node(dependency_id, dependency_id, "", NodeStatus::Missing)]
+                    }
+                };
+
+                for &from in &from_nodes {
+                    for &to in &to_nodes {
+                        self.add_edge(
+                            from,
+                            to,
+                            EdgeKind::Requires {
+                                version_requirement: version_requirement.clone(),
+                            },
+                        );
+                    }
+                }
+
+                let mod_name = format!(
+                    "{} {}",
+                    installed_package.id, installed_package.manifest.version_number
+                );
+
+                match (dependency_package, required_version) {
+                    (None, _) => self.issues.push(DependencyIssue {
+                        mod_name,
+                        kind: DependencyIssueKind::MissingDependency(MissingDependency {
+                            guid: dependency_id.to_string(),
+                            version_requirement,
+                        }),
+                    }),
+                    (Some(dependency_package), Some(required_version))
+                        if bepinex_mod::compare_versions(
+                            &dependency_package.manifest.version_number,
+                            required_version,
+                        ) == Ordering::Less =>
+                    {
+                        self.issues.push(DependencyIssue {
+                            mod_name,
+                            kind: DependencyIssueKind::VersionMismatch {
+                                dependency_id: dependency_id.to_string(),
+                                required_version: format!("{required_version} or newer"),
+                                installed_version: dependency_package.manifest.version_number,
+                            },
+                        });
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    // Returns the index of the node, a more precise status overrides the previous one
+    fn add_node(&mut self, id: &str, name: &str, version: &str, status: NodeStatus) -> usize {
+        if let Some(&i) = self.node_indices.get(id) {
+            let node = &mut self.nodes[i];
+
+            if node.version.is_empty() {
+                node.version = version.to_string();
+            }
+
+            node.status = match (node.status, status) {
+                (_, NodeStatus::FailedToLoad) => status,
+                (NodeStatus::Loaded | NodeStatus::FailedToLoad, _) => node.status,
+                (_, NodeStatus::Missing) => node.status,
+                _ => status,
+            };
+
+            return i;
+        }
+
+        self.nodes.push(DependencyNode {
+            id: id.to_string(),
+            name: name.to_string(),
+            version: version.to_string(),
+            status,
+        });
+
+        let i = self.nodes.len() - 1;
+        self.node_indices.insert(id.to_string(), i);
+
+        i
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize, kind: EdgeKind) {
+        let edge_already_exists = self
+            .edges
+            .iter()
+            .any(|edge| edge.from == from && edge.to == to && edge.kind == kind);
+
+        if !edge_already_exists {
+            self.edges.push(DependencyEdge { from, to, kind });
+        }
+    }
+}
+
+// "v1.2.3 or newer" on BepInEx 5, None for the version ranges of BepInEx 6
+fn minimum_version(version_requirement: &str) -> Option<&str> {
+    version_requirement.strip_suffix(" or newer")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::{
+        backend::{assembly_metadata::BepInPlugin, thunderstore::manifest::Manifest},
+        data::installed_plugins::InstalledPlugin,
+    };
+
+    use super::*;
+
+    fn package(id: &str, version: &str, dependencies: &[&str]) -> InstalledPackage {
+        InstalledPackage {
+            id: id.to_string(),
+            manifest: Manifest {
+                name: id.split_once('-').map_or(id, |(_, name)| name).to_string(),
+                version_number: version.to_string(),
+                website_url: String::new(),
+                description: String::new(),
+                dependencies: dependencies.iter().map(ToString::to_string).collect(),
+            },
+            folder_full_path: PathBuf::from(id),
+        }
+    }
+
+    fn plugin(package_id: &str, guid: &str, name: &str, version: &str) -> InstalledPlugin {
+        InstalledPlugin {
+            name: package_id.to_string(),
+            version: Some(version.to_string()),
+            full_path: PathBuf::from(package_id),
+            bepinex_plugins: vec![BepInPlugin {
+                guid: guid.to_string(),
+                name: name.to_string(),
+                version: version.to_string(),
+                type_name: format!("{name}.Plugin"),
+            }],
+            package_id: Some(package_id.to_string()),
+        }
+    }
+
+    fn installed_plugins() -> InstalledPlugins {
+        InstalledPlugins {
+            plugins: vec![
+                plugin(
+                    "tristanmcpherson-R2API",
+                    "com.bepis.r2api",
+                    "R2API",
+                    "4.4.1",
+                ),
+                plugin("Author-CoolMod", "com.author.coolmod", "Cool Mod", "1.0.0"),
+            ],
+            packages: vec![
+                package(
+                    "tristanmcpherson-R2API",
+                    "4.4.1",
+                    &["bbepis-BepInExPack-5.4.2100"],
+                ),
+                package(
+                    "Author-CoolMod",
+                    "1.0.0",
+                    &["tristanmcpherson-R2API-5.0.0", "Someone-Library-1.0.0"],
+                ),
+            ],
+            patcher_assembly_count: 0,
+        }
+    }
+
+    fn node<'a>(graph: &'a DependencyGraph, id: &str) -> &'a DependencyNode {
+        graph
+            .nodes()
+            .iter()
+            .find(|node| node.id == id)
+            .unwrap_or_else(|| panic!("no node {id}"))
+    }
+
+    #[test]
+    fn keys_loaded_plugins_and_packages_on_guids() {
+        let mut graph = DependencyGraph::default();
+        graph.on_chainloader_event(&ChainloaderEvent::Loading(BepInExMod::new(
+            "R2API", "4.4.1",
+        )));
+        graph.set_installed_plugins(&installed_plugins());
+
+        let r2api = node(&graph, "com.bepis.r2api");
+        assert_eq!(r2api.name, "R2API");
+        assert_eq!(r2api.status, NodeStatus::Loaded);
+        assert_eq!(
+            node(&graph, "com.author.coolmod").status,
+            NodeStatus::Installed
+        );
+        assert!(!graph
+            .nodes()
+            .iter()
+            .any(|node| node.id == "R2API" || node.id == "tristanmcpherson-R2API"));
+
+        assert_eq!(node(&graph, "Someone-Library").status, NodeStatus::Missing);
+        assert_eq!(
+            graph.issues(),
+            [
+                DependencyIssue {
+                    mod_name: "Author-CoolMod 1.0.0".to_string(),
+                    kind: DependencyIssueKind::VersionMismatch {
+                        dependency_id: "tristanmcpherson-R2API".to_string(),
+                        required_version: "5.0.0 or newer".to_string(),
+                        installed_version: "4.4.1".to_string(),
+                    },
+                },
+                DependencyIssue {
+                    mod_name: "Author-CoolMod 1.0.0".to_string(),
+                    kind: DependencyIssueKind::MissingDependency(MissingDependency {
+                        guid: "Someone-Library".to_string(),
+                        version_requirement: Some("1.0.0 or newer".to_string()),
+                    }),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn installed_dependencies_are_not_missing() {
+        let mut graph = DependencyGraph::default();
+        graph.set_installed_plugins(&installed_plugins());
+        graph.on_chainloader_event(&ChainloaderEvent::MissingDependencies {
+            mod_: BepInExMod::new("Cool Mod", "1.0.0"),
+            dependencies: vec![
+                MissingDependency {
+                    guid: "com.bepis.r2api".to_string(),
+                    version_requirement: Some("v5.0.0 or newer".to_string()),
+                },
+                MissingDependency {
+                    guid: "com.someone.library".to_string(),
+                    version_requirement: None,
+                },
+            ],
+        });
+
+        assert_eq!(
+            node(&graph, "com.author.coolmod").status,
+            NodeStatus::FailedToLoad
+        );
+        assert_eq!(
+            node(&graph, "com.bepis.r2api").status,
+            NodeStatus::Installed
+        );
+        assert_eq!(
+            node(&graph, "com.someone.library").status,
+            NodeStatus::Missing
+        );
+
+        let chainloader_issues: Vec<_> = graph
+            .issues()
+            .iter()
+            .filter(|issue| issue.mod_name == "Cool Mod 1.0.0")
+            .map(|issue| &issue.kind)
+            .collect();
+        assert_eq!(
+            chainloader_issues,
+            [
+                &DependencyIssueKind::VersionMismatch {
+                    dependency_id: "com.bepis.r2api".to_string(),
+                    required_version: "v5.0.0 or newer".to_string(),
+                    installed_version: "4.4.1".to_string(),
+                },
+                &DependencyIssueKind::MissingDependency(MissingDependency {
+                    guid: "com.someone.library".to_string(),
+                    version_requirement: None,
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn installed_dependency_with_a_satisfied_version_that_failed_to_load_is_not_outdated() {
+        let mut graph = DependencyGraph::default();
+        graph.set_installed_plugins(&installed_plugins());
+        graph.on_chainloader_event(&ChainloaderEvent::MissingDependencies {
+            mod_: BepInExMod::new("Cool Mod", "1.0.0"),
+            dependencies: vec![MissingDependency {
+                guid: "com.bepis.r2api".to_string(),
+                version_requirement: Some("v4.0.0 or newer".to_string()),
+            }],
+        });
+
+        assert_eq!(
+            node(&graph, "com.author.coolmod").status,
+            NodeStatus::FailedToLoad
+        );
+        assert!(!graph
+            .issues()
+            .iter()
+            .any(|issue| issue.mod_name == "Cool Mod 1.0.0"));
+    }
+
+    #[test]
+    fn clearing_chainloader_events_keeps_installed_plugins() {
+        let mut graph = DependencyGraph::default();
+        graph.set_installed_plugins(&installed_plugins());
+        graph.on_chainloader_event(&ChainloaderEvent::Loading(BepInExMod::new(
+            "R2API", "4.4.1",
+        )));
+
+        graph.clear_chainloader_events();
+
+        assert_eq!(
+            node(&graph, "com.bepis.r2api").status,
+            NodeStatus::Installed
+        );
+    }
+}
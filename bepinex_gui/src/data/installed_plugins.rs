@@ -0,0 +1,171 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+};
+
+use crossbeam_channel::Sender;
+
+use crate::{
+    backend::{
+        assembly_metadata::{self, BepInPlugin},
+        thunderstore::manifest::{self, InstalledPackage},
+    },
+    data::bepinex_mod::BepInExMod,
+};
+
+const ASSEMBLY_EXTENSION: &str = "dll";
+
+// Plugin folders are sometimes deeply nested by hand made installs, but not that much
+const MAX_FOLDER_DEPTH: usize = 8;
+
+// Either a Thunderstore / r2modman package, or a dll that was dropped directly in the plugins folder
+#[derive(Debug, Clone)]
+pub struct InstalledPlugin {
+    pub name: String,
+    pub version: Option<String>,
+    pub full_path: PathBuf,
+    // Read from the assemblies, libraries without any are not plugins
+    pub bepinex_plugins: Vec<BepInPlugin>,
+    // None for plugins that are not part of a package
+    pub package_id: Option<String>,
+}
+
+impl InstalledPlugin {
+    // The chainloader logs the name from the BepInPlugin attribute when loading a plugin
+    pub fn matches(&self, mod_: &BepInExMod) -> bool {
+        self.bepinex_plugins
+            .iter()
+            .any(|bepinex_plugin| bepinex_plugin.name == mod_.name())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct InstalledPlugins {
+    pub plugins: Vec<InstalledPlugin>,
+    pub packages: Vec<InstalledPackage>,
+    pub patcher_assembly_count: usize,
+}
+
+impl InstalledPlugins {
+    pub fn not_loaded<'a>(
+        &'a self,
+        loaded_mods: &'a [BepInExMod],
+    ) -> impl Iterator<Item = &'a InstalledPlugin> {
+        self.plugins
+            .iter()
+            .filter(|plugin| !loaded_mods.iter().any(|mod_| plugin.matches(mod_)))
+    }
+}
+
+// The BepInEx folder is in the mod manager profile folder and not in the game one when using r2modman
+pub fn spawn_thread_scan(
+    bepinex_folder_full_path: PathBuf,
+    installed_plugins_sender: Sender<InstalledPlugins>,
+) {
+    thread::spawn(move || {
+        let installed_plugins = scan(&bepinex_folder_full_path);
+
+        tracing::info!(
+            "Found {} installed plugins and {} patchers",
+            installed_plugins.plugins.len(),
+            installed_plugins.patcher_assembly_count
+        );
+
+        if let Err(err) = installed_plugins_sender.send(installed_plugins) {
+            tracing::error!("Failed sending installed plugins: {}", err);
+        }
+    });
+}
+
+pub fn scan(bepinex_folder_full_path: &Path) -> InstalledPlugins {
+    let plugins_folder_full_path = bepinex_folder_full_path.join("plugins");
+    let patchers_folder_full_path = bepinex_folder_full_path.join("patchers");
+
+    let plugin_packages = manifest::find_installed_packages(&plugins_folder_full_path);
+    let patcher_packages = manifest::find_installed_packages(&patchers_folder_full_path);
+
+    let mut plugin_assemblies = Vec::new();
+    find_assemblies(&plugins_folder_full_path, 0, &mut plugin_assemblies);
+
+    let mut patcher_assemblies = Vec::new();
+    find_assemblies(&patchers_folder_full_path, 0, &mut patcher_assemblies);
+
+    let mut plugins: Vec<InstalledPlugin> = plugin_packages
+        .iter()
+        .map(|package| InstalledPlugin {
+            name: package.id.clone(),
+            version: Some(package.manifest.version_number.clone()),
+            full_path: package.folder_full_path.clone(),
+            bepinex_plugins: Vec::new(),
+            package_id: Some(package.id.clone()),
+        })
+        .collect();
+    let package_count = plugins.len();
+
+    for assembly_full_path in plugin_assemblies {
+        let bepinex_plugins = assembly_metadata::read_bepinex_plugins(&assembly_full_path);
+        if bepinex_plugins.is_empty() {
+            continue;
+        }
+
+        // the deepest package folder containing the assembly owns it
+        let owning_package_index = plugins[..package_count]
+            .iter()
+            .enumerate()
+            .filter(|(_, plugin)| assembly_full_path.starts_with(&plugin.full_path))
+            .max_by_key(|(_, plugin)| plugin.full_path.components().count())
+            .map(|(i, _)| i);
+
+        match owning_package_index {
+            Some(i) => plugins[i].bepinex_plugins.extend(bepinex_plugins),
+            None => plugins.push(InstalledPlugin {
+                name: assembly_full_path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                // an assembly can hold several plugins, the version only makes sense for one
+                version: match bepinex_plugins.as_slice() {
+                    [bepinex_plugin] => Some(bepinex_plugin.version.clone()),
+                    _ => None,
+                },
+                full_path: assembly_full_path,
+                bepinex_plugins,
+                package_id: None,
+            }),
+        }
+    }
+
+    // packages without any plugin are libraries, assets, language files, etc. they are never "loaded"
+    plugins.retain(|plugin| !plugin.bepinex_plugins.is_empty());
+
+    let mut packages = plugin_packages;
+    packages.extend(patcher_packages);
+
+    InstalledPlugins {
+        plugins,
+        packages,
+        patcher_assembly_count: patcher_assemblies.len(),
+    }
+}
+
+fn find_assemblies(folder_full_path: &Path, depth: usize, assemblies: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(folder_full_path) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        if path.is_dir() {
+            if depth < MAX_FOLDER_DEPTH {
+                find_assemblies(&path, depth + 1, assemblies);
+            }
+        } else if path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case(ASSEMBLY_EXTENSION))
+        {
+            assemblies.push(path);
+        }
+    }
+}
@@ -1,2 +1,8 @@
 pub mod bepinex_log;
 pub mod bepinex_mod;
+pub mod crash_report;
+pub mod dependency_graph;
+pub mod installed_plugins;
+pub mod mod_list;
+pub mod resource_usage;
+pub mod startup_profile;
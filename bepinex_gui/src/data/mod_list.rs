@@ -0,0 +1,418 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter};
+
+use crate::{
+    backend::thunderstore::manifest,
+    data::{bepinex_mod::BepInExMod, installed_plugins::InstalledPlugins},
+    paths,
+};
+
+const SAVED_MOD_LISTS_FOLDER_NAME: &str = "mod_lists";
+const SAVED_MOD_LIST_EXTENSION: &str = "json";
+
+// Oldest saved mod lists are deleted past that
+const MAX_SAVED_MOD_LIST_COUNT: usize = 15;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportedMod {
+    pub name: String,
+    // The version of the plugin itself, which often differs from the package one
+    pub version: String,
+    // "Author-Name" of the Thunderstore package the plugin comes from, if known
+    #[serde(default)]
+    pub package_id: Option<String>,
+    // Mod lists saved before this field existed had the package version in `version`
+    #[serde(default)]
+    pub package_version: Option<String>,
+}
+
+impl ExportedMod {
+    // r2modman / Thunderstore "Author-Name-Version" format, only packages can be installed that way
+    pub fn dependency_string(&self) -> Option<String> {
+        let package_id = self.package_id.as_ref()?;
+
+        Some(format!("{}-{}", package_id, self.package_version()))
+    }
+
+    fn package_version(&self) -> &str {
+        self.package_version.as_deref().unwrap_or(&self.version)
+    }
+
+    // A package is compared as a whole, the package id is the most stable identifier across sessions
+    pub fn diff_name(&self) -> &str {
+        self.package_id.as_deref().unwrap_or(&self.name)
+    }
+
+    pub fn diff_version(&self) -> &str {
+        if self.package_id.is_some() {
+            self.package_version()
+        } else {
+            &self.version
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumIter)]
+pub enum ExportFormat {
+    #[strum(serialize = "Plain Text")]
+    PlainText,
+    #[strum(serialize = "JSON")]
+    Json,
+    #[strum(serialize = "Thunderstore Dependencies")]
+    ThunderstoreDependencies,
+}
+
+pub fn make_exported_mods(
+    mods: &[BepInExMod],
+    installed_plugins: Option<&InstalledPlugins>,
+) -> Vec<ExportedMod> {
+    mods.iter()
+        .map(|mod_| make_exported_mod(mod_, installed_plugins))
+        .collect()
+}
+
+pub fn make_exported_mod(
+    mod_: &BepInExMod,
+    installed_plugins: Option<&InstalledPlugins>,
+) -> ExportedMod {
+    let installed_package = installed_plugins.and_then(|installed_plugins| {
+        installed_plugins
+            .plugins
+            .iter()
+            .filter(|plugin| plugin.package_id.is_some())
+            .find(|plugin| plugin.matches(mod_))
+    });
+
+    ExportedMod {
+        name: mod_.name().to_string(),
+        version: mod_.version().to_string(),
+        package_id: installed_package.and_then(|package| package.package_id.clone()),
+        package_version: installed_package.and_then(|package| package.version.clone()),
+    }
+}
+
+// Several plugins can come from the same package, it's only listed once
+fn dependency_strings(exported_mods: &[ExportedMod]) -> Vec<String> {
+    let mut dependency_strings: Vec<String> = Vec::new();
+
+    for dependency_string in exported_mods
+        .iter()
+        .filter_map(ExportedMod::dependency_string)
+    {
+        if !dependency_strings.contains(&dependency_string) {
+            dependency_strings.push(dependency_string);
+        }
+    }
+
+    dependency_strings
+}
+
+// Several plugins can come from the same package, the first one stands for the package
+fn dedup_by_diff_name(exported_mods: &[ExportedMod]) -> Vec<&ExportedMod> {
+    let mut deduped_mods: Vec<&ExportedMod> = Vec::new();
+
+    for exported_mod in exported_mods {
+        if !deduped_mods
+            .iter()
+            .any(|deduped_mod| deduped_mod.diff_name() == exported_mod.diff_name())
+        {
+            deduped_mods.push(exported_mod);
+        }
+    }
+
+    deduped_mods
+}
+
+pub fn export(exported_mods: &[ExportedMod], format: ExportFormat) -> String {
+    match format {
+        ExportFormat::PlainText => exported_mods
+            .iter()
+            .map(|mod_| format!("{} {}", mod_.name, mod_.version))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        ExportFormat::Json => serde_json::to_string_pretty(exported_mods).unwrap_or_default(),
+        ExportFormat::ThunderstoreDependencies => dependency_strings(exported_mods).join("\n"),
+    }
+}
+
+// Accepts any of the export formats
+pub fn import(text: &str) -> Vec<ExportedMod> {
+    if let Ok(exported_mods) = serde_json::from_str::<Vec<ExportedMod>>(text) {
+        return exported_mods;
+    }
+
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            if !line.contains(' ') {
+                if let (package_id, Some(version)) = manifest::split_dependency_string(line) {
+                    return ExportedMod {
+                        name: package_id
+                            .split_once('-')
+                            .map_or(package_id, |(_author, name)| name)
+                            .replace('_', " "),
+                        version: version.to_string(),
+                        package_id: Some(package_id.to_string()),
+                        package_version: Some(version.to_string()),
+                    };
+                }
+            }
+
+            match line.rsplit_once(' ') {
+                Some((name, version)) => ExportedMod {
+                    name: name.to_string(),
+                    version: version.to_string(),
+                    package_id: None,
+                    package_version: None,
+                },
+                None => ExportedMod {
+                    name: line.to_string(),
+                    version: String::new(),
+                    package_id: None,
+                    package_version: None,
+                },
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Default)]
+pub struct ModListDiff {
+    pub added: Vec<ExportedMod>,
+    pub removed: Vec<ExportedMod>,
+    // (old, new)
+    pub version_changed: Vec<(ExportedMod, ExportedMod)>,
+}
+
+impl ModListDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.version_changed.is_empty()
+    }
+}
+
+pub fn diff(old_mods: &[ExportedMod], new_mods: &[ExportedMod]) -> ModListDiff {
+    let mut mod_list_diff = ModListDiff::default();
+
+    let old_mods = dedup_by_diff_name(old_mods);
+    let new_mods = dedup_by_diff_name(new_mods);
+
+    for &new_mod in &new_mods {
+        match old_mods
+            .iter()
+            .find(|old_mod| old_mod.diff_name() == new_mod.diff_name())
+        {
+            Some(&old_mod) => {
+                if old_mod.diff_version() != new_mod.diff_version() {
+                    mod_list_diff
+                        .version_changed
+                        .push((old_mod.clone(), new_mod.clone()));
+                }
+            }
+            None => mod_list_diff.added.push(new_mod.clone()),
+        }
+    }
+
+    for &old_mod in &old_mods {
+        if !new_mods
+            .iter()
+            .any(|new_mod| new_mod.diff_name() == old_mod.diff_name())
+        {
+            mod_list_diff.removed.push(old_mod.clone());
+        }
+    }
+
+    mod_list_diff
+}
+
+fn saved_mod_lists_directory(target_name: &str) -> Option<PathBuf> {
+    paths::get_app_config_directory().map(|directory| {
+        directory
+            .join(SAVED_MOD_LISTS_FOLDER_NAME)
+            .join(target_name)
+    })
+}
+
+pub fn save(target_name: &str, exported_mods: &[ExportedMod]) -> io::Result<PathBuf> {
+    let directory_full_path = saved_mod_lists_directory(target_name)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No app config directory"))?;
+    fs::create_dir_all(&directory_full_path)?;
+
+    let unix_timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let file_full_path =
+        directory_full_path.join(format!("{unix_timestamp}.{SAVED_MOD_LIST_EXTENSION}"));
+    fs::write(&file_full_path, export(exported_mods, ExportFormat::Json))?;
+
+    for old_mod_list_full_path in find_saved(target_name)
+        .iter()
+        .skip(MAX_SAVED_MOD_LIST_COUNT)
+    {
+        if let Err(err) = fs::remove_file(old_mod_list_full_path) {
+            tracing::error!("Failed deleting old saved mod list: {}", err);
+        }
+    }
+
+    Ok(file_full_path)
+}
+
+// Most recent first
+pub fn find_saved(target_name: &str) -> Vec<PathBuf> {
+    let Some(directory_full_path) = saved_mod_lists_directory(target_name) else {
+        return Vec::new();
+    };
+
+    let Ok(entries) = fs::read_dir(directory_full_path) else {
+        return Vec::new();
+    };
+
+    let mut saved_mod_lists: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == SAVED_MOD_LIST_EXTENSION)
+        })
+        .collect();
+
+    saved_mod_lists.sort_by(|a, b| b.cmp(a));
+
+    saved_mod_lists
+}
+
+pub fn load(file_full_path: &Path) -> io::Result<Vec<ExportedMod>> {
+    Ok(import(&fs::read_to_string(file_full_path)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        backend::assembly_metadata::BepInPlugin, data::installed_plugins::InstalledPlugin,
+    };
+
+    use super::*;
+
+    fn bepinex_plugin(name: &str, version: &str) -> BepInPlugin {
+        BepInPlugin {
+            guid: format!("com.author.{}", name.to_lowercase()),
+            name: name.to_string(),
+            version: version.to_string(),
+            type_name: format!("{name}.Plugin"),
+        }
+    }
+
+    fn installed_plugins() -> InstalledPlugins {
+        InstalledPlugins {
+            plugins: vec![
+                InstalledPlugin {
+                    name: "Author-Bundle".to_string(),
+                    version: Some("2.5.0".to_string()),
+                    full_path: PathBuf::from("Author-Bundle"),
+                    bepinex_plugins: vec![
+                        bepinex_plugin("First", "1.0.0"),
+                        bepinex_plugin("Second", "1.1.0"),
+                    ],
+                    package_id: Some("Author-Bundle".to_string()),
+                },
+                InstalledPlugin {
+                    name: "Loose".to_string(),
+                    version: Some("0.1.0".to_string()),
+                    full_path: PathBuf::from("Loose.dll"),
+                    bepinex_plugins: vec![bepinex_plugin("Loose", "0.1.0")],
+                    package_id: None,
+                },
+            ],
+            packages: Vec::new(),
+            patcher_assembly_count: 0,
+        }
+    }
+
+    fn exported_mods() -> Vec<ExportedMod> {
+        let mods = [
+            BepInExMod::new("First", "1.0.0"),
+            BepInExMod::new("Second", "1.1.0"),
+            BepInExMod::new("Loose", "0.1.0"),
+        ];
+
+        make_exported_mods(&mods, Some(&installed_plugins()))
+    }
+
+    #[test]
+    fn keeps_plugin_versions() {
+        let exported_mods = exported_mods();
+
+        assert_eq!(
+            exported_mods[1],
+            ExportedMod {
+                name: "Second".to_string(),
+                version: "1.1.0".to_string(),
+                package_id: Some("Author-Bundle".to_string()),
+                package_version: Some("2.5.0".to_string()),
+            }
+        );
+        assert_eq!(exported_mods[2].package_id, None);
+        assert_eq!(
+            export(&exported_mods, ExportFormat::PlainText),
+            "First 1.0.0\nSecond 1.1.0\nLoose 0.1.0"
+        );
+    }
+
+    #[test]
+    fn thunderstore_dependencies_list_each_package_once() {
+        assert_eq!(
+            export(&exported_mods(), ExportFormat::ThunderstoreDependencies),
+            "Author-Bundle-2.5.0"
+        );
+    }
+
+    #[test]
+    fn diffs_packages_as_a_whole() {
+        let old_mods = import("Author-Bundle-2.4.0\nOther-Removed-1.0.0");
+
+        let mod_list_diff = diff(&old_mods, &exported_mods());
+
+        let added: Vec<_> = mod_list_diff
+            .added
+            .iter()
+            .map(ExportedMod::diff_name)
+            .collect();
+        assert_eq!(added, ["Loose"]);
+        let removed: Vec<_> = mod_list_diff
+            .removed
+            .iter()
+            .map(ExportedMod::diff_name)
+            .collect();
+        assert_eq!(removed, ["Other-Removed"]);
+        let version_changed: Vec<_> = mod_list_diff
+            .version_changed
+            .iter()
+            .map(|(old_mod, new_mod)| {
+                (
+                    new_mod.diff_name(),
+                    old_mod.diff_version(),
+                    new_mod.diff_version(),
+                )
+            })
+            .collect();
+        assert_eq!(version_changed, [("Author-Bundle", "2.4.0", "2.5.0")]);
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let exported_mods = exported_mods();
+
+        assert_eq!(
+            import(&export(&exported_mods, ExportFormat::Json)),
+            exported_mods
+        );
+    }
+}
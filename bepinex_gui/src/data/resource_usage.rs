@@ -0,0 +1,237 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, SystemTime},
+};
+
+use strum::EnumCount;
+
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+// One hour of samples
+const MAX_SAMPLE_COUNT: usize = 3600;
+
+// Samples averaged for knowing what the usual usage of the game is, before a spike
+const BASELINE_SAMPLE_COUNT: usize = 30;
+
+const CPU_SPIKE_MIN_USAGE: f32 = 80.;
+const MEMORY_SPIKE_MIN_GROWTH_BYTES: u64 = 256 * 1024 * 1024;
+const DISK_IO_SPIKE_MIN_BYTES: u64 = 50 * 1024 * 1024;
+
+// Memory is considered leaking when it kept growing over that window
+const LEAK_WINDOW_SAMPLE_COUNT: usize = 5 * 60;
+const LEAK_WINDOW_BUCKET_COUNT: usize = 10;
+const LEAK_MIN_GROWTH_BYTES: u64 = 100 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ResourceSample {
+    pub time: SystemTime,
+    // Percentage of one core, goes above 100 when using multiple cores
+    pub cpu_usage: f32,
+    pub memory_bytes: u64,
+    pub thread_count: usize,
+    // Handles on Windows, file descriptors on Linux
+    pub handle_count: Option<u32>,
+    // Since the previous sample
+    pub disk_read_bytes: u64,
+    pub disk_written_bytes: u64,
+}
+
+impl ResourceSample {
+    pub const fn disk_io_bytes(&self) -> u64 {
+        self.disk_read_bytes + self.disk_written_bytes
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumCount)]
+pub enum ResourceKind {
+    Cpu,
+    Memory,
+    DiskIo,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ResourceSpike {
+    pub time: SystemTime,
+    pub kind: ResourceKind,
+    // CPU percentage, memory growth in bytes, or disk bytes read and written
+    pub value: f64,
+}
+
+pub struct MemoryLeakWarning {
+    pub growth_bytes: u64,
+    pub duration: Duration,
+}
+
+#[derive(Default)]
+pub struct ResourceHistory {
+    samples: VecDeque<ResourceSample>,
+    spikes: Vec<ResourceSpike>,
+    // a spike lasting several samples is only reported once
+    is_spiking: [bool; ResourceKind::COUNT],
+    // only recomputed when a sample is pushed, it goes through minutes of samples
+    memory_leak_warning: Option<MemoryLeakWarning>,
+}
+
+impl ResourceHistory {
+    pub fn samples(&self) -> &VecDeque<ResourceSample> {
+        &self.samples
+    }
+
+    pub fn spikes(&self) -> &[ResourceSpike] {
+        &self.spikes
+    }
+
+    pub fn last_sample(&self) -> Option<&ResourceSample> {
+        self.samples.back()
+    }
+
+    pub fn memory_leak_warning(&self) -> Option<&MemoryLeakWarning> {
+        self.memory_leak_warning.as_ref()
+    }
+
+    pub fn push(&mut self, sample: ResourceSample) {
+        self.detect_cpu_spike(&sample);
+        self.detect_memory_spike(&sample);
+        self.detect_disk_io_spike(&sample);
+
+        self.samples.push_back(sample);
+        if self.samples.len() > MAX_SAMPLE_COUNT {
+            self.samples.pop_front();
+        }
+
+        self.memory_leak_warning = self.detect_memory_leak();
+    }
+
+    fn baseline(&self, value: impl Fn(&ResourceSample) -> f64) -> Option<f64> {
+        if self.samples.len() < BASELINE_SAMPLE_COUNT {
+            return None;
+        }
+
+        let baseline_samples = self.samples.iter().rev().take(BASELINE_SAMPLE_COUNT);
+        Some(baseline_samples.map(value).sum::<f64>() / BASELINE_SAMPLE_COUNT as f64)
+    }
+
+    fn detect_cpu_spike(&mut self, sample: &ResourceSample) {
+        let Some(baseline) = self.baseline(|sample| sample.cpu_usage as f64) else {
+            return;
+        };
+
+        let cpu_usage = sample.cpu_usage as f64;
+        let is_spiking = cpu_usage >= CPU_SPIKE_MIN_USAGE as f64
+            && cpu_usage >= (baseline * 2.).max(baseline + 50.);
+
+        self.on_spike_check(sample.time, ResourceKind::Cpu, cpu_usage, is_spiking);
+    }
+
+    fn detect_memory_spike(&mut self, sample: &ResourceSample) {
+        let Some(last_sample) = self.samples.back() else {
+            return;
+        };
+
+        let growth_bytes = sample.memory_bytes.saturating_sub(last_sample.memory_bytes);
+        let is_spiking = growth_bytes >= MEMORY_SPIKE_MIN_GROWTH_BYTES;
+
+        self.on_spike_check(
+            sample.time,
+            ResourceKind::Memory,
+            growth_bytes as f64,
+            is_spiking,
+        );
+    }
+
+    fn detect_disk_io_spike(&mut self, sample: &ResourceSample) {
+        let Some(baseline) = self.baseline(|sample| sample.disk_io_bytes() as f64) else {
+            return;
+        };
+
+        let disk_io_bytes = sample.disk_io_bytes() as f64;
+        let is_spiking = disk_io_bytes >= (DISK_IO_SPIKE_MIN_BYTES as f64).max(baseline * 5.);
+
+        self.on_spike_check(sample.time, ResourceKind::DiskIo, disk_io_bytes, is_spiking);
+    }
+
+    fn on_spike_check(
+        &mut self,
+        time: SystemTime,
+        kind: ResourceKind,
+        value: f64,
+        is_spiking: bool,
+    ) {
+        let was_spiking = std::mem::replace(&mut self.is_spiking[kind as usize], is_spiking);
+        if is_spiking && !was_spiking {
+            self.spikes.push(ResourceSpike { time, kind, value });
+        }
+    }
+
+    // The window is split in buckets whose average memory must keep growing,
+    // so that the garbage collector freeing memory from time to time doesn't hide a leak
+    fn detect_memory_leak(&self) -> Option<MemoryLeakWarning> {
+        if self.samples.len() < LEAK_WINDOW_SAMPLE_COUNT {
+            return None;
+        }
+
+        let window: Vec<&ResourceSample> = self
+            .samples
+            .iter()
+            .skip(self.samples.len() - LEAK_WINDOW_SAMPLE_COUNT)
+            .collect();
+
+        let bucket_averages: Vec<u64> = window
+            .chunks(LEAK_WINDOW_SAMPLE_COUNT / LEAK_WINDOW_BUCKET_COUNT)
+            .map(|bucket| {
+                bucket.iter().map(|sample| sample.memory_bytes).sum::<u64>() / bucket.len() as u64
+            })
+            .collect();
+
+        let is_always_growing = bucket_averages.windows(2).all(|pair| pair[1] > pair[0]);
+        if !is_always_growing {
+            return None;
+        }
+
+        let growth_bytes = bucket_averages.last()? - bucket_averages.first()?;
+        if growth_bytes < LEAK_MIN_GROWTH_BYTES {
+            return None;
+        }
+
+        Some(MemoryLeakWarning {
+            growth_bytes,
+            duration: SAMPLE_INTERVAL * LEAK_WINDOW_SAMPLE_COUNT as u32,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(memory_bytes: u64) -> ResourceSample {
+        ResourceSample {
+            time: SystemTime::now(),
+            cpu_usage: 0.,
+            memory_bytes,
+            thread_count: 1,
+            handle_count: None,
+            disk_read_bytes: 0,
+            disk_written_bytes: 0,
+        }
+    }
+
+    #[test]
+    fn memory_leak_is_detected_once_the_window_is_full() {
+        let mut resource_history = ResourceHistory::default();
+
+        for i in 0..LEAK_WINDOW_SAMPLE_COUNT as u64 {
+            assert!(resource_history.memory_leak_warning().is_none());
+            resource_history.push(sample(i * 1024 * 1024));
+        }
+
+        let memory_leak_warning = resource_history.memory_leak_warning().unwrap();
+        assert!(memory_leak_warning.growth_bytes >= LEAK_MIN_GROWTH_BYTES);
+
+        // memory freed, the warning clears once the window stops growing
+        for _ in 0..LEAK_WINDOW_SAMPLE_COUNT {
+            resource_history.push(sample(0));
+        }
+        assert!(resource_history.memory_leak_warning().is_none());
+    }
+}
@@ -0,0 +1,298 @@
+use std::{
+    fs, io,
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+use serde::Serialize;
+
+use crate::data::{
+    bepinex_log::chainloader::{ChainloaderEvent, TimedChainloaderEvent},
+    bepinex_mod::BepInExMod,
+};
+
+pub struct PluginLoadSpan {
+    pub mod_: BepInExMod,
+    pub start: SystemTime,
+    // None while the plugin is still being loaded
+    pub end: Option<SystemTime>,
+}
+
+impl PluginLoadSpan {
+    pub fn duration(&self) -> Option<Duration> {
+        self.end
+            .map(|end| end.duration_since(self.start).unwrap_or_default())
+    }
+}
+
+pub struct StartupProfile {
+    process_start_time: Option<SystemTime>,
+    first_chainloader_event_time: Option<SystemTime>,
+    startup_complete_time: Option<SystemTime>,
+    plugin_load_spans: Vec<PluginLoadSpan>,
+}
+
+impl StartupProfile {
+    pub const fn new(process_start_time: Option<SystemTime>) -> Self {
+        Self {
+            process_start_time,
+            first_chainloader_event_time: None,
+            startup_complete_time: None,
+            plugin_load_spans: Vec::new(),
+        }
+    }
+
+    pub fn on_chainloader_event(&mut self, timed_chainloader_event: &TimedChainloaderEvent) {
+        let time = timed_chainloader_event.time;
+
+        if self.first_chainloader_event_time.is_none() {
+            self.first_chainloader_event_time = Some(time);
+        }
+
+        // The chainloader loads plugins one after the other,
+        // so any new chainloader message marks the end of the plugin currently loading
+        self.end_current_plugin_load_span(time);
+
+        match &timed_chainloader_event.event {
+            ChainloaderEvent::Loading(mod_) => {
+                self.plugin_load_spans.push(PluginLoadSpan {
+                    mod_: mod_.clone(),
+                    start: time,
+                    end: None,
+                });
+            }
+            ChainloaderEvent::StartupComplete => {
+                self.startup_complete_time = Some(time);
+            }
+            ChainloaderEvent::Skipped { .. }
+            | ChainloaderEvent::Incompatible { .. }
+            | ChainloaderEvent::MissingDependencies { .. } => {}
+        }
+    }
+
+    fn end_current_plugin_load_span(&mut self, time: SystemTime) {
+        if let Some(span) = self.plugin_load_spans.last_mut() {
+            if span.end.is_none() {
+                span.end = Some(time);
+            }
+        }
+    }
+
+    pub fn plugin_load_spans(&self) -> &[PluginLoadSpan] {
+        self.plugin_load_spans.as_ref()
+    }
+
+    pub const fn is_startup_complete(&self) -> bool {
+        self.startup_complete_time.is_some()
+    }
+
+    // Process start time is preferred because the chainloader only starts once BepInEx and the preloader are done
+    pub fn startup_start_time(&self) -> Option<SystemTime> {
+        self.process_start_time
+            .or(self.first_chainloader_event_time)
+    }
+
+    // Elapsed time until now if the startup is not complete yet
+    pub fn startup_duration(&self) -> Option<Duration> {
+        let start = self.startup_start_time()?;
+        let end = self.startup_complete_time.unwrap_or_else(SystemTime::now);
+
+        Some(end.duration_since(start).unwrap_or_default())
+    }
+
+    pub fn slowest_plugins(&self, count: usize) -> Vec<&PluginLoadSpan> {
+        let mut spans: Vec<&PluginLoadSpan> = self
+            .plugin_load_spans
+            .iter()
+            .filter(|span| span.end.is_some())
+            .collect();
+
+        spans.sort_by_key(|span| std::cmp::Reverse(span.duration()));
+        spans.truncate(count);
+
+        spans
+    }
+
+    // https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU
+    pub fn to_chrome_trace_json(&self) -> serde_json::Result<String> {
+        let Some(startup_start_time) = self.startup_start_time() else {
+            return serde_json::to_string(&ChromeTrace {
+                trace_events: Vec::new(),
+            });
+        };
+
+        let micros_since_startup = |time: SystemTime| {
+            time.duration_since(startup_start_time)
+                .unwrap_or_default()
+                .as_micros() as u64
+        };
+
+        let mut trace_events = Vec::with_capacity(self.plugin_load_spans.len() + 1);
+
+        if let Some(startup_duration) = self.startup_duration() {
+            trace_events.push(ChromeTraceEvent {
+                name: "Startup".to_string(),
+                cat: "startup",
+                ph: "X",
+                ts: 0,
+                dur: startup_duration.as_micros() as u64,
+                pid: 1,
+                tid: 0,
+            });
+        }
+
+        for span in &self.plugin_load_spans {
+            let end = span.end.unwrap_or_else(SystemTime::now);
+
+            trace_events.push(ChromeTraceEvent {
+                name: span.mod_.to_string(),
+                cat: "plugin",
+                ph: "X",
+                ts: micros_since_startup(span.start),
+                dur: end
+                    .duration_since(span.start)
+                    .unwrap_or_default()
+                    .as_micros() as u64,
+                pid: 1,
+                tid: 1,
+            });
+        }
+
+        serde_json::to_string_pretty(&ChromeTrace { trace_events })
+    }
+
+    pub fn export_chrome_trace(&self, file_full_path: &Path) -> io::Result<()> {
+        let json = self.to_chrome_trace_json()?;
+
+        fs::write(file_full_path, json)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ChromeTrace {
+    trace_events: Vec<ChromeTraceEvent>,
+}
+
+#[derive(Serialize)]
+struct ChromeTraceEvent {
+    name: String,
+    cat: &'static str,
+    ph: &'static str,
+    ts: u64,
+    dur: u64,
+    pid: u32,
+    tid: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::UNIX_EPOCH;
+
+    use super::*;
+
+    fn at_millis(millis: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(millis)
+    }
+
+    fn loading(millis: u64, name: &str) -> TimedChainloaderEvent {
+        TimedChainloaderEvent {
+            time: at_millis(millis),
+            event: ChainloaderEvent::Loading(BepInExMod::new(name, "1.0.0")),
+        }
+    }
+
+    fn startup_complete(millis: u64) -> TimedChainloaderEvent {
+        TimedChainloaderEvent {
+            time: at_millis(millis),
+            event: ChainloaderEvent::StartupComplete,
+        }
+    }
+
+    fn complete_startup_profile() -> StartupProfile {
+        let mut startup_profile = StartupProfile::new(Some(at_millis(100_000)));
+        for timed_chainloader_event in [
+            loading(101_000, "R2API"),
+            loading(101_500, "MoreItems"),
+            loading(104_000, "BetterUI"),
+            startup_complete(104_200),
+        ] {
+            startup_profile.on_chainloader_event(&timed_chainloader_event);
+        }
+
+        startup_profile
+    }
+
+    #[test]
+    fn each_plugin_load_ends_at_the_next_chainloader_event() {
+        let startup_profile = complete_startup_profile();
+
+        assert!(startup_profile.is_startup_complete());
+        assert_eq!(
+            startup_profile.startup_duration(),
+            Some(Duration::from_millis(4_200))
+        );
+        assert_eq!(
+            startup_profile
+                .plugin_load_spans()
+                .iter()
+                .map(PluginLoadSpan::duration)
+                .collect::<Vec<_>>(),
+            [
+                Some(Duration::from_millis(500)),
+                Some(Duration::from_millis(2_500)),
+                Some(Duration::from_millis(200)),
+            ]
+        );
+
+        let slowest_plugin_names: Vec<&str> = startup_profile
+            .slowest_plugins(2)
+            .iter()
+            .map(|span| span.mod_.name())
+            .collect();
+        assert_eq!(slowest_plugin_names, ["MoreItems", "R2API"]);
+    }
+
+    #[test]
+    fn plugins_still_loading_are_not_among_the_slowest() {
+        let mut startup_profile = StartupProfile::new(None);
+        startup_profile.on_chainloader_event(&loading(1_000, "R2API"));
+        startup_profile.on_chainloader_event(&loading(3_000, "MoreItems"));
+
+        assert!(!startup_profile.is_startup_complete());
+        // without the process start time, the startup starts with the chainloader
+        assert_eq!(startup_profile.startup_start_time(), Some(at_millis(1_000)));
+
+        let slowest_plugin_names: Vec<&str> = startup_profile
+            .slowest_plugins(5)
+            .iter()
+            .map(|span| span.mod_.name())
+            .collect();
+        assert_eq!(slowest_plugin_names, ["R2API"]);
+    }
+
+    #[test]
+    fn chrome_trace_has_the_startup_then_each_plugin_in_microseconds() {
+        let json = complete_startup_profile().to_chrome_trace_json().unwrap();
+        let chrome_trace: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(
+            chrome_trace,
+            serde_json::json!({
+                "traceEvents": [
+                    { "name": "Startup", "cat": "startup", "ph": "X", "ts": 0, "dur": 4_200_000, "pid": 1, "tid": 0 },
+                    { "name": "R2API 1.0.0", "cat": "plugin", "ph": "X", "ts": 1_000_000, "dur": 500_000, "pid": 1, "tid": 1 },
+                    { "name": "MoreItems 1.0.0", "cat": "plugin", "ph": "X", "ts": 1_500_000, "dur": 2_500_000, "pid": 1, "tid": 1 },
+                    { "name": "BetterUI 1.0.0", "cat": "plugin", "ph": "X", "ts": 4_000_000, "dur": 200_000, "pid": 1, "tid": 1 },
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn chrome_trace_is_empty_before_anything_happened() {
+        let json = StartupProfile::new(None).to_chrome_trace_json().unwrap();
+
+        assert_eq!(json, r#"{"traceEvents":[]}"#);
+    }
+}
@@ -0,0 +1,237 @@
+use eframe::egui::{Context, Id};
+use strum::IntoEnumIterator;
+
+use crate::{
+    app::BepInExGUI,
+    backend::{file_explorer_utils, thunderstore},
+    config::keymap::{KeyCommand, Keymap},
+    data::bepinex_log::{self, LogLevel},
+    views::game_picker::GamePicker,
+};
+
+// Everything the user can do from a button, a shortcut or the command palette goes through here,
+// so that each of them behaves the same no matter where it was triggered from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    SwitchTab(usize),
+    ToggleLogAutoScroll,
+    SetLogLevelFilter(LogLevel),
+    TogglePauseGame,
+    KillGame,
+    RestartGame,
+    AttachToGame,
+    OpenGameFolder,
+    CopyLogFile,
+    OpenModdingDiscord,
+    ExportLogs,
+    SwitchTheme,
+    FocusSearch,
+    SelectNextError,
+    SelectPreviousError,
+    SelectAllLogs,
+    ClearLogSelection,
+    ScrollConsoleToBottom,
+    NextTab,
+    PreviousTab,
+    // Sent to the tabs when the GUI follows a new instance of the game, not user triggered
+    GameAttached,
+}
+
+impl From<KeyCommand> for Action {
+    fn from(key_command: KeyCommand) -> Self {
+        match key_command {
+            KeyCommand::FocusSearch => Self::FocusSearch,
+            KeyCommand::NextError => Self::SelectNextError,
+            KeyCommand::PreviousError => Self::SelectPreviousError,
+            KeyCommand::SelectAllLogs => Self::SelectAllLogs,
+            KeyCommand::ClearSelection => Self::ClearLogSelection,
+            KeyCommand::PauseGame => Self::TogglePauseGame,
+            KeyCommand::ScrollToBottom => Self::ScrollConsoleToBottom,
+            KeyCommand::NextTab => Self::NextTab,
+            KeyCommand::PreviousTab => Self::PreviousTab,
+        }
+    }
+}
+
+pub struct RegisteredAction {
+    pub action: Action,
+    pub name: String,
+    // Text of the key binding triggering it, if any
+    pub shortcut: Option<String>,
+}
+
+pub fn registry(tab_names: &[&str], keymap: &Keymap) -> Vec<RegisteredAction> {
+    let mut registered_actions: Vec<RegisteredAction> = tab_names
+        .iter()
+        .enumerate()
+        .map(|(i, tab_name)| RegisteredAction {
+            action: Action::SwitchTab(i),
+            name: format!("Switch to {tab_name} Tab"),
+            shortcut: None,
+        })
+        .collect();
+
+    let mut register = |action, name: &str| {
+        let shortcut = KeyCommand::iter()
+            .find(|key_command| Action::from(*key_command) == action)
+            .and_then(|key_command| keymap.key_binding(key_command))
+            .map(|key_binding| key_binding.to_string());
+
+        registered_actions.push(RegisteredAction {
+            action,
+            name: name.to_string(),
+            shortcut,
+        });
+    };
+
+    register(Action::ToggleLogAutoScroll, "Toggle Console Auto Scroll");
+    register(Action::TogglePauseGame, "Pause / Resume Game");
+    register(Action::KillGame, "Kill GUI & Game");
+    register(Action::RestartGame, "Restart Game");
+    register(Action::AttachToGame, "Attach to a Running Game");
+    register(Action::OpenGameFolder, "Open Game Folder");
+    register(Action::CopyLogFile, "Copy Log File");
+    register(Action::OpenModdingDiscord, "Open Modding Discord");
+    register(Action::ExportLogs, "Export Console Logs");
+    register(Action::SwitchTheme, "Switch Theme");
+    register(Action::NextTab, "Next Tab");
+    register(Action::PreviousTab, "Previous Tab");
+    register(Action::FocusSearch, "Focus Search");
+    register(Action::SelectNextError, "Select Next Error");
+    register(Action::SelectPreviousError, "Select Previous Error");
+    register(Action::SelectAllLogs, "Select All Logs");
+    register(Action::ClearLogSelection, "Clear Log Selection");
+    register(Action::ScrollConsoleToBottom, "Scroll Console to Bottom");
+
+    for log_level in LogLevel::iter().filter(|log_level| *log_level != LogLevel::None) {
+        register(
+            Action::SetLogLevelFilter(log_level),
+            &format!("Set Log Level Filter: {log_level}"),
+        );
+    }
+
+    registered_actions
+}
+
+// Queued in the egui context so that any tab or view can dispatch one,
+// they are run by the app once the frame is rendered
+#[derive(Clone, Default)]
+struct DispatchedActions {
+    actions: Vec<Action>,
+}
+
+fn dispatched_actions_id() -> Id {
+    Id::new("dispatched_actions")
+}
+
+pub fn dispatch(ctx: &Context, action: Action) {
+    ctx.data_mut(|data| {
+        data.get_temp_mut_or_default::<DispatchedActions>(dispatched_actions_id())
+            .actions
+            .push(action);
+    });
+
+    ctx.request_repaint();
+}
+
+fn take_dispatched(ctx: &Context) -> Vec<Action> {
+    ctx.data_mut(|data| {
+        std::mem::take(
+            &mut data
+                .get_temp_mut_or_default::<DispatchedActions>(dispatched_actions_id())
+                .actions,
+        )
+    })
+}
+
+impl BepInExGUI {
+    // Text fields keep their own shortcuts (select all, escape...) while they have the focus
+    pub(crate) fn update_keymap(&mut self, ctx: &Context) {
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+
+        for key_command in KeyCommand::iter() {
+            let Some(key_binding) = self.config.keymap.key_binding(key_command) else {
+                continue;
+            };
+
+            if ctx.input_mut(|i| i.consume_key(key_binding.modifiers(), key_binding.key)) {
+                dispatch(ctx, key_command.into());
+            }
+        }
+    }
+
+    pub(crate) fn update_actions(&mut self, ctx: &Context) {
+        for action in take_dispatched(ctx) {
+            self.run_action(ctx, action);
+        }
+    }
+
+    fn run_action(&mut self, ctx: &Context, action: Action) {
+        let data = &self.app_launch_config;
+
+        match action {
+            Action::SwitchTab(tab_index) => {
+                if tab_index < self.tabs.len() {
+                    self.config.selected_tab_index = tab_index;
+                }
+            }
+            Action::ToggleLogAutoScroll => {
+                self.config.log_auto_scroll_to_bottom = !self.config.log_auto_scroll_to_bottom;
+            }
+            Action::SetLogLevelFilter(log_level) => self.config.log_level_filter = log_level,
+            Action::KillGame => self.kill_game(),
+            Action::RestartGame => self.restart_game(ctx),
+            Action::AttachToGame => {
+                self.game_picker.get_or_insert_with(GamePicker::default);
+            }
+            Action::OpenGameFolder => {
+                file_explorer_utils::open_path_in_explorer(data.game_folder_full_path());
+            }
+            Action::CopyLogFile => {
+                bepinex_log::file::open_file_explorer_to_file_and_zip_it_if_needed(
+                    data.bepinex_log_output_file_full_path(),
+                    "zipped_log.zip",
+                );
+            }
+            Action::OpenModdingDiscord => {
+                thunderstore::api::open_modding_discord(data.target_process_id());
+            }
+            Action::SwitchTheme => {
+                self.config.dark_mode = !self.config.dark_mode;
+                self.config.theme_just_changed = true;
+            }
+            Action::NextTab => {
+                self.config.selected_tab_index =
+                    (self.config.selected_tab_index + 1) % self.tabs.len();
+            }
+            Action::PreviousTab => {
+                self.config.selected_tab_index =
+                    (self.config.selected_tab_index + self.tabs.len() - 1) % self.tabs.len();
+            }
+            // only the search field of the tab on screen
+            Action::FocusSearch => {
+                self.tabs[self.config.selected_tab_index].handle_action(
+                    data,
+                    &mut self.config,
+                    ctx,
+                    action,
+                );
+            }
+            // those need the state of a tab
+            Action::TogglePauseGame
+            | Action::ExportLogs
+            | Action::SelectNextError
+            | Action::SelectPreviousError
+            | Action::SelectAllLogs
+            | Action::ClearLogSelection
+            | Action::ScrollConsoleToBottom
+            | Action::GameAttached => {
+                for tab in &mut self.tabs {
+                    tab.handle_action(data, &mut self.config, ctx, action);
+                }
+            }
+        }
+    }
+}
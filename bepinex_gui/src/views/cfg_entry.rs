@@ -0,0 +1,484 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use eframe::{
+    egui::{
+        self, Button, ComboBox, Context, DragValue, Event, Key, Label, Layout, RichText, Slider,
+        TextEdit, Ui, WidgetText,
+    },
+    emath::Align,
+    epaint::{Color32, FontId},
+};
+
+use crate::{
+    config::bepinex_cfg::{CfgFile, Entry, SaveError, Value},
+    views,
+};
+
+// Editing of BepInEx .cfg files and their entries, shared by the Configs and Settings tabs
+
+const KEYBOARD_SHORTCUT_SETTING_TYPE: &str = "KeyboardShortcut";
+const KEYBOARD_SHORTCUT_NONE: &str = "None";
+
+#[derive(Clone, PartialEq)]
+pub struct EntryId {
+    // Index of the cfg file in the list of the tab rendering it
+    pub cfg_file_index: usize,
+    pub section_name: String,
+    pub key: String,
+}
+
+pub struct EntryEdit {
+    pub entry_id: EntryId,
+    pub raw_value: String,
+}
+
+// Waits for the next key press after a KeyboardShortcut button got clicked
+#[derive(Default)]
+pub struct KeyBindingListener {
+    listening_entry_id: Option<EntryId>,
+}
+
+impl KeyBindingListener {
+    pub fn stop(&mut self) {
+        self.listening_entry_id = None;
+    }
+
+    pub fn update(&mut self, ctx: &Context) -> Option<EntryEdit> {
+        self.listening_entry_id.as_ref()?;
+
+        let (key, modifiers) = pressed_key(ctx)?;
+
+        // so that the keymap of the GUI doesn't react to it
+        ctx.input_mut(|i| i.consume_key(modifiers, key));
+
+        let key_binding = make_keyboard_shortcut_string(key, modifiers);
+
+        let entry_id = self.listening_entry_id.take()?;
+
+        Some(EntryEdit {
+            entry_id,
+            raw_value: key_binding,
+        })
+    }
+}
+
+// Time before writing a file that failed to save again
+const SAVE_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+pub enum SaveMode {
+    // The changes made on disk since the file was read are left to the user, see has_save_conflict
+    DetectConflicts,
+    // For files the GUI only touches a few entries of
+    MergeChangesOnDisk,
+}
+
+pub struct EditedCfgFile {
+    pub full_path: PathBuf,
+    pub file_name: String,
+    pub cfg_file: CfgFile,
+    // Written to disk once the user is done interacting with the widgets
+    pub is_dirty: bool,
+    // The file changed on disk while it had unsaved changes, the user picks what to do with them
+    pub has_save_conflict: bool,
+    // Stays dirty when writing it failed, it is tried again after SAVE_RETRY_DELAY
+    last_failed_save_time: Option<Instant>,
+}
+
+impl EditedCfgFile {
+    pub fn read(full_path: PathBuf) -> io::Result<Self> {
+        Ok(Self {
+            file_name: file_name(&full_path),
+            cfg_file: CfgFile::read(&full_path)?,
+            full_path,
+            is_dirty: false,
+            has_save_conflict: false,
+            last_failed_save_time: None,
+        })
+    }
+
+    pub fn apply_edit(&mut self, edit: &EntryEdit) {
+        let entry_id = &edit.entry_id;
+
+        if self
+            .cfg_file
+            .set_raw(&entry_id.section_name, &entry_id.key, &edit.raw_value)
+        {
+            self.is_dirty = true;
+        }
+    }
+
+    // Returns true when the file was written
+    pub fn save_if_dirty(&mut self, ctx: &Context, save_mode: &SaveMode) -> bool {
+        if !self.is_dirty
+            || self
+                .last_failed_save_time
+                .is_some_and(|last_failed_save_time| {
+                    last_failed_save_time.elapsed() < SAVE_RETRY_DELAY
+                })
+        {
+            return false;
+        }
+
+        let result = match save_mode {
+            SaveMode::DetectConflicts => self.cfg_file.save(&self.full_path),
+            SaveMode::MergeChangesOnDisk => self
+                .cfg_file
+                .save_merging_changes_on_disk(&self.full_path)
+                .map_err(SaveError::Io),
+        };
+
+        match result {
+            Ok(()) => {
+                self.is_dirty = false;
+                self.last_failed_save_time = None;
+                true
+            }
+            Err(SaveError::ChangedOnDisk) => {
+                self.is_dirty = false;
+                self.has_save_conflict = true;
+                false
+            }
+            Err(SaveError::Io(err)) => {
+                tracing::error!("Failed saving {}: {}", self.full_path.display(), err);
+
+                // only once, not on every retry
+                if self.last_failed_save_time.is_none() {
+                    views::notifications::push(
+                        ctx,
+                        format!("Failed saving {}: {err}", self.file_name),
+                    );
+                }
+                self.last_failed_save_time = Some(Instant::now());
+                false
+            }
+        }
+    }
+}
+
+pub fn file_name(full_path: &Path) -> String {
+    full_path
+        .file_name()
+        .map(|file_name| file_name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+// Sliders and text fields change on every frame while they are used,
+// only write once the user let go of them
+pub fn can_save(ctx: &Context) -> bool {
+    !ctx.is_using_pointer() && !ctx.wants_keyboard_input()
+}
+
+#[derive(Default)]
+pub struct SearchField {
+    text: String,
+    text_lowercase: String,
+    should_focus: bool,
+}
+
+impl SearchField {
+    pub fn text_lowercase(&self) -> &str {
+        &self.text_lowercase
+    }
+
+    pub fn focus(&mut self) {
+        self.should_focus = true;
+    }
+
+    pub fn show(&mut self, ui: &mut Ui, text_edit: impl FnOnce(&mut String) -> TextEdit<'_>) {
+        ui.label(RichText::new("Search: ").font(FontId::proportional(20.0)));
+
+        let search_input = ui.add(text_edit(&mut self.text));
+
+        if self.should_focus {
+            search_input.request_focus();
+            self.should_focus = false;
+        }
+
+        if search_input.changed() {
+            self.text_lowercase = self.text.to_lowercase();
+        }
+    }
+}
+
+pub fn matches_search(search_text_lowercase: &str, entry: &Entry) -> bool {
+    search_text_lowercase.is_empty()
+        || entry.key().to_lowercase().contains(search_text_lowercase)
+        || entry
+            .description()
+            .to_lowercase()
+            .contains(search_text_lowercase)
+}
+
+pub fn pressed_key(ctx: &Context) -> Option<(Key, egui::Modifiers)> {
+    ctx.input(|i| {
+        i.events.iter().find_map(|event| match event {
+            Event::Key {
+                key,
+                pressed: true,
+                modifiers,
+                ..
+            } => Some((*key, *modifiers)),
+            _ => None,
+        })
+    })
+}
+
+// Returns the new raw value of the entry if the user changed it
+pub fn render(
+    ui: &mut Ui,
+    cfg_file_index: usize,
+    section_name: &str,
+    entry: &Entry,
+    key_binding_listener: &mut KeyBindingListener,
+) -> Option<EntryEdit> {
+    ui.push_id((cfg_file_index, section_name, entry.key()), |ui| {
+        let entry_id = EntryId {
+            cfg_file_index,
+            section_name: section_name.to_string(),
+            key: entry.key().to_string(),
+        };
+
+        render_entry(ui, entry, entry_id, key_binding_listener)
+    })
+    .inner
+}
+
+fn render_entry(
+    ui: &mut Ui,
+    entry: &Entry,
+    entry_id: EntryId,
+    key_binding_listener: &mut KeyBindingListener,
+) -> Option<EntryEdit> {
+    let mut new_raw_value = None;
+
+    ui.horizontal(|ui| {
+        let key_label = ui.add(Label::new(RichText::new(entry.key()).strong()).wrap(false));
+        if let Some(setting_type) = entry.setting_type() {
+            key_label.on_hover_text(format!("Setting type: {setting_type}"));
+        }
+
+        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+            if let Some(default_value) = entry.default_value() {
+                if ui
+                    .add_enabled(entry.raw_value() != default_value, Button::new("⟲"))
+                    .on_hover_text(format!("Reset to default: {default_value}"))
+                    .clicked()
+                {
+                    new_raw_value = Some(default_value.to_string());
+                }
+            }
+
+            if let Some(value) =
+                render_value_widget(ui, entry, entry_id.clone(), key_binding_listener)
+            {
+                new_raw_value = Some(value);
+            }
+        });
+    });
+
+    if let Some(validation_error) = entry.validation_error() {
+        ui.label(
+            RichText::new(format!(
+                "Invalid value, BepInEx will use the default one instead: {validation_error}"
+            ))
+            .color(Color32::RED),
+        );
+    }
+
+    if !entry.description().is_empty() {
+        ui.add(
+            Label::new(
+                RichText::new(entry.description()).color(ui.style().visuals.weak_text_color()),
+            )
+            .wrap(true),
+        );
+    }
+
+    if let Some(default_value) = entry.default_value() {
+        ui.label(
+            RichText::new(format!("Default: {default_value}"))
+                .small()
+                .color(ui.style().visuals.weak_text_color()),
+        );
+    }
+
+    ui.add_space(6.);
+
+    new_raw_value.map(|raw_value| EntryEdit {
+        entry_id,
+        raw_value,
+    })
+}
+
+fn render_value_widget(
+    ui: &mut Ui,
+    entry: &Entry,
+    entry_id: EntryId,
+    key_binding_listener: &mut KeyBindingListener,
+) -> Option<String> {
+    let new_value = match entry.value() {
+        Value::Bool(mut value) => ui
+            .checkbox(&mut value, "")
+            .changed()
+            .then_some(Value::Bool(value)),
+        Value::Integer(mut value) => {
+            let range = entry
+                .acceptable_value_range()
+                .and_then(|(min, max)| Some(min.parse::<i64>().ok()?..=max.parse::<i64>().ok()?));
+
+            let response = match range {
+                Some(range) => ui.add(Slider::new(&mut value, range)),
+                None => match entry.integer_type_range() {
+                    Some(type_range) => ui.add(DragValue::new(&mut value).clamp_range(type_range)),
+                    None => ui.add(DragValue::new(&mut value)),
+                },
+            };
+
+            response.changed().then_some(Value::Integer(value))
+        }
+        Value::Float(mut value) => {
+            let range = entry
+                .acceptable_value_range()
+                .and_then(|(min, max)| Some(min.parse::<f64>().ok()?..=max.parse::<f64>().ok()?));
+
+            let response = match range {
+                Some(range) => ui.add(Slider::new(&mut value, range)),
+                None => ui.add(DragValue::new(&mut value).speed(0.1)),
+            };
+
+            response.changed().then_some(Value::Float(value))
+        }
+        Value::Enum(value) => {
+            let mut selected_value = value.clone();
+
+            ComboBox::from_id_source("enum")
+                .selected_text(&value)
+                .show_ui(ui, |ui| {
+                    for acceptable_value in entry.acceptable_values() {
+                        ui.selectable_value(
+                            &mut selected_value,
+                            acceptable_value.clone(),
+                            acceptable_value,
+                        );
+                    }
+                });
+
+            (selected_value != value).then_some(Value::Enum(selected_value))
+        }
+        Value::Flags(flags) => {
+            let mut new_flags = None;
+
+            ui.menu_button(flags.join(", "), |ui| {
+                for acceptable_value in entry.acceptable_values() {
+                    let mut is_set = flags.contains(acceptable_value);
+
+                    if ui.checkbox(&mut is_set, acceptable_value).changed() {
+                        let flags = entry
+                            .acceptable_values()
+                            .iter()
+                            .filter(|flag| {
+                                if *flag == acceptable_value {
+                                    is_set
+                                } else {
+                                    flags.contains(flag)
+                                }
+                            })
+                            .cloned()
+                            .collect();
+
+                        new_flags = Some(Value::Flags(flags));
+                    }
+                }
+            });
+
+            new_flags
+        }
+        Value::String(mut value) => ui
+            .add(TextEdit::singleline(&mut value).desired_width(300.))
+            .changed()
+            .then_some(Value::String(value)),
+        Value::Other(value) if entry.setting_type() == Some(KEYBOARD_SHORTCUT_SETTING_TYPE) => {
+            render_key_binding_widget(ui, &value, entry_id, key_binding_listener).map(Value::Other)
+        }
+        Value::Other(mut value) => ui
+            .add(TextEdit::singleline(&mut value).desired_width(300.))
+            .changed()
+            .then_some(Value::Other(value)),
+    };
+
+    new_value.map(|value| value.to_raw_string())
+}
+
+fn render_key_binding_widget(
+    ui: &mut Ui,
+    value: &str,
+    entry_id: EntryId,
+    key_binding_listener: &mut KeyBindingListener,
+) -> Option<String> {
+    let mut new_value = None;
+
+    if ui
+        .button("✖")
+        .on_hover_text("Clear the key binding")
+        .clicked()
+    {
+        new_value = Some(KEYBOARD_SHORTCUT_NONE.to_string());
+    }
+
+    let is_listening = key_binding_listener.listening_entry_id.as_ref() == Some(&entry_id);
+
+    let text = if is_listening {
+        WidgetText::from(RichText::new("Press a key...").color(Color32::YELLOW))
+    } else {
+        WidgetText::from(value)
+    };
+
+    if ui.button(text).clicked() {
+        key_binding_listener.listening_entry_id = if is_listening { None } else { Some(entry_id) };
+    }
+
+    new_value
+}
+
+// BepInEx KeyboardShortcut format: the main key first, then the modifiers, using unity KeyCode names
+fn make_keyboard_shortcut_string(key: Key, modifiers: egui::Modifiers) -> String {
+    let mut keys = vec![unity_key_code_name(key)];
+
+    if modifiers.ctrl || modifiers.mac_cmd {
+        keys.push("LeftControl".to_string());
+    }
+    if modifiers.shift {
+        keys.push("LeftShift".to_string());
+    }
+    if modifiers.alt {
+        keys.push("LeftAlt".to_string());
+    }
+
+    keys.join(" + ")
+}
+
+fn unity_key_code_name(key: Key) -> String {
+    match key {
+        Key::ArrowDown => "DownArrow".to_string(),
+        Key::ArrowLeft => "LeftArrow".to_string(),
+        Key::ArrowRight => "RightArrow".to_string(),
+        Key::ArrowUp => "UpArrow".to_string(),
+        Key::Enter => "Return".to_string(),
+        Key::PlusEquals => "Equals".to_string(),
+        Key::Num0
+        | Key::Num1
+        | Key::Num2
+        | Key::Num3
+        | Key::Num4
+        | Key::Num5
+        | Key::Num6
+        | Key::Num7
+        | Key::Num8
+        | Key::Num9 => format!("Alpha{}", key.name()),
+        _ => key.name().to_string(),
+    }
+}
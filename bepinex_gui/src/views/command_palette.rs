@@ -0,0 +1,144 @@
+use eframe::{
+    egui::{Align2, Context, Key, Modifiers, RichText, ScrollArea, TextEdit, Window},
+    emath::Vec2,
+    epaint::FontId,
+};
+
+use crate::views::{
+    actions::{self, RegisteredAction},
+    utils,
+};
+
+const MAX_SHOWN_ACTION_COUNT: usize = 12;
+
+#[derive(Default)]
+pub struct CommandPalette {
+    is_open: bool,
+    search_text: String,
+    selected_index: usize,
+}
+
+impl CommandPalette {
+    pub const fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    pub fn update(&mut self, ctx: &Context, registered_actions: &[RegisteredAction]) {
+        if ctx.input_mut(|i| {
+            i.consume_key(Modifiers::COMMAND, Key::P)
+                || i.consume_key(Modifiers::COMMAND | Modifiers::SHIFT, Key::P)
+        }) {
+            self.is_open = !self.is_open;
+            self.search_text.clear();
+            self.selected_index = 0;
+        }
+
+        if !self.is_open {
+            return;
+        }
+
+        if ctx.input_mut(|i| i.consume_key(Modifiers::NONE, Key::Escape)) {
+            self.is_open = false;
+            return;
+        }
+
+        let matching_actions = self.matching_actions(registered_actions);
+
+        ctx.input_mut(|i| {
+            if i.consume_key(Modifiers::NONE, Key::ArrowDown) {
+                self.selected_index += 1;
+            }
+            if i.consume_key(Modifiers::NONE, Key::ArrowUp) {
+                self.selected_index = self.selected_index.saturating_sub(1);
+            }
+        });
+        self.selected_index = self
+            .selected_index
+            .min(matching_actions.len().saturating_sub(1));
+
+        let mut chosen_action = None;
+        if ctx.input_mut(|i| i.consume_key(Modifiers::NONE, Key::Enter)) {
+            chosen_action = matching_actions
+                .get(self.selected_index)
+                .map(|registered_action| registered_action.action);
+        }
+
+        Window::new("Command Palette")
+            .title_bar(false)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_TOP, Vec2::new(0., 60.))
+            .fixed_size(Vec2::new(500., 0.))
+            .show(ctx, |ui| {
+                let search_input = ui.add(
+                    TextEdit::singleline(&mut self.search_text)
+                        .desired_width(f32::INFINITY)
+                        .font(FontId::proportional(20.0))
+                        .hint_text("Type an action name..."),
+                );
+                search_input.request_focus();
+                if search_input.changed() {
+                    self.selected_index = 0;
+                }
+
+                ui.separator();
+
+                if matching_actions.is_empty() {
+                    ui.label("No matching action.");
+                    return;
+                }
+
+                ScrollArea::vertical().show(ui, |ui| {
+                    for (i, registered_action) in matching_actions.iter().enumerate() {
+                        let text = match &registered_action.shortcut {
+                            Some(shortcut) => format!("{}    ({shortcut})", registered_action.name),
+                            None => registered_action.name.clone(),
+                        };
+
+                        let label = ui.selectable_label(
+                            i == self.selected_index,
+                            RichText::new(text).font(FontId::proportional(18.0)),
+                        );
+
+                        if i == self.selected_index {
+                            label.scroll_to_me(None);
+                        }
+
+                        if label.clicked() {
+                            chosen_action = Some(registered_action.action);
+                        }
+                    }
+                });
+            });
+
+        if let Some(action) = chosen_action {
+            actions::dispatch(ctx, action);
+            self.is_open = false;
+        }
+    }
+
+    // Best matches first, in registry order when no text is typed
+    fn matching_actions<'a>(
+        &self,
+        registered_actions: &'a [RegisteredAction],
+    ) -> Vec<&'a RegisteredAction> {
+        let search_text_lowercase = self.search_text.trim().to_lowercase();
+
+        let mut matching_actions: Vec<(i32, &RegisteredAction)> = registered_actions
+            .iter()
+            .filter_map(|registered_action| {
+                utils::fuzzy::score(&search_text_lowercase, &registered_action.name)
+                    .map(|score| (score, registered_action))
+            })
+            .collect();
+
+        // stable, keeps the registry order between equal scores
+        matching_actions.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+        matching_actions
+            .into_iter()
+            .take(MAX_SHOWN_ACTION_COUNT)
+            .map(|(_, registered_action)| registered_action)
+            .collect()
+    }
+}
@@ -20,20 +20,3 @@ pub fn colored_button(
 
     ui.add_sized(button_size, btn).clicked()
 }
-
-pub fn checkbox(
-    bool_ref: &mut bool,
-    text: &str,
-    ui: &mut Ui,
-    button_size: Vec2,
-    font_size: f32,
-) -> bool {
-    ui.add_sized(
-        button_size,
-        Checkbox::new(
-            bool_ref,
-            RichText::new(text).font(FontId::proportional(font_size)),
-        ),
-    )
-    .clicked()
-}
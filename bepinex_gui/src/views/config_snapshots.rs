@@ -0,0 +1,202 @@
+use std::path::{Path, PathBuf};
+
+use eframe::{
+    egui::{ComboBox, Context, RichText, ScrollArea, Ui, Window},
+    emath::Vec2,
+    epaint::Color32,
+};
+
+use crate::{
+    config::snapshots::{self, EntryDiff, FileDiff, Snapshot},
+    views,
+};
+
+enum RestoreAction {
+    File {
+        file_name: String,
+    },
+    Entry {
+        file_name: String,
+        entry_diff: EntryDiff,
+    },
+}
+
+pub struct ConfigSnapshotsView {
+    pub is_open: bool,
+    config_folder_full_path: PathBuf,
+    snapshots: Vec<Snapshot>,
+    selected_index: usize,
+    file_diffs: Vec<FileDiff>,
+}
+
+impl ConfigSnapshotsView {
+    pub const fn new(config_folder_full_path: PathBuf) -> Self {
+        Self {
+            is_open: false,
+            config_folder_full_path,
+            snapshots: Vec::new(),
+            selected_index: 0,
+            file_diffs: Vec::new(),
+        }
+    }
+
+    pub fn open(&mut self, target_name: &str, modpack_name: Option<&str>) {
+        self.is_open = true;
+        self.snapshots = snapshots::find_snapshots(target_name, modpack_name);
+        self.selected_index = 0;
+        self.compute_diff();
+    }
+
+    pub fn show(&mut self, ctx: &Context) {
+        let mut is_open = self.is_open;
+        let mut restore_action = None;
+
+        Window::new("Config Snapshots")
+            .open(&mut is_open)
+            .default_size(Vec2::new(600., 450.))
+            .show(ctx, |ui| {
+                if self.snapshots.is_empty() {
+                    ui.label("No snapshot yet, the config folder is saved each time the game is launched.");
+                    return;
+                }
+
+                ui.horizontal(|ui| {
+                    let selected_index_before = self.selected_index;
+                    ComboBox::from_label("Compare the current configs with")
+                        .show_index(ui, &mut self.selected_index, self.snapshots.len(), |i| {
+                            views::utils::time::format_time_ago(self.snapshots[i].unix_timestamp)
+                        });
+                    if selected_index_before != self.selected_index {
+                        self.compute_diff();
+                    }
+
+                    if ui.button("Refresh").clicked() {
+                        self.compute_diff();
+                    }
+                });
+
+                ui.separator();
+
+                restore_action = render_file_diffs(ui, &self.file_diffs);
+            });
+
+        self.is_open = is_open;
+
+        if let Some(restore_action) = restore_action {
+            self.restore(ctx, restore_action);
+        }
+    }
+
+    fn selected_snapshot(&self) -> Option<&Snapshot> {
+        self.snapshots.get(self.selected_index)
+    }
+
+    fn compute_diff(&mut self) {
+        self.file_diffs = self
+            .selected_snapshot()
+            .map(|snapshot| snapshots::diff(snapshot, &self.config_folder_full_path))
+            .unwrap_or_default();
+    }
+
+    fn restore(&mut self, ctx: &Context, restore_action: RestoreAction) {
+        let Some(snapshot) = self.selected_snapshot() else {
+            return;
+        };
+
+        let config_folder_full_path: &Path = &self.config_folder_full_path;
+
+        let (result, restored_text) = match &restore_action {
+            RestoreAction::File { file_name } => (
+                snapshots::restore_file(snapshot, config_folder_full_path, file_name),
+                file_name.clone(),
+            ),
+            RestoreAction::Entry {
+                file_name,
+                entry_diff,
+            } => (
+                snapshots::restore_entry(config_folder_full_path, file_name, entry_diff),
+                format!("[{}] {}", entry_diff.section_name, entry_diff.key),
+            ),
+        };
+
+        match result {
+            Ok(()) => views::notifications::push(ctx, format!("Restored {restored_text}")),
+            Err(err) => {
+                tracing::error!("Failed restoring {}: {}", restored_text, err);
+                views::notifications::push(ctx, format!("Failed restoring {restored_text}: {err}"));
+            }
+        }
+
+        self.compute_diff();
+    }
+}
+
+fn render_file_diffs(ui: &mut Ui, file_diffs: &[FileDiff]) -> Option<RestoreAction> {
+    if file_diffs.is_empty() {
+        ui.label("No difference.");
+        return None;
+    }
+
+    let mut restore_action = None;
+
+    ScrollArea::vertical()
+        .auto_shrink([false; 2])
+        .show(ui, |ui| {
+            for file_diff in file_diffs {
+                ui.horizontal(|ui| {
+                    let (prefix, color) = match file_diff {
+                        FileDiff::Added { .. } => ("+", Color32::GREEN),
+                        FileDiff::Removed { .. } => ("-", Color32::RED),
+                        FileDiff::Changed { .. } => ("~", Color32::YELLOW),
+                    };
+
+                    ui.label(
+                        RichText::new(format!("{prefix} {}", file_diff.file_name()))
+                            .strong()
+                            .color(color),
+                    );
+
+                    // a file that didn't exist back then can't be restored
+                    if !matches!(file_diff, FileDiff::Added { .. })
+                        && ui
+                            .button("Restore File")
+                            .on_hover_text("Replace the whole file with the snapshot version")
+                            .clicked()
+                    {
+                        restore_action = Some(RestoreAction::File {
+                            file_name: file_diff.file_name().to_string(),
+                        });
+                    }
+                });
+
+                if let FileDiff::Changed { file_name, entries } = file_diff {
+                    for entry_diff in entries {
+                        ui.horizontal(|ui| {
+                            ui.add_space(20.);
+                            ui.label(format!(
+                                "[{}] {}: {} -> {}",
+                                entry_diff.section_name,
+                                entry_diff.key,
+                                entry_diff.snapshot_value.as_deref().unwrap_or("(none)"),
+                                entry_diff.current_value.as_deref().unwrap_or("(none)"),
+                            ));
+
+                            if entry_diff.snapshot_value.is_some()
+                                && entry_diff.current_value.is_some()
+                                && ui.small_button("Restore").clicked()
+                            {
+                                restore_action = Some(RestoreAction::Entry {
+                                    file_name: file_name.clone(),
+                                    entry_diff: entry_diff.clone(),
+                                });
+                            }
+                        });
+                    }
+                }
+
+                ui.add_space(4.);
+            }
+        });
+
+    restore_action
+}
@@ -0,0 +1,164 @@
+use std::{io, path::PathBuf};
+
+use crossbeam_channel::Receiver;
+use eframe::{
+    egui::{Align2, Button, Context, Id, RichText, ScrollArea, Window},
+    emath::Vec2,
+    epaint::{Color32, FontId},
+};
+
+use crate::{
+    backend::file_explorer_utils,
+    config::launch::AppLaunchConfig,
+    data::crash_report::{self, CrashReport},
+    views::{self, actions, actions::Action},
+};
+
+// Shown instead of closing the GUI when the game didn't exit cleanly,
+// it can be collapsed for reading the console logs
+pub struct CrashSummary {
+    crash_report: CrashReport,
+    bundle_receiver: Option<Receiver<io::Result<PathBuf>>>,
+}
+
+impl CrashSummary {
+    pub const fn new(crash_report: CrashReport) -> Self {
+        Self {
+            crash_report,
+            bundle_receiver: None,
+        }
+    }
+
+    // Returns true when the user wants to close the GUI
+    pub fn show(&mut self, ctx: &Context, data: &AppLaunchConfig) -> bool {
+        self.update_bundle_receiver(ctx);
+
+        let mut should_exit_app = false;
+
+        Window::new(RichText::new("💥 The game crashed").font(FontId::proportional(22.0)))
+            .id(Id::new("crash_summary"))
+            .anchor(Align2::CENTER_CENTER, Vec2::ZERO)
+            .default_width(700.)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new(self.crash_report.summary())
+                        .font(FontId::proportional(18.0))
+                        .color(Color32::RED),
+                );
+
+                ui.add_space(6.);
+
+                if let Some(player_log_full_path) = &self.crash_report.player_log_full_path {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("Player.log: {}", player_log_full_path.display()));
+                        if ui.small_button("Show").clicked() {
+                            file_explorer_utils::highlight_path_in_explorer(player_log_full_path);
+                        }
+                    });
+                }
+                for crash_dump_full_path in &self.crash_report.crash_dump_full_paths {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("Crash dump: {}", crash_dump_full_path.display()));
+                        if ui.small_button("Show").clicked() {
+                            file_explorer_utils::highlight_path_in_explorer(crash_dump_full_path);
+                        }
+                    });
+                }
+
+                ui.add_space(6.);
+
+                ui.label(
+                    RichText::new(format!("Last {} logs", self.crash_report.last_logs.len()))
+                        .font(FontId::proportional(18.0)),
+                );
+                ScrollArea::vertical()
+                    .max_height(250.)
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        for log in &self.crash_report.last_logs {
+                            ui.label(RichText::new(log).monospace());
+                        }
+                    });
+
+                ui.add_space(10.);
+
+                ui.horizontal(|ui| {
+                    let is_building_bundle = self.bundle_receiver.is_some();
+                    let bundle_button_text = if is_building_bundle {
+                        "Building Report Bundle ⌛"
+                    } else {
+                        "Build Report Bundle"
+                    };
+                    if ui
+                        .add_enabled(
+                            !is_building_bundle,
+                            Button::new(
+                                RichText::new(bundle_button_text).font(FontId::proportional(18.0)),
+                            ),
+                        )
+                        .clicked()
+                    {
+                        self.build_bundle(data);
+                    }
+
+                    if ui
+                        .button(RichText::new("Restart Game").font(FontId::proportional(18.0)))
+                        .clicked()
+                    {
+                        actions::dispatch(ctx, Action::RestartGame);
+                    }
+
+                    if ui
+                        .button(RichText::new("Open Game Folder").font(FontId::proportional(18.0)))
+                        .clicked()
+                    {
+                        actions::dispatch(ctx, Action::OpenGameFolder);
+                    }
+
+                    if ui
+                        .button(RichText::new("Close GUI").font(FontId::proportional(18.0)))
+                        .clicked()
+                    {
+                        should_exit_app = true;
+                    }
+                });
+            });
+
+        should_exit_app
+    }
+
+    fn build_bundle(&mut self, data: &AppLaunchConfig) {
+        let (bundle_s, bundle_r) = crossbeam_channel::bounded(1);
+        crash_report::spawn_thread_build_bundle(
+            self.crash_report.clone(),
+            data.bepinex_log_output_file_full_path().clone(),
+            bundle_s,
+        );
+        self.bundle_receiver = Some(bundle_r);
+    }
+
+    fn update_bundle_receiver(&mut self, ctx: &Context) {
+        let Some(bundle_receiver) = &self.bundle_receiver else {
+            return;
+        };
+
+        let Ok(result) = bundle_receiver.try_recv() else {
+            return;
+        };
+
+        match result {
+            Ok(bundle_full_path) => {
+                file_explorer_utils::highlight_path_in_explorer(&bundle_full_path);
+            }
+            Err(err) => {
+                views::notifications::push(
+                    ctx,
+                    format!("Failed building the report bundle: {err}"),
+                );
+            }
+        }
+
+        self.bundle_receiver = None;
+    }
+}
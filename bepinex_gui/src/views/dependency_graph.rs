@@ -0,0 +1,287 @@
+use std::collections::HashSet;
+
+use eframe::{
+    egui::{Context, Sense, Ui, Window},
+    emath::{Align2, Pos2, Rect, Vec2},
+    epaint::{Color32, FontId, Stroke},
+};
+
+use crate::data::dependency_graph::{DependencyGraph, EdgeKind, NodeStatus};
+
+const NODE_RADIUS: f32 = 6.;
+
+// Simple force directed layout, applied a bit every frame so that the graph settles smoothly
+const REPULSION: f32 = 4000.;
+const SPRING_LENGTH: f32 = 120.;
+const SPRING_STRENGTH: f32 = 0.02;
+const CENTER_GRAVITY: f32 = 0.005;
+const MAX_NODE_MOVE_PER_FRAME: f32 = 10.;
+
+pub struct DependencyGraphView {
+    pub is_open: bool,
+    show_all_nodes: bool,
+    // relative to the center of the canvas
+    node_positions: Vec<Vec2>,
+    pan: Vec2,
+    dragged_node: Option<usize>,
+    selected_node: Option<usize>,
+}
+
+impl DependencyGraphView {
+    pub const fn new() -> Self {
+        Self {
+            is_open: false,
+            show_all_nodes: false,
+            node_positions: Vec::new(),
+            pan: Vec2::ZERO,
+            dragged_node: None,
+            selected_node: None,
+        }
+    }
+
+    pub fn show(&mut self, ctx: &Context, graph: &DependencyGraph) {
+        let mut is_open = self.is_open;
+
+        Window::new("Dependency Graph")
+            .open(&mut is_open)
+            .default_size(Vec2::new(700., 450.))
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.checkbox(
+                        &mut self.show_all_nodes,
+                        "Show plugins without dependencies",
+                    );
+                    ui.label(
+                        "Drag nodes or the background to move them, click a node to highlight it.",
+                    );
+                });
+
+                self.render_canvas(ui, graph);
+            });
+
+        self.is_open = is_open;
+    }
+
+    fn render_canvas(&mut self, ui: &mut Ui, graph: &DependencyGraph) {
+        self.add_new_node_positions(graph.nodes().len());
+
+        let visible_nodes = self.visible_nodes(graph);
+
+        self.step_layout(graph, &visible_nodes);
+
+        let (canvas_rect, canvas_response) =
+            ui.allocate_exact_size(ui.available_size(), Sense::click_and_drag());
+        let painter = ui.painter_at(canvas_rect);
+        painter.rect_filled(canvas_rect, 0., ui.style().visuals.extreme_bg_color);
+
+        let hovered_node = ui.input(|i| i.pointer.hover_pos()).and_then(|pointer_pos| {
+            let origin = canvas_rect.center() + self.pan;
+            visible_nodes.iter().copied().find(|i| {
+                (origin + self.node_positions[*i]).distance(pointer_pos) <= NODE_RADIUS * 2.
+            })
+        });
+
+        self.handle_pointer(ui, &canvas_response, hovered_node);
+
+        let origin = canvas_rect.center() + self.pan;
+        let node_screen_pos = |i: usize| origin + self.node_positions[i];
+
+        let highlighted_node = self.dragged_node.or(self.selected_node).or(hovered_node);
+        let text_color = ui.style().visuals.text_color();
+
+        for edge in graph.edges() {
+            if !visible_nodes.contains(&edge.from) || !visible_nodes.contains(&edge.to) {
+                continue;
+            }
+
+            let is_highlighted = highlighted_node.is_some_and(|i| i == edge.from || i == edge.to);
+
+            let color = match edge.kind {
+                EdgeKind::IncompatibleWith => Color32::RED,
+                EdgeKind::Requires { .. } if is_highlighted => text_color,
+                EdgeKind::Requires { .. } => Color32::GRAY,
+            };
+
+            let from = node_screen_pos(edge.from);
+            let to = node_screen_pos(edge.to);
+            painter.arrow(
+                from,
+                (to - from) - (to - from).normalized() * NODE_RADIUS,
+                Stroke::new(if is_highlighted { 2. } else { 1. }, color),
+            );
+
+            if is_highlighted {
+                if let EdgeKind::Requires {
+                    version_requirement: Some(version_requirement),
+                } = &edge.kind
+                {
+                    painter.text(
+                        from + (to - from) * 0.5,
+                        Align2::CENTER_CENTER,
+                        version_requirement,
+                        FontId::proportional(12.),
+                        text_color,
+                    );
+                }
+            }
+        }
+
+        for &i in &visible_nodes {
+            let node = &graph.nodes()[i];
+            let pos = node_screen_pos(i);
+
+            painter.circle_filled(pos, NODE_RADIUS, node_status_color(node.status));
+
+            if highlighted_node == Some(i) {
+                painter.circle_stroke(pos, NODE_RADIUS + 2., Stroke::new(2., text_color));
+            }
+
+            painter.text(
+                pos + Vec2::new(0., NODE_RADIUS + 2.),
+                Align2::CENTER_TOP,
+                if node.version.is_empty() {
+                    node.name.clone()
+                } else {
+                    format!("{} {}", node.name, node.version)
+                },
+                FontId::proportional(13.),
+                text_color,
+            );
+
+            if highlighted_node == Some(i) && node.id != node.name {
+                painter.text(
+                    pos + Vec2::new(0., NODE_RADIUS + 18.),
+                    Align2::CENTER_TOP,
+                    &node.id,
+                    FontId::proportional(11.),
+                    ui.style().visuals.weak_text_color(),
+                );
+            }
+        }
+
+        render_legend(&painter, canvas_rect, text_color);
+    }
+
+    fn handle_pointer(
+        &mut self,
+        ui: &Ui,
+        canvas_response: &eframe::egui::Response,
+        hovered_node: Option<usize>,
+    ) {
+        if canvas_response.drag_started() {
+            self.dragged_node = hovered_node;
+        }
+
+        if canvas_response.dragged() {
+            let delta = ui.input(|i| i.pointer.delta());
+            match self.dragged_node {
+                Some(i) => self.node_positions[i] += delta,
+                None => self.pan += delta,
+            }
+        }
+
+        if canvas_response.drag_released() {
+            self.dragged_node = None;
+        }
+
+        if canvas_response.clicked() {
+            self.selected_node = if hovered_node == self.selected_node {
+                None
+            } else {
+                hovered_node
+            };
+        }
+    }
+
+    fn add_new_node_positions(&mut self, node_count: usize) {
+        // new nodes are spawned on a spiral so that they don't overlap
+        while self.node_positions.len() < node_count {
+            let i = self.node_positions.len() as f32;
+            let angle = i * 2.4;
+            let distance = 20. * i.sqrt();
+            self.node_positions
+                .push(Vec2::new(angle.cos(), angle.sin()) * distance);
+        }
+    }
+
+    fn visible_nodes(&self, graph: &DependencyGraph) -> Vec<usize> {
+        if self.show_all_nodes {
+            return (0..graph.nodes().len()).collect();
+        }
+
+        let nodes_with_edges: HashSet<usize> = graph
+            .edges()
+            .iter()
+            .flat_map(|edge| [edge.from, edge.to])
+            .collect();
+
+        let mut visible_nodes: Vec<usize> = nodes_with_edges.into_iter().collect();
+        visible_nodes.sort_unstable();
+
+        visible_nodes
+    }
+
+    fn step_layout(&mut self, graph: &DependencyGraph, visible_nodes: &[usize]) {
+        let mut forces = vec![Vec2::ZERO; self.node_positions.len()];
+
+        for (a_index, &a) in visible_nodes.iter().enumerate() {
+            for &b in &visible_nodes[a_index + 1..] {
+                let delta = self.node_positions[a] - self.node_positions[b];
+                let distance_squared = delta.length_sq().max(1.);
+                let force = delta.normalized() * (REPULSION / distance_squared);
+                forces[a] += force;
+                forces[b] -= force;
+            }
+        }
+
+        for edge in graph.edges() {
+            let delta = self.node_positions[edge.to] - self.node_positions[edge.from];
+            let force = delta.normalized() * (delta.length() - SPRING_LENGTH) * SPRING_STRENGTH;
+            forces[edge.from] += force;
+            forces[edge.to] -= force;
+        }
+
+        for &i in visible_nodes {
+            if self.dragged_node == Some(i) {
+                continue;
+            }
+
+            let force = forces[i] - self.node_positions[i] * CENTER_GRAVITY;
+            self.node_positions[i] += force.clamp(
+                Vec2::splat(-MAX_NODE_MOVE_PER_FRAME),
+                Vec2::splat(MAX_NODE_MOVE_PER_FRAME),
+            );
+        }
+    }
+}
+
+const fn node_status_color(status: NodeStatus) -> Color32 {
+    match status {
+        NodeStatus::Loaded => Color32::from_rgb(80, 200, 120),
+        NodeStatus::FailedToLoad => Color32::RED,
+        NodeStatus::Missing => Color32::YELLOW,
+        NodeStatus::Installed => Color32::GRAY,
+    }
+}
+
+fn render_legend(painter: &eframe::egui::Painter, canvas_rect: Rect, text_color: Color32) {
+    let mut pos = canvas_rect.left_bottom() + Vec2::new(10., -10.);
+
+    for (status, text) in [
+        (NodeStatus::Installed, "Installed"),
+        (NodeStatus::Missing, "Missing"),
+        (NodeStatus::FailedToLoad, "Failed to load"),
+        (NodeStatus::Loaded, "Loaded"),
+    ] {
+        painter.circle_filled(pos, NODE_RADIUS, node_status_color(status));
+        painter.text(
+            Pos2::new(pos.x + NODE_RADIUS * 2., pos.y),
+            Align2::LEFT_CENTER,
+            text,
+            FontId::proportional(13.),
+            text_color,
+        );
+        pos.y -= NODE_RADIUS * 3.;
+    }
+}
@@ -0,0 +1,137 @@
+use eframe::{
+    egui::{Align2, Context, Id, RichText, ScrollArea, Ui, Window},
+    emath::Vec2,
+    epaint::{Color32, FontId},
+};
+
+use crate::{app::BepInExGUI, backend::file_explorer_utils};
+
+// Only the end of the last logs fits in the header
+const BANNER_LOG_COUNT: usize = 5;
+
+impl BepInExGUI {
+    // Stays after the dialog got dismissed, until the game responds again
+    pub(crate) fn render_game_hang_banner(&self, ui: &mut Ui) {
+        let Some(game_hang_watch) = &self.game_hang_watch else {
+            return;
+        };
+        let status = game_hang_watch.status();
+        let Some(hung_since) = status.hung_since else {
+            return;
+        };
+
+        ui.label(
+            RichText::new(format!(
+                "⏳ Game not responding for {} seconds",
+                hung_since.elapsed().as_secs()
+            ))
+            .font(FontId::proportional(18.0))
+            .color(Color32::YELLOW),
+        );
+
+        if !status.last_logs.is_empty() {
+            ui.label(
+                RichText::new("Last logs before the freeze, usually from the mod that got stuck:")
+                    .small()
+                    .color(ui.style().visuals.weak_text_color()),
+            );
+
+            ScrollArea::vertical()
+                .id_source("game_hang_banner_logs")
+                .max_height(100.)
+                .stick_to_bottom(true)
+                .show(ui, |ui| {
+                    let first_log_index = status.last_logs.len().saturating_sub(BANNER_LOG_COUNT);
+                    for log in &status.last_logs[first_log_index..] {
+                        ui.label(RichText::new(log).monospace());
+                    }
+                });
+        }
+
+        ui.add_space(10.);
+    }
+
+    // Killing is left to the user, a game busy loading can also stop responding for a while
+    pub(crate) fn update_game_hang_dialog(&mut self, ctx: &Context) {
+        if self.is_game_hang_dialog_dismissed || self.game_shutdown.is_some() {
+            return;
+        }
+
+        let Some(game_hang_watch) = &self.game_hang_watch else {
+            return;
+        };
+        let Some(hung_since) = game_hang_watch.status().hung_since else {
+            return;
+        };
+        let report_folder_full_path = game_hang_watch.status().report_folder_full_path.clone();
+
+        let mut should_kill_game = false;
+
+        Window::new(
+            RichText::new("⏳ The game is not responding").font(FontId::proportional(22.0)),
+        )
+        .id(Id::new("game_hang_dialog"))
+        .anchor(Align2::CENTER_CENTER, Vec2::ZERO)
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.label(
+                RichText::new(format!(
+                    "No answer from the game for {}s.",
+                    hung_since.elapsed().as_secs()
+                ))
+                .font(FontId::proportional(18.0))
+                .color(Color32::YELLOW),
+            );
+            ui.label("It may come back on its own, or be stuck for good because of a mod.");
+
+            match &report_folder_full_path {
+                Some(report_folder_full_path) => {
+                    ui.horizontal(|ui| {
+                        ui.label(format!(
+                            "Hang report: {}",
+                            report_folder_full_path.display()
+                        ));
+                        if ui.small_button("Show").clicked() {
+                            file_explorer_utils::highlight_path_in_explorer(
+                                report_folder_full_path,
+                            );
+                        }
+                    });
+                }
+                None => {
+                    ui.label("Writing the hang report ⌛");
+                }
+            }
+
+            ui.add_space(6.);
+
+            ui.horizontal(|ui| {
+                if ui
+                    .button(RichText::new("Kill the Game").font(FontId::proportional(18.0)))
+                    .clicked()
+                {
+                    should_kill_game = true;
+                }
+
+                if ui
+                    .button(RichText::new("Wait").font(FontId::proportional(18.0)))
+                    .clicked()
+                {
+                    self.is_game_hang_dialog_dismissed = true;
+                }
+            });
+        });
+
+        // for the timer
+        ctx.request_repaint();
+
+        // a hung game won't answer the request to quit anyway
+        if should_kill_game {
+            self.start_game_shutdown();
+            if let Some(game_shutdown) = &self.game_shutdown {
+                game_shutdown.kill_now();
+            }
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use std::time::{Duration, Instant};
+
+use crossbeam_channel::Receiver;
+use eframe::{
+    egui::{Align2, CentralPanel, Context, Id, RichText, ScrollArea, Ui, Window},
+    emath::Vec2,
+    epaint::{Color32, FontId},
+};
+
+use crate::{
+    app::BepInExGUI,
+    backend::{
+        game_discovery::{self, DiscoveredGame},
+        game_restart::GameAttach,
+    },
+    config::launch::AppLaunchConfig,
+    views,
+};
+
+const DISCOVERY_INTERVAL: Duration = Duration::from_secs(2);
+
+pub enum GamePickerResponse {
+    Picked(DiscoveredGame),
+    Closed,
+}
+
+// Lists the running games whose BepInEx.GUI.Loader still waits for a GUI to connect
+#[derive(Default)]
+pub struct GamePicker {
+    discovered_games: Vec<DiscoveredGame>,
+    discovered_games_receiver: Option<Receiver<Vec<DiscoveredGame>>>,
+    last_discovery_time: Option<Instant>,
+}
+
+impl GamePicker {
+    // Without an attached game the picker takes the whole window
+    pub fn show(
+        &mut self,
+        ctx: &Context,
+        attached_game: Option<&AppLaunchConfig>,
+    ) -> Option<GamePickerResponse> {
+        self.update_discovery(ctx);
+
+        let mut response = None;
+
+        if attached_game.is_some() {
+            let mut is_open = true;
+            Window::new(RichText::new("Attach to a Game").font(FontId::proportional(22.0)))
+                .id(Id::new("game_picker"))
+                .anchor(Align2::CENTER_CENTER, Vec2::ZERO)
+                .default_width(600.)
+                .collapsible(false)
+                .resizable(false)
+                .open(&mut is_open)
+                .show(ctx, |ui| {
+                    response = self.render_games(ui, attached_game);
+                });
+
+            if !is_open {
+                response = Some(GamePickerResponse::Closed);
+            }
+        } else {
+            CentralPanel::default().show(ctx, |ui| {
+                ui.heading(RichText::new("Attach to a Game").font(FontId::proportional(26.0)));
+                ui.add_space(10.);
+
+                response = self.render_games(ui, attached_game);
+            });
+        }
+
+        response
+    }
+
+    fn render_games(
+        &mut self,
+        ui: &mut Ui,
+        attached_game: Option<&AppLaunchConfig>,
+    ) -> Option<GamePickerResponse> {
+        ui.label(
+            "Games started with BepInEx.GUI enabled show up here, even when their GUI was closed.",
+        );
+        ui.add_space(6.);
+
+        if self.discovered_games.is_empty() {
+            ui.label(
+                RichText::new("Looking for running games ⌛").font(FontId::proportional(18.0)),
+            );
+            return None;
+        }
+
+        let mut picked_game_index = None;
+
+        ScrollArea::vertical().max_height(300.).show(ui, |ui| {
+            for (i, discovered_game) in self.discovered_games.iter().enumerate() {
+                let app_launch_config = &discovered_game.app_launch_config;
+
+                ui.horizontal(|ui| {
+                    let is_attached = attached_game.is_some_and(|attached_game| {
+                        attached_game.target_process_id() == app_launch_config.target_process_id()
+                    });
+
+                    if is_attached {
+                        ui.label(RichText::new("Attached").color(Color32::GREEN));
+                    } else if ui
+                        .button(RichText::new("Attach").font(FontId::proportional(18.0)))
+                        .clicked()
+                    {
+                        picked_game_index = Some(i);
+                    }
+
+                    ui.label(
+                        RichText::new(format!(
+                            "{} (pid {})",
+                            app_launch_config.target_name(),
+                            app_launch_config.target_process_id()
+                        ))
+                        .font(FontId::proportional(18.0)),
+                    );
+                    ui.label(
+                        app_launch_config
+                            .game_folder_full_path()
+                            .display()
+                            .to_string(),
+                    );
+                });
+            }
+        });
+
+        picked_game_index.map(|i| GamePickerResponse::Picked(self.discovered_games.swap_remove(i)))
+    }
+
+    fn update_discovery(&mut self, ctx: &Context) {
+        if let Some(discovered_games_receiver) = &self.discovered_games_receiver {
+            if let Ok(discovered_games) = discovered_games_receiver.try_recv() {
+                self.discovered_games = discovered_games;
+                self.discovered_games_receiver = None;
+            }
+        }
+
+        let is_discovery_due = match self.last_discovery_time {
+            Some(last_discovery_time) => last_discovery_time.elapsed() >= DISCOVERY_INTERVAL,
+            None => true,
+        };
+
+        if self.discovered_games_receiver.is_none() && is_discovery_due {
+            let (discovered_games_s, discovered_games_r) = crossbeam_channel::bounded(1);
+            game_discovery::spawn_thread_discover(discovered_games_s);
+            self.discovered_games_receiver = Some(discovered_games_r);
+            self.last_discovery_time = Some(Instant::now());
+        }
+
+        ctx.request_repaint_after(DISCOVERY_INTERVAL);
+    }
+}
+
+impl BepInExGUI {
+    pub(crate) fn update_game_picker(&mut self, ctx: &Context) {
+        let is_game_attached = self.is_game_attached();
+        let Some(game_picker) = &mut self.game_picker else {
+            return;
+        };
+
+        let attached_game = is_game_attached.then_some(&self.app_launch_config);
+
+        match game_picker.show(ctx, attached_game) {
+            Some(GamePickerResponse::Picked(discovered_game)) => {
+                self.game_picker = None;
+                self.pick_game(ctx, discovered_game);
+            }
+            Some(GamePickerResponse::Closed) => self.game_picker = None,
+            None => {}
+        }
+    }
+
+    fn pick_game(&mut self, ctx: &Context, discovered_game: DiscoveredGame) {
+        let app_launch_config = &discovered_game.app_launch_config;
+
+        if !self.is_game_attached() {
+            self.app_launch_config = discovered_game.app_launch_config;
+            self.init_game();
+            return;
+        }
+
+        // the tabs are tied to the folders of the game they were made for
+        if app_launch_config.game_folder_full_path()
+            != self.app_launch_config.game_folder_full_path()
+            || app_launch_config.bepinex_gui_csharp_cfg_full_path()
+                != self.app_launch_config.bepinex_gui_csharp_cfg_full_path()
+        {
+            if let Err(err) = discovered_game.launch_new_gui() {
+                views::notifications::push(
+                    ctx,
+                    format!("Failed opening a GUI for the game: {err}"),
+                );
+            }
+            return;
+        }
+
+        self.crash_summary = None;
+        self.attach_to_game(
+            ctx,
+            GameAttach {
+                target_process_id: app_launch_config.target_process_id(),
+                log_socket_port_receiver: app_launch_config.log_socket_port_receiver(),
+            },
+        );
+        views::notifications::push(ctx, "Attached to the game.");
+    }
+}
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::sync::atomic::Ordering;
 
 use eframe::{
     self,
@@ -6,16 +6,24 @@ use eframe::{
     emath::Vec2,
     epaint::FontId,
 };
-use sysinfo::Pid;
 
-use crate::{
-    app::BepInExGUI,
-    backend::{file_explorer_utils, thunderstore},
-    data::bepinex_log,
-};
+use crate::app::BepInExGUI;
+
+use self::actions::Action;
 
+pub mod actions;
+pub mod cfg_entry;
+pub mod command_palette;
 pub mod components;
+pub mod config_snapshots;
+pub mod crash_summary;
+pub mod dependency_graph;
 pub mod disclaimer;
+pub mod game_hang_dialog;
+pub mod game_picker;
+pub mod mod_list_diff;
+pub mod notifications;
+pub mod shutdown_dialog;
 pub mod tabs;
 pub mod utils;
 
@@ -31,6 +39,10 @@ impl BepInExGUI {
             self.config.theme_just_changed = false;
         }
 
+        for tab in &mut self.tabs {
+            tab.update_in_background(&self.app_launch_config, &mut self.config, ctx, frame);
+        }
+
         if self.config.first_time {
             self.show_first_time_disclaimer(ctx);
         } else {
@@ -39,7 +51,31 @@ impl BepInExGUI {
             let tab = &mut self.tabs[self.config.selected_tab_index];
 
             tab.update(&self.app_launch_config, &mut self.config, ctx, frame);
+
+            let tab_names: Vec<&str> = self.tabs.iter().map(|tab| tab.name()).collect();
+            self.command_palette
+                .update(ctx, &actions::registry(&tab_names, &self.config.keymap));
+
+            if !self.command_palette.is_open() {
+                self.update_keymap(ctx);
+            }
+        }
+
+        if let Some(crash_summary) = &mut self.crash_summary {
+            if crash_summary.show(ctx, &self.app_launch_config) {
+                self.should_exit_app.store(true, Ordering::Relaxed);
+            }
         }
+
+        self.update_game_picker(ctx);
+
+        self.update_shutdown_dialogs(ctx);
+
+        self.update_game_hang_dialog(ctx);
+
+        notifications::show(ctx);
+
+        self.update_actions(ctx);
     }
 
     fn show_first_time_disclaimer(&mut self, ctx: &Context) {
@@ -49,7 +85,7 @@ impl BepInExGUI {
     fn render_header(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
         TopBottomPanel::top("top_panel").show(ctx, |ui| {
             ui.horizontal(|ui| {
-                let mut button_size = ui.available_size() / 3.;
+                let mut button_size = ui.available_size() / self.tabs.len() as f32;
                 button_size.y += 25.;
 
                 ui.spacing_mut().item_spacing.x = 1.;
@@ -70,6 +106,8 @@ impl BepInExGUI {
 
             ui.add_space(10.);
 
+            self.render_game_hang_banner(ui);
+
             if !self.config.first_time_console_disclaimer {
                 self.tabs[self.config.selected_tab_index].update_top_panel(
                     &self.app_launch_config,
@@ -81,13 +119,7 @@ impl BepInExGUI {
         });
     }
 
-    pub fn render_useful_buttons_footer(
-        ui: &mut Ui,
-        _ctx: &Context,
-        game_folder_full_path: &PathBuf,
-        bepinex_log_output_file_full_path: &PathBuf,
-        target_process_id: Pid,
-    ) {
+    pub fn render_useful_buttons_footer(ui: &mut Ui, ctx: &Context) {
         ui.add_space(3.0);
 
         ui.horizontal(|ui| {
@@ -101,59 +133,52 @@ impl BepInExGUI {
             let placement_cursor = ui.cursor();
             ui.add_space(spacing * 0.5);
 
-            render_open_game_folder_button(ui, button_size, game_folder_full_path, FONT_SIZE);
+            render_action_button(
+                ui,
+                ctx,
+                "Open Game Folder",
+                Action::OpenGameFolder,
+                button_size,
+                FONT_SIZE,
+            );
 
             ui.set_cursor(placement_cursor);
             ui.add_space(spacing * 3.25);
 
-            render_copy_log_file_button(
+            render_action_button(
                 ui,
+                ctx,
+                "Copy Log File",
+                Action::CopyLogFile,
                 button_size,
-                bepinex_log_output_file_full_path,
                 FONT_SIZE,
             );
 
             ui.set_cursor(placement_cursor);
             ui.add_space(spacing * 5.85);
 
-            render_open_modding_discord_button(ui, button_size, target_process_id, FONT_SIZE);
+            render_action_button(
+                ui,
+                ctx,
+                "Modding Discord",
+                Action::OpenModdingDiscord,
+                button_size,
+                FONT_SIZE,
+            );
         });
         ui.add_space(25.);
     }
 }
 
-fn render_open_game_folder_button(
-    ui: &mut Ui,
-    button_size: Vec2,
-    game_folder_full_path: &PathBuf,
-    font_size: f32,
-) {
-    if components::button("Open Game Folder", ui, button_size, font_size) {
-        file_explorer_utils::open_path_in_explorer(game_folder_full_path);
-    }
-}
-
-fn render_copy_log_file_button(
-    ui: &mut Ui,
-    button_size: Vec2,
-    bepinex_log_output_file_full_path: &PathBuf,
-    font_size: f32,
-) {
-    if components::button("Copy Log File", ui, button_size, font_size) {
-        bepinex_log::file::open_file_explorer_to_file_and_zip_it_if_needed(
-            bepinex_log_output_file_full_path,
-            "zipped_log.zip",
-        );
-    }
-}
-
-fn render_open_modding_discord_button(
+fn render_action_button(
     ui: &mut Ui,
+    ctx: &Context,
+    text: &str,
+    action: Action,
     button_size: Vec2,
-    target_process_id: Pid,
     font_size: f32,
 ) {
-    if components::button("Modding Discord", ui, button_size, font_size) {
-        thunderstore::api::open_modding_discord(target_process_id);
+    if components::button(text, ui, button_size, font_size) {
+        actions::dispatch(ctx, action);
     }
 }
@@ -0,0 +1,143 @@
+use std::path::{Path, PathBuf};
+
+use eframe::{
+    egui::{ComboBox, Context, RichText, ScrollArea, Ui, Window},
+    emath::Vec2,
+    epaint::Color32,
+};
+
+use crate::{
+    data::mod_list::{self, ExportedMod, ModListDiff},
+    views,
+};
+
+pub struct ModListDiffView {
+    pub is_open: bool,
+    saved_mod_lists: Vec<PathBuf>,
+    selected_index: usize,
+    diff: Option<ModListDiff>,
+}
+
+impl ModListDiffView {
+    pub const fn new() -> Self {
+        Self {
+            is_open: false,
+            saved_mod_lists: Vec::new(),
+            selected_index: 0,
+            diff: None,
+        }
+    }
+
+    // The mod list saved during the current session is excluded, comparing it with itself is pointless
+    pub fn open(
+        &mut self,
+        target_name: &str,
+        current_session_mod_list: Option<&Path>,
+        current_mods: &[ExportedMod],
+    ) {
+        self.is_open = true;
+        self.saved_mod_lists = mod_list::find_saved(target_name);
+        self.saved_mod_lists
+            .retain(|saved_mod_list| Some(saved_mod_list.as_path()) != current_session_mod_list);
+        self.selected_index = 0;
+        self.compute_diff(current_mods);
+    }
+
+    pub fn show(&mut self, ctx: &Context, current_mods: &[ExportedMod]) {
+        let mut is_open = self.is_open;
+
+        Window::new("Compare Mod List")
+            .open(&mut is_open)
+            .default_size(Vec2::new(500., 400.))
+            .show(ctx, |ui| {
+                if self.saved_mod_lists.is_empty() {
+                    ui.label("No saved mod list yet, a list is saved each time the game finishes loading.");
+                    return;
+                }
+
+                let selected_index_before = self.selected_index;
+                ComboBox::from_label("Compare with")
+                    .show_index(
+                        ui,
+                        &mut self.selected_index,
+                        self.saved_mod_lists.len(),
+                        |i| saved_mod_list_display_name(&self.saved_mod_lists[i]),
+                    );
+                if selected_index_before != self.selected_index {
+                    self.compute_diff(current_mods);
+                }
+
+                ui.separator();
+
+                if let Some(diff) = &self.diff {
+                    render_diff(ui, diff);
+                }
+            });
+
+        self.is_open = is_open;
+    }
+
+    fn compute_diff(&mut self, current_mods: &[ExportedMod]) {
+        self.diff = self
+            .saved_mod_lists
+            .get(self.selected_index)
+            .and_then(|saved_mod_list| match mod_list::load(saved_mod_list) {
+                Ok(saved_mods) => Some(mod_list::diff(&saved_mods, current_mods)),
+                Err(err) => {
+                    tracing::error!("Failed loading saved mod list: {}", err);
+                    None
+                }
+            });
+    }
+}
+
+fn render_diff(ui: &mut Ui, diff: &ModListDiff) {
+    if diff.is_empty() {
+        ui.label("No difference.");
+        return;
+    }
+
+    ScrollArea::vertical()
+        .auto_shrink([false; 2])
+        .show(ui, |ui| {
+            for mod_ in &diff.added {
+                ui.label(
+                    RichText::new(format!("+ {} {}", mod_.diff_name(), mod_.diff_version()))
+                        .color(Color32::GREEN),
+                );
+            }
+
+            for mod_ in &diff.removed {
+                ui.label(
+                    RichText::new(format!("- {} {}", mod_.diff_name(), mod_.diff_version()))
+                        .color(Color32::RED),
+                );
+            }
+
+            for (old_mod, new_mod) in &diff.version_changed {
+                ui.label(
+                    RichText::new(format!(
+                        "~ {} {} -> {}",
+                        new_mod.diff_name(),
+                        old_mod.diff_version(),
+                        new_mod.diff_version()
+                    ))
+                    .color(Color32::YELLOW),
+                );
+            }
+        });
+}
+
+// Saved mod lists are named after their unix timestamp
+fn saved_mod_list_display_name(saved_mod_list: &Path) -> String {
+    let file_stem = saved_mod_list
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let Ok(unix_timestamp) = file_stem.parse::<u64>() else {
+        return file_stem;
+    };
+
+    views::utils::time::format_time_ago(unix_timestamp)
+}
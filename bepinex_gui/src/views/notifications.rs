@@ -0,0 +1,64 @@
+use std::time::{Duration, Instant};
+
+use eframe::{
+    egui::{Align2, Area, Context, Frame, Id, Label, RichText},
+    emath::Vec2,
+};
+
+const NOTIFICATION_DURATION: Duration = Duration::from_secs(6);
+const MAX_NOTIFICATION_COUNT: usize = 5;
+
+// Short lived messages shown in the bottom right corner.
+// Stored in the egui context so that any tab or view can push one.
+#[derive(Clone, Default)]
+struct Notifications {
+    entries: Vec<(String, Instant)>,
+}
+
+fn notifications_id() -> Id {
+    Id::new("notifications")
+}
+
+pub fn push(ctx: &Context, text: impl Into<String>) {
+    let text = text.into();
+    tracing::info!("Notification: {}", text);
+
+    ctx.data_mut(|data| {
+        let notifications = data.get_temp_mut_or_default::<Notifications>(notifications_id());
+
+        notifications.entries.push((text, Instant::now()));
+        if notifications.entries.len() > MAX_NOTIFICATION_COUNT {
+            notifications.entries.remove(0);
+        }
+    });
+
+    ctx.request_repaint();
+}
+
+pub fn show(ctx: &Context) {
+    let entries = ctx.data_mut(|data| {
+        let notifications = data.get_temp_mut_or_default::<Notifications>(notifications_id());
+
+        notifications
+            .entries
+            .retain(|(_, time)| time.elapsed() < NOTIFICATION_DURATION);
+
+        notifications.entries.clone()
+    });
+
+    if entries.is_empty() {
+        return;
+    }
+
+    Area::new("notifications_area")
+        .anchor(Align2::RIGHT_BOTTOM, Vec2::new(-10., -10.))
+        .interactable(false)
+        .show(ctx, |ui| {
+            for (text, _) in entries {
+                Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.set_max_width(350.);
+                    ui.add(Label::new(RichText::new(text)).wrap(true));
+                });
+            }
+        });
+}
@@ -0,0 +1,126 @@
+use eframe::{
+    egui::{Align2, Context, Id, ProgressBar, RichText, Window},
+    emath::Vec2,
+    epaint::FontId,
+};
+
+use crate::{app::BepInExGUI, backend::game_shutdown::GameShutdown};
+
+pub enum ShutdownDialogResponse {
+    Confirmed,
+    Cancelled,
+}
+
+// Asks before closing the game, can be turned off from the dialog or the settings tab
+#[derive(Default)]
+pub struct ShutdownDialog {
+    is_dont_ask_again_checked: bool,
+}
+
+impl ShutdownDialog {
+    pub fn show(&mut self, ctx: &Context) -> Option<ShutdownDialogResponse> {
+        let mut response = None;
+
+        Window::new(RichText::new("Close the game?").font(FontId::proportional(22.0)))
+            .id(Id::new("shutdown_confirmation"))
+            .anchor(Align2::CENTER_CENTER, Vec2::ZERO)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label("The game will be asked to quit, and killed if it doesn't in time.");
+                ui.label("Progress that the game didn't save yet will be lost.");
+
+                ui.add_space(6.);
+
+                ui.checkbox(&mut self.is_dont_ask_again_checked, "Don't ask again");
+
+                ui.add_space(6.);
+
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(RichText::new("Close Game").font(FontId::proportional(18.0)))
+                        .clicked()
+                    {
+                        response = Some(ShutdownDialogResponse::Confirmed);
+                    }
+
+                    if ui
+                        .button(RichText::new("Cancel").font(FontId::proportional(18.0)))
+                        .clicked()
+                    {
+                        response = Some(ShutdownDialogResponse::Cancelled);
+                    }
+                });
+            });
+
+        response
+    }
+}
+
+fn show_progress(ctx: &Context, game_shutdown: &GameShutdown) {
+    Window::new(RichText::new("Closing the game").font(FontId::proportional(22.0)))
+        .id(Id::new("shutdown_progress"))
+        .anchor(Align2::CENTER_CENTER, Vec2::ZERO)
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            let elapsed = game_shutdown.elapsed().as_secs_f32();
+            let timeout = game_shutdown.timeout().as_secs_f32();
+
+            if game_shutdown.is_kill_requested() {
+                ui.label("Killing the game ⌛");
+            } else {
+                ui.label(format!(
+                    "Waiting for the game to quit, it will be killed in {:.0}s ⌛",
+                    (timeout - elapsed).max(0.)
+                ));
+            }
+
+            ui.add(
+                ProgressBar::new(if timeout > 0. { elapsed / timeout } else { 1. })
+                    .desired_width(400.),
+            );
+
+            ui.add_space(6.);
+
+            if ui
+                .add_enabled(
+                    !game_shutdown.is_kill_requested(),
+                    eframe::egui::Button::new(
+                        RichText::new("Kill Now").font(FontId::proportional(18.0)),
+                    ),
+                )
+                .clicked()
+            {
+                game_shutdown.kill_now();
+            }
+        });
+
+    ctx.request_repaint();
+}
+
+impl BepInExGUI {
+    pub(crate) fn update_shutdown_dialogs(&mut self, ctx: &Context) {
+        if let Some(game_shutdown) = &self.game_shutdown {
+            show_progress(ctx, game_shutdown);
+            return;
+        }
+
+        let Some(shutdown_dialog) = &mut self.shutdown_dialog else {
+            return;
+        };
+
+        match shutdown_dialog.show(ctx) {
+            Some(ShutdownDialogResponse::Confirmed) => {
+                if shutdown_dialog.is_dont_ask_again_checked {
+                    self.config.confirm_kill_game = false;
+                }
+                self.shutdown_dialog = None;
+
+                self.start_game_shutdown();
+            }
+            Some(ShutdownDialogResponse::Cancelled) => self.shutdown_dialog = None,
+            None => {}
+        }
+    }
+}
@@ -0,0 +1,452 @@
+use std::path::PathBuf;
+
+use crossbeam_channel::Receiver;
+use eframe::{
+    egui::{
+        CentralPanel, Context, Layout, RichText, ScrollArea, SidePanel, TextEdit, Ui, WidgetText,
+        Window,
+    },
+    emath::{Align, Align2, Vec2},
+    epaint::{Color32, FontId},
+};
+
+use crate::{
+    config::{
+        bepinex_cfg::{self, CfgFile, Entry},
+        launch::AppLaunchConfig,
+        Config,
+    },
+    views::{
+        self,
+        actions::Action,
+        cfg_entry::{self, EditedCfgFile, EntryEdit, KeyBindingListener, SaveMode, SearchField},
+        config_snapshots::ConfigSnapshotsView,
+    },
+};
+
+use super::Tab;
+
+pub struct ConfigsTab {
+    config_folder_full_path: PathBuf,
+    cfg_file_changed_receiver: Receiver<PathBuf>,
+    cfg_files: Vec<EditedCfgFile>,
+    selected_cfg_file_index: usize,
+    search_field: SearchField,
+    key_binding_listener: KeyBindingListener,
+    snapshots_view: ConfigSnapshotsView,
+}
+
+impl ConfigsTab {
+    pub fn new(
+        config_folder_full_path: PathBuf,
+        cfg_file_changed_receiver: Receiver<PathBuf>,
+    ) -> Self {
+        let mut configs_tab = Self {
+            snapshots_view: ConfigSnapshotsView::new(config_folder_full_path.clone()),
+            config_folder_full_path,
+            cfg_file_changed_receiver,
+            cfg_files: Vec::new(),
+            selected_cfg_file_index: 0,
+            search_field: SearchField::default(),
+            key_binding_listener: KeyBindingListener::default(),
+        };
+
+        configs_tab.reload();
+
+        configs_tab
+    }
+
+    fn reload(&mut self) {
+        self.cfg_files = bepinex_cfg::find_cfg_files(&self.config_folder_full_path)
+            .into_iter()
+            .filter_map(|full_path| match EditedCfgFile::read(full_path.clone()) {
+                Ok(cfg_file) => Some(cfg_file),
+                Err(err) => {
+                    tracing::error!("Failed reading {}: {}", full_path.display(), err);
+                    None
+                }
+            })
+            .collect();
+
+        self.selected_cfg_file_index = self
+            .selected_cfg_file_index
+            .min(self.cfg_files.len().saturating_sub(1));
+        self.key_binding_listener.stop();
+    }
+
+    fn update_cfg_file_changed_receiver(&mut self, ctx: &Context) {
+        while let Ok(changed_file_full_path) = self.cfg_file_changed_receiver.try_recv() {
+            let Some(cfg_file) = self
+                .cfg_files
+                .iter_mut()
+                .find(|cfg_file| cfg_file.full_path == changed_file_full_path)
+            else {
+                // plugins create their config file the first time they load
+                if !self.cfg_files.iter().any(|cfg_file| cfg_file.is_dirty) {
+                    self.reload();
+                }
+                continue;
+            };
+
+            // our own saves also end up here
+            if !cfg_file
+                .cfg_file
+                .has_changed_on_disk(&cfg_file.full_path)
+                .unwrap_or_default()
+            {
+                continue;
+            }
+
+            if cfg_file.cfg_file.has_unsaved_changes() {
+                cfg_file.has_save_conflict = true;
+                continue;
+            }
+
+            match CfgFile::read(&cfg_file.full_path) {
+                Ok(reloaded_cfg_file) => {
+                    cfg_file.cfg_file = reloaded_cfg_file;
+                    views::notifications::push(
+                        ctx,
+                        format!("{} was modified on disk and reloaded.", cfg_file.file_name),
+                    );
+                }
+                Err(err) => {
+                    tracing::error!("Failed reloading {}: {}", cfg_file.full_path.display(), err);
+                }
+            }
+        }
+    }
+
+    fn matches_search(&self, cfg_file: &EditedCfgFile, section_name: &str, entry: &Entry) -> bool {
+        let search_text_lowercase = self.search_field.text_lowercase();
+
+        cfg_entry::matches_search(search_text_lowercase, entry)
+            || [cfg_file.file_name.as_str(), section_name]
+                .iter()
+                .any(|text| text.to_lowercase().contains(search_text_lowercase))
+    }
+
+    fn match_count(&self, cfg_file: &EditedCfgFile) -> usize {
+        cfg_file
+            .cfg_file
+            .sections()
+            .iter()
+            .map(|section| {
+                section
+                    .entries()
+                    .iter()
+                    .filter(|entry| self.matches_search(cfg_file, section.name(), entry))
+                    .count()
+            })
+            .sum()
+    }
+
+    fn render_cfg_file_list(&mut self, ctx: &Context) {
+        SidePanel::left("configs_cfg_file_list")
+            .resizable(true)
+            .default_width(250.)
+            .show(ctx, |ui| {
+                ScrollArea::vertical()
+                    .auto_shrink([false; 2])
+                    .show(ui, |ui| {
+                        for (i, cfg_file) in self.cfg_files.iter().enumerate() {
+                            let file_name = if cfg_file.cfg_file.has_unsaved_changes() {
+                                format!("{} *", cfg_file.file_name)
+                            } else {
+                                cfg_file.file_name.clone()
+                            };
+
+                            let text = if self.search_field.text_lowercase().is_empty() {
+                                file_name
+                            } else {
+                                match self.match_count(cfg_file) {
+                                    0 => continue,
+                                    match_count => format!("{file_name} ({match_count})"),
+                                }
+                            };
+
+                            if ui
+                                .selectable_label(self.selected_cfg_file_index == i, text)
+                                .on_hover_text(cfg_file.full_path.display().to_string())
+                                .clicked()
+                            {
+                                self.selected_cfg_file_index = i;
+                            }
+                        }
+                    });
+            });
+    }
+
+    fn render(&mut self, ctx: &Context) -> Vec<EntryEdit> {
+        let mut edits = Vec::new();
+
+        CentralPanel::default().show(ctx, |ui| {
+            if self.cfg_files.is_empty() {
+                ui.vertical_centered_justified(|ui| {
+                    let text = "No config file found";
+                    let text_size =
+                        views::utils::egui::compute_text_size(ui, text, true, false, None);
+                    ui.add_space(ui.available_height() / 2. - text_size.y);
+                    ui.heading(text);
+                });
+                return;
+            }
+
+            ui.spacing_mut().scroll_bar_width = 16.;
+            ScrollArea::vertical()
+                .auto_shrink([false; 2])
+                .show(ui, |ui| {
+                    // when searching, the matching settings of every file are shown at once
+                    let cfg_file_indices: Vec<usize> =
+                        if self.search_field.text_lowercase().is_empty() {
+                            vec![self.selected_cfg_file_index]
+                        } else {
+                            (0..self.cfg_files.len()).collect()
+                        };
+
+                    for cfg_file_index in cfg_file_indices {
+                        self.render_cfg_file(ui, cfg_file_index, &mut edits);
+                    }
+                });
+        });
+
+        edits
+    }
+
+    fn render_cfg_file(&mut self, ui: &mut Ui, cfg_file_index: usize, edits: &mut Vec<EntryEdit>) {
+        let cfg_file = &self.cfg_files[cfg_file_index];
+
+        if !self.search_field.text_lowercase().is_empty() && self.match_count(cfg_file) == 0 {
+            return;
+        }
+
+        ui.label(RichText::new(&cfg_file.file_name).font(FontId::proportional(22.0)));
+        for header_line in cfg_file.cfg_file.header() {
+            ui.label(RichText::new(header_line).color(ui.style().visuals.weak_text_color()));
+        }
+
+        for section in cfg_file.cfg_file.sections() {
+            let entries: Vec<&Entry> = section
+                .entries()
+                .iter()
+                .filter(|entry| self.matches_search(cfg_file, section.name(), entry))
+                .collect();
+
+            if entries.is_empty() {
+                continue;
+            }
+
+            ui.add_space(8.);
+            ui.label(
+                RichText::new(format!("[{}]", section.name())).font(FontId::proportional(20.0)),
+            );
+            ui.separator();
+
+            for entry in entries {
+                edits.extend(cfg_entry::render(
+                    ui,
+                    cfg_file_index,
+                    section.name(),
+                    entry,
+                    &mut self.key_binding_listener,
+                ));
+            }
+        }
+
+        ui.add_space(16.);
+    }
+
+    fn apply_edits(&mut self, edits: Vec<EntryEdit>) {
+        for edit in edits {
+            self.cfg_files[edit.entry_id.cfg_file_index].apply_edit(&edit);
+        }
+    }
+
+    fn save_dirty_cfg_files(&mut self, ctx: &Context) {
+        if !cfg_entry::can_save(ctx) {
+            return;
+        }
+
+        for cfg_file in &mut self.cfg_files {
+            cfg_file.save_if_dirty(ctx, &SaveMode::DetectConflicts);
+        }
+    }
+
+    fn render_save_conflicts(&mut self, ctx: &Context) {
+        if !self
+            .cfg_files
+            .iter()
+            .any(|cfg_file| cfg_file.has_save_conflict)
+        {
+            return;
+        }
+
+        Window::new("Config File Changed On Disk")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(
+                    "Those files were modified by the game or another program since they were read, \
+                    saving now would erase their changes.",
+                );
+                ui.add_space(8.);
+
+                for cfg_file in self
+                    .cfg_files
+                    .iter_mut()
+                    .filter(|cfg_file| cfg_file.has_save_conflict)
+                {
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(&cfg_file.file_name).strong());
+
+                        if ui
+                            .button("Merge")
+                            .on_hover_text("Keep the changes made on disk and apply yours on top")
+                            .clicked()
+                        {
+                            resolve_save_conflict(cfg_file, SaveConflictResolution::Merge);
+                        }
+
+                        if ui
+                            .button("Reload")
+                            .on_hover_text("Discard your changes")
+                            .clicked()
+                        {
+                            resolve_save_conflict(cfg_file, SaveConflictResolution::Reload);
+                        }
+
+                        if ui
+                            .button("Overwrite")
+                            .on_hover_text("Discard the changes made on disk")
+                            .clicked()
+                        {
+                            resolve_save_conflict(cfg_file, SaveConflictResolution::Overwrite);
+                        }
+                    });
+                }
+            });
+    }
+}
+
+enum SaveConflictResolution {
+    Merge,
+    Reload,
+    Overwrite,
+}
+
+fn resolve_save_conflict(cfg_file: &mut EditedCfgFile, resolution: SaveConflictResolution) {
+    let result = match resolution {
+        SaveConflictResolution::Merge => cfg_file
+            .cfg_file
+            .merge_with_disk(&cfg_file.full_path)
+            .and_then(|_| cfg_file.cfg_file.force_save(&cfg_file.full_path)),
+        SaveConflictResolution::Reload => {
+            CfgFile::read(&cfg_file.full_path).map(|reloaded_cfg_file| {
+                cfg_file.cfg_file = reloaded_cfg_file;
+            })
+        }
+        SaveConflictResolution::Overwrite => cfg_file.cfg_file.force_save(&cfg_file.full_path),
+    };
+
+    if let Err(err) = result {
+        tracing::error!(
+            "Failed resolving save conflict of {}: {}",
+            cfg_file.full_path.display(),
+            err
+        );
+    }
+
+    cfg_file.has_save_conflict = false;
+}
+
+impl Tab for ConfigsTab {
+    fn name(&self) -> &str {
+        "Configs"
+    }
+
+    fn update_top_panel(
+        &mut self,
+        data: &AppLaunchConfig,
+        gui_config: &mut Config,
+        ui: &mut eframe::egui::Ui,
+    ) {
+        ui.horizontal(|ui| {
+            let text_color = if gui_config.dark_mode {
+                Color32::WHITE
+            } else {
+                Color32::BLACK
+            };
+            let hint_text_color = ui.style().visuals.text_color();
+            self.search_field.show(ui, |search_text| {
+                TextEdit::singleline(search_text)
+                    .desired_width(300.)
+                    .text_color(text_color)
+                    .hint_text(
+                        WidgetText::from("Setting name, description, file...")
+                            .color(hint_text_color),
+                    )
+            });
+
+            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                if ui
+                    .button(RichText::new("Snapshots").font(FontId::proportional(20.0)))
+                    .on_hover_text("Compare with and restore the configs from a previous launch")
+                    .clicked()
+                {
+                    self.snapshots_view
+                        .open(data.target_name(), data.modpack_name().as_deref());
+                }
+
+                if ui
+                    .button(RichText::new("Reload").font(FontId::proportional(20.0)))
+                    .on_hover_text("Read the config files from disk again")
+                    .clicked()
+                {
+                    self.reload();
+                }
+
+                ui.label(
+                    RichText::new(format!("Config Files: {}", self.cfg_files.len()))
+                        .font(FontId::proportional(20.0)),
+                );
+            });
+        });
+    }
+
+    fn update(
+        &mut self,
+        _data: &AppLaunchConfig,
+        _gui_config: &mut Config,
+        ctx: &eframe::egui::Context,
+        _frame: &mut eframe::Frame,
+    ) {
+        self.update_cfg_file_changed_receiver(ctx);
+
+        let mut edits = Vec::new();
+        edits.extend(self.key_binding_listener.update(ctx));
+
+        self.render_cfg_file_list(ctx);
+        edits.extend(self.render(ctx));
+
+        self.apply_edits(edits);
+        self.save_dirty_cfg_files(ctx);
+        self.render_save_conflicts(ctx);
+
+        if self.snapshots_view.is_open {
+            self.snapshots_view.show(ctx);
+        }
+    }
+
+    fn handle_action(
+        &mut self,
+        _data: &AppLaunchConfig,
+        _gui_config: &mut Config,
+        _ctx: &eframe::egui::Context,
+        action: Action,
+    ) {
+        if action == Action::FocusSearch {
+            self.search_field.focus();
+        }
+    }
+}
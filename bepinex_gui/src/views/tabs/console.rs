@@ -3,21 +3,35 @@ use crossbeam_channel::Receiver;
 use eframe::{egui::*, *};
 use std::{
     collections::HashMap,
-    sync::{
-        atomic::{AtomicBool, Ordering},
-        Arc,
-    },
-    time::SystemTime,
+    time::{Duration, Instant, SystemTime},
 };
+use strum::IntoEnumIterator;
 
 use crate::{
-    backend::process,
-    config::{launch::AppLaunchConfig, Config},
+    backend::{
+        alert, file_explorer_utils,
+        game_pause::{AutoPauseFilter, GamePause, PauseCommand},
+    },
+    config::{
+        alert_rules::{AlertRule, AlertRuleMatcher},
+        launch::AppLaunchConfig,
+        Config,
+    },
     data::{
-        bepinex_log::{BepInExLogEntry, LogLevel},
+        bepinex_log::{
+            self,
+            chainloader::{ChainloaderEvent, TimedChainloaderEvent},
+            BepInExLogEntry, LogLevel,
+        },
         bepinex_mod::BepInExMod,
+        resource_usage::{ResourceHistory, ResourceSample, ResourceSpike},
+    },
+    views::{
+        self,
+        actions::{self, Action},
+        disclaimer::Disclaimer,
+        tabs::resources,
     },
-    views::{self, disclaimer::Disclaimer},
 };
 
 use super::Tab;
@@ -96,11 +110,30 @@ struct Filter {
     text: String,
     text_lowercase: String,
     pub selected_index_in_mods_combo_box: usize,
+    should_focus_text_input: bool,
 }
 
 struct Scroll {
     last_log_count: usize,
     pending_scroll: Option<Vec2>,
+    pending_scroll_to_log_index: Option<usize>,
+}
+
+// Inputs of the timed and automatic pause controls of the footer
+struct PauseControls {
+    pause_secs: f32,
+    step_ms: u32,
+    auto_pause_level: LogLevel,
+    auto_pause_text: String,
+}
+
+impl PauseControls {
+    fn auto_pause_filter(&self) -> AutoPauseFilter {
+        AutoPauseFilter {
+            max_level: self.auto_pause_level,
+            text_lowercase: self.auto_pause_text.to_lowercase(),
+        }
+    }
 }
 
 pub struct ConsoleTab {
@@ -108,20 +141,26 @@ pub struct ConsoleTab {
     log_selection: LogSelection,
     filter: Filter,
     scroll: Scroll,
-    target_process_paused: bool,
-    mod_receiver: Receiver<BepInExMod>,
+    game_pause: GamePause,
+    pause_controls: PauseControls,
+    mod_receiver: Receiver<TimedChainloaderEvent>,
     mods: Vec<BepInExMod>,
     log_receiver: Receiver<BepInExLogEntry>,
     logs: Vec<BepInExLogEntry>,
-    should_exit_app: Arc<AtomicBool>,
     log_heights: HashMap<usize, f32>,
+    resource_sample_receiver: Receiver<ResourceSample>,
+    // only used for marking the resource spikes between the logs
+    resource_history: ResourceHistory,
+    is_memory_leak_notified: bool,
+    alert_rule_matcher: AlertRuleMatcher,
 }
 
 impl ConsoleTab {
     pub fn new(
-        mod_receiver: Receiver<BepInExMod>,
+        mod_receiver: Receiver<TimedChainloaderEvent>,
         log_receiver: Receiver<BepInExLogEntry>,
-        should_exit_app: Arc<AtomicBool>,
+        resource_sample_receiver: Receiver<ResourceSample>,
+        game_pause: GamePause,
     ) -> Self {
         Self {
             disclaimer: Disclaimer {
@@ -140,18 +179,29 @@ impl ConsoleTab {
                 text: Default::default(),
                 text_lowercase: Default::default(),
                 selected_index_in_mods_combo_box: 0,
+                should_focus_text_input: false,
             },
             scroll: Scroll {
                 last_log_count: 0,
                 pending_scroll: None,
+                pending_scroll_to_log_index: None,
+            },
+            game_pause,
+            pause_controls: PauseControls {
+                pause_secs: 5.,
+                step_ms: 100,
+                auto_pause_level: LogLevel::Error,
+                auto_pause_text: String::new(),
             },
-            target_process_paused: false,
             mod_receiver,
             mods: vec![BepInExMod::new("", "")],
             log_receiver,
             logs: vec![],
-            should_exit_app,
             log_heights: HashMap::new(),
+            resource_sample_receiver,
+            resource_history: ResourceHistory::default(),
+            is_memory_leak_notified: false,
+            alert_rule_matcher: AlertRuleMatcher::default(),
         }
     }
 
@@ -205,13 +255,91 @@ impl ConsoleTab {
         }
     }
 
-    fn kill_gui_and_target(&mut self, data: &AppLaunchConfig) {
-        process::kill(data.target_process_id(), || {
-            tracing::info!("Exiting because Command + F5 was pressed.");
-            self.should_exit_app.store(true, Ordering::Relaxed);
+    fn toggle_pause_game(&mut self) {
+        // may have been paused by a timer or a log while another tab was on screen
+        self.game_pause.update_status_receiver();
+
+        self.game_pause.send(if self.game_pause.status().is_paused {
+            PauseCommand::Resume
+        } else {
+            PauseCommand::Pause
         });
     }
 
+    fn is_log_shown(&self, gui_config: &Config, log: &BepInExLogEntry) -> bool {
+        log.level() <= gui_config.log_level_filter
+            && Self::does_log_match_text_filter(&self.filter.text_lowercase, log)
+    }
+
+    // Selects the closest error after the selection, or before it when going backward
+    fn select_next_error(&mut self, gui_config: &Config, backward: bool) {
+        let is_shown_error = |log: &BepInExLogEntry| {
+            matches!(log.level(), LogLevel::Fatal | LogLevel::Error)
+                && self.is_log_shown(gui_config, log)
+        };
+
+        let has_selection = self.log_selection.index_of_first_selected_log != usize::MAX;
+
+        let error_index = if backward {
+            let end = if has_selection {
+                std::cmp::min(
+                    self.log_selection.index_of_first_selected_log,
+                    self.log_selection.index_of_last_selected_log,
+                )
+            } else {
+                self.logs.len()
+            };
+
+            self.logs[..end].iter().rposition(is_shown_error)
+        } else {
+            let start = if has_selection {
+                std::cmp::max(
+                    self.log_selection.index_of_first_selected_log,
+                    self.log_selection.index_of_last_selected_log,
+                ) + 1
+            } else {
+                0
+            };
+
+            self.logs
+                .get(start..)
+                .and_then(|logs| logs.iter().position(is_shown_error))
+                .map(|i| start + i)
+        };
+
+        if let Some(error_index) = error_index {
+            self.log_selection.index_of_first_selected_log = error_index;
+            self.log_selection.index_of_last_selected_log = error_index;
+            self.scroll.pending_scroll_to_log_index = Some(error_index);
+        }
+    }
+
+    fn select_all_logs(&mut self) {
+        if self.logs.is_empty() {
+            return;
+        }
+
+        self.log_selection.index_of_first_selected_log = 0;
+        self.log_selection.index_of_last_selected_log = self.logs.len() - 1;
+    }
+
+    fn clear_log_selection(&mut self) {
+        self.log_selection.index_of_first_selected_log = usize::MAX;
+        self.log_selection.index_of_last_selected_log = usize::MAX;
+    }
+
+    fn export_logs(&self, data: &AppLaunchConfig, ctx: &Context) {
+        match bepinex_log::file::export(data.target_name(), &self.logs) {
+            Ok(exported_logs_file_full_path) => {
+                file_explorer_utils::highlight_path_in_explorer(&exported_logs_file_full_path);
+            }
+            Err(err) => {
+                tracing::error!("Failed exporting logs: {}", err);
+                views::notifications::push(ctx, format!("Failed exporting logs: {err}"));
+            }
+        }
+    }
+
     fn render_logs(&mut self, gui_config: &Config, ui: &mut eframe::egui::Ui) {
         let clip_rect = ui.painter().clip_rect();
 
@@ -221,8 +349,23 @@ impl ConsoleTab {
             Color32::BLACK
         };
 
+        let spikes = self.resource_history.spikes();
+        let mut next_spike_index = 0;
+
         let log_count = self.logs.len();
         for i in 0..log_count {
+            // spikes are shown right before the first log that came after them
+            while let Some(spike) = spikes.get(next_spike_index) {
+                if spike.time > self.logs[i].time() {
+                    break;
+                }
+
+                render_resource_spike_marker(ui, spike);
+                next_spike_index += 1;
+            }
+
+            let pos_before_log = ui.next_widget_position();
+
             Self::render_log(
                 &mut self.log_heights,
                 &mut self.filter,
@@ -240,6 +383,17 @@ impl ConsoleTab {
                 self.log_selection.index_of_first_selected_log,
                 self.log_selection.index_of_last_selected_log,
             );
+
+            // works for the logs that are out of screen too, they still take their height
+            if self.scroll.pending_scroll_to_log_index == Some(i) {
+                let log_rect = Rect::from_two_pos(pos_before_log, ui.next_widget_position());
+                ui.scroll_to_rect(log_rect, Some(Align::Center));
+                self.scroll.pending_scroll_to_log_index = None;
+            }
+        }
+
+        for spike in &spikes[next_spike_index..] {
+            render_resource_spike_marker(ui, spike);
         }
 
         if gui_config.log_auto_scroll_to_bottom
@@ -331,7 +485,7 @@ impl ConsoleTab {
         true
     }
 
-    fn render_footer(&mut self, data: &AppLaunchConfig, gui_config: &mut Config, ctx: &Context) {
+    fn render_footer(&mut self, gui_config: &mut Config, ctx: &Context) {
         TopBottomPanel::bottom("footer").show(ctx, |ui| {
             ui.add_space(2.0);
 
@@ -349,14 +503,106 @@ impl ConsoleTab {
                 );
             });
 
-            views::BepInExGUI::render_useful_buttons_footer(
-                ui,
-                ctx,
-                data.game_folder_full_path(),
-                data.bepinex_log_output_file_full_path(),
-                data.target_process_id(),
+            self.render_pause_controls(ui);
+
+            views::BepInExGUI::render_useful_buttons_footer(ui, ctx);
+        });
+    }
+
+    fn render_pause_controls(&mut self, ui: &mut Ui) {
+        let pause_status = self.game_pause.status().clone();
+
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("Pause Game For: ").font(FontId::proportional(15.0)));
+            ui.add(
+                DragValue::new(&mut self.pause_controls.pause_secs)
+                    .clamp_range(0.1..=600.)
+                    .speed(0.1)
+                    .suffix(" s"),
             );
+            if ui.button("Pause").clicked() {
+                self.game_pause
+                    .send(PauseCommand::PauseFor(Duration::from_secs_f32(
+                        self.pause_controls.pause_secs,
+                    )));
+            }
+
+            ui.separator();
+
+            ui.label(RichText::new("Resume For: ").font(FontId::proportional(15.0)));
+            ui.add(
+                DragValue::new(&mut self.pause_controls.step_ms)
+                    .clamp_range(1..=10000)
+                    .suffix(" ms"),
+            );
+            if ui
+                .button("Step")
+                .on_hover_text("Resumes the game, then pauses it again")
+                .clicked()
+            {
+                self.game_pause
+                    .send(PauseCommand::ResumeFor(Duration::from_millis(
+                        self.pause_controls.step_ms as u64,
+                    )));
+            }
+
+            ui.separator();
+
+            ui.label(RichText::new("Auto Pause On: ").font(FontId::proportional(15.0)));
+            let mut is_auto_pause_filter_changed = false;
+            ComboBox::from_id_source("combo_box_auto_pause_level")
+                .selected_text(format!("{} or worse", self.pause_controls.auto_pause_level))
+                .show_ui(ui, |ui| {
+                    for log_level in LogLevel::iter()
+                        .filter(|log_level| !matches!(log_level, LogLevel::None | LogLevel::All))
+                    {
+                        is_auto_pause_filter_changed |= ui
+                            .selectable_value(
+                                &mut self.pause_controls.auto_pause_level,
+                                log_level,
+                                log_level.to_string(),
+                            )
+                            .changed();
+                    }
+                });
+            is_auto_pause_filter_changed |= ui
+                .add(
+                    TextEdit::singleline(&mut self.pause_controls.auto_pause_text)
+                        .desired_width(150.)
+                        .hint_text("Plugin name or text"),
+                )
+                .changed();
+
+            let mut is_auto_pause_armed = pause_status.auto_pause_filter.is_some();
+            if ui.checkbox(&mut is_auto_pause_armed, "Armed").changed() {
+                self.game_pause.send(PauseCommand::SetAutoPauseFilter(
+                    is_auto_pause_armed.then(|| self.pause_controls.auto_pause_filter()),
+                ));
+            } else if is_auto_pause_armed && is_auto_pause_filter_changed {
+                self.game_pause.send(PauseCommand::SetAutoPauseFilter(Some(
+                    self.pause_controls.auto_pause_filter(),
+                )));
+            }
         });
+
+        if let Some(timer_deadline) = pause_status.timer_deadline {
+            let remaining_secs = timer_deadline
+                .saturating_duration_since(Instant::now())
+                .as_secs_f32();
+            ui.label(if pause_status.is_paused {
+                format!("Resuming the game in {remaining_secs:.1}s")
+            } else {
+                format!("Pausing the game again in {remaining_secs:.1}s")
+            });
+        }
+
+        if let Some(auto_pause_log) = &pause_status.auto_pause_log {
+            if pause_status.is_paused {
+                ui.label(
+                    RichText::new(format!("Paused by: {auto_pause_log}")).color(Color32::YELLOW),
+                );
+            }
+        }
     }
 
     fn render_console_first_time_disclaimer(&mut self, ctx: &Context, gui_config: &mut Config) {
@@ -398,12 +644,7 @@ Please use the buttons below and use the "Copy Log File" button, and drag and dr
         });
     }
 
-    fn render_kill_gui_and_game_butto(
-        &mut self,
-        ui: &mut Ui,
-        pause_game_btn_size: Vec2,
-        data: &AppLaunchConfig,
-    ) {
+    fn render_kill_gui_and_game_butto(&mut self, ui: &mut Ui, pause_game_btn_size: Vec2) -> Vec2 {
         let kill_game_btn_text = "Kill GUI & Game";
         let kill_game_btn_size =
             views::utils::egui::compute_text_size(ui, kill_game_btn_text, true, false, None);
@@ -419,12 +660,41 @@ Please use the buttons below and use the "Copy Log File" button, and drag and dr
             ))
             .clicked()
         {
-            self.kill_gui_and_target(data);
+            actions::dispatch(ui.ctx(), Action::KillGame);
+        }
+        kill_game_btn_size
+    }
+
+    fn render_restart_game_button(
+        &mut self,
+        ui: &mut Ui,
+        pause_game_btn_size: Vec2,
+        kill_game_btn_size: Vec2,
+    ) {
+        let restart_game_btn_text = "Restart Game";
+        let restart_game_btn_size =
+            views::utils::egui::compute_text_size(ui, restart_game_btn_text, true, false, None);
+
+        ui.add_space(ui.spacing().item_spacing.x.mul_add(
+            -6.,
+            ui.available_width()
+                - pause_game_btn_size.x
+                - kill_game_btn_size.x
+                - restart_game_btn_size.x,
+        ));
+
+        if ui
+            .add(Button::new(
+                RichText::new(restart_game_btn_text).text_style(egui::TextStyle::Heading),
+            ))
+            .clicked()
+        {
+            actions::dispatch(ui.ctx(), Action::RestartGame);
         }
     }
 
-    fn render_pause_game_button(&mut self, ui: &mut Ui, data: &AppLaunchConfig) -> Vec2 {
-        let pause_game_btn_text = if self.target_process_paused {
+    fn render_pause_game_button(&mut self, ui: &mut Ui) -> Vec2 {
+        let pause_game_btn_text = if self.game_pause.status().is_paused {
             "Resume Game"
         } else {
             "Pause Game"
@@ -445,13 +715,7 @@ Please use the buttons below and use the "Copy Log File" button, and drag and dr
             ))
             .clicked()
         {
-            let target_process_id = data.target_process_id();
-
-            if self.target_process_paused {
-                self.target_process_paused = !process::resume(target_process_id);
-            } else {
-                self.target_process_paused = process::suspend(target_process_id);
-            }
+            actions::dispatch(ui.ctx(), Action::TogglePauseGame);
         }
         pause_game_btn_size
     }
@@ -462,21 +726,23 @@ Please use the buttons below and use the "Copy Log File" button, and drag and dr
         mods_combo_box: &Response,
         gui_config: &mut Config,
     ) {
-        if ui
-            .add_sized(
-                mods_combo_box.rect.size(),
-                TextEdit::singleline(&mut self.filter.text)
-                    .text_color(if gui_config.dark_mode {
-                        Color32::WHITE
-                    } else {
-                        Color32::BLACK
-                    })
-                    .hint_text(
-                        WidgetText::from("Filter Text").color(ui.style().visuals.text_color()),
-                    ),
-            )
-            .changed()
-        {
+        let text_input = ui.add_sized(
+            mods_combo_box.rect.size(),
+            TextEdit::singleline(&mut self.filter.text)
+                .text_color(if gui_config.dark_mode {
+                    Color32::WHITE
+                } else {
+                    Color32::BLACK
+                })
+                .hint_text(WidgetText::from("Filter Text").color(ui.style().visuals.text_color())),
+        );
+
+        if self.filter.should_focus_text_input {
+            text_input.request_focus();
+            self.filter.should_focus_text_input = false;
+        }
+
+        if text_input.changed() {
             self.filter.text_lowercase = self.filter.text.to_lowercase();
         }
     }
@@ -537,6 +803,14 @@ fn make_log_render_decision(
     LogRenderDecision::RenderNormally
 }
 
+fn render_resource_spike_marker(ui: &mut Ui, spike: &ResourceSpike) {
+    ui.label(
+        RichText::new(format!("⚡ {}", resources::format_spike(spike)))
+            .color(Color32::from_rgb(255, 100, 100))
+            .italics(),
+    );
+}
+
 fn render_loading_text(ui: &mut Ui) {
     ui.vertical_centered_justified(|ui| {
         let loading_text = "Loading ⌛";
@@ -569,7 +843,7 @@ impl Tab for ConsoleTab {
 
     fn update_top_panel(
         &mut self,
-        data: &AppLaunchConfig,
+        _data: &AppLaunchConfig,
         gui_config: &mut Config,
         ui: &mut eframe::egui::Ui,
     ) {
@@ -586,31 +860,38 @@ impl Tab for ConsoleTab {
 
                 ui.set_cursor(cur_cursor_rect);
 
-                let pause_game_btn_size = self.render_pause_game_button(ui, data);
+                let pause_game_btn_size = self.render_pause_game_button(ui);
+
+                ui.set_cursor(cur_cursor_rect);
+
+                let kill_game_btn_size =
+                    self.render_kill_gui_and_game_butto(ui, pause_game_btn_size);
 
                 ui.set_cursor(cur_cursor_rect);
 
-                self.render_kill_gui_and_game_butto(ui, pause_game_btn_size, data);
+                self.render_restart_game_button(ui, pause_game_btn_size, kill_game_btn_size);
             });
         });
     }
 
     fn update(
         &mut self,
-        data: &AppLaunchConfig,
+        _data: &AppLaunchConfig,
         gui_config: &mut Config,
         ctx: &eframe::egui::Context,
         _frame: &mut eframe::Frame,
     ) {
         self.log_selection.update_pointer_state(ctx);
 
+        self.game_pause.update_status_receiver();
+
         self.update_mod_receiver();
-        self.update_log_receiver();
+        self.update_resource_sample_receiver(ctx);
 
         if gui_config.first_time_console_disclaimer {
             self.render_console_first_time_disclaimer(ctx, gui_config);
         } else {
-            self.render_footer(data, gui_config, ctx);
+            self.render_footer(gui_config, ctx);
 
             self.render(gui_config, ctx);
 
@@ -618,7 +899,48 @@ impl Tab for ConsoleTab {
         }
 
         if ctx.input(|i| i.modifiers.command) && ctx.input(|i| i.key_pressed(Key::F5)) {
-            self.kill_gui_and_target(data);
+            actions::dispatch(ctx, Action::KillGame);
+        }
+    }
+
+    // The alert rules must be checked even when the console is not on screen
+    fn update_in_background(
+        &mut self,
+        _data: &AppLaunchConfig,
+        gui_config: &mut Config,
+        ctx: &eframe::egui::Context,
+        frame: &mut eframe::Frame,
+    ) {
+        self.update_log_receiver(gui_config, ctx, frame);
+    }
+
+    fn handle_action(
+        &mut self,
+        data: &AppLaunchConfig,
+        gui_config: &mut Config,
+        ctx: &eframe::egui::Context,
+        action: Action,
+    ) {
+        match action {
+            Action::TogglePauseGame => self.toggle_pause_game(),
+            Action::ExportLogs => self.export_logs(data, ctx),
+            Action::FocusSearch => self.filter.should_focus_text_input = true,
+            Action::SelectNextError => self.select_next_error(gui_config, false),
+            Action::SelectPreviousError => self.select_next_error(gui_config, true),
+            Action::SelectAllLogs => self.select_all_logs(),
+            Action::ClearLogSelection => self.clear_log_selection(),
+            Action::ScrollConsoleToBottom => {
+                self.scroll.pending_scroll = Some(Vec2::new(0., f32::NEG_INFINITY));
+            }
+            // the logs of the previous instance are kept
+            Action::GameAttached => {
+                self.update_mod_receiver();
+                self.mods = vec![BepInExMod::new("", "")];
+                self.filter.selected_index_in_mods_combo_box = 0;
+                self.game_pause
+                    .send(PauseCommand::Attach(data.target_process_id()));
+            }
+            _ => {}
         }
     }
 }
@@ -632,18 +954,38 @@ fn render_auto_scroll_to_bottom_checkbox(ui: &mut Ui, gui_config: &mut Config) {
 
 impl ConsoleTab {
     fn update_mod_receiver(&mut self) {
-        if let Ok(mod_) = self.mod_receiver.try_recv() {
-            self.mods.push(mod_);
+        while let Ok(timed_chainloader_event) = self.mod_receiver.try_recv() {
+            if let ChainloaderEvent::Loading(mod_) = timed_chainloader_event.event {
+                self.mods.push(mod_);
+            }
         }
     }
 
-    fn update_log_receiver(&mut self) {
+    fn update_log_receiver(
+        &mut self,
+        gui_config: &mut Config,
+        ctx: &Context,
+        frame: &mut eframe::Frame,
+    ) {
+        self.alert_rule_matcher.set_rules(&gui_config.alert_rules);
+
         // loop until the channel is emptied
         // if we don't do that the maximum amount of log received is
         // tied to the framerate of the GUI
         loop {
             match self.log_receiver.try_recv() {
                 Ok(log) => {
+                    for alert_rule in self.alert_rule_matcher.check(&log) {
+                        self.trigger_alert(
+                            &alert_rule,
+                            &log,
+                            self.logs.len(),
+                            gui_config,
+                            ctx,
+                            frame,
+                        );
+                    }
+
                     self.logs.push(log);
                 }
                 Err(err) => match err {
@@ -655,6 +997,71 @@ impl ConsoleTab {
             }
         }
     }
+
+    fn trigger_alert(
+        &mut self,
+        alert_rule: &AlertRule,
+        log: &BepInExLogEntry,
+        log_index: usize,
+        gui_config: &mut Config,
+        ctx: &Context,
+        frame: &mut eframe::Frame,
+    ) {
+        tracing::info!(
+            "Alert rule {} triggered by: {}",
+            alert_rule.name,
+            log.data()
+        );
+        views::notifications::push(ctx, format!("{}: {}", alert_rule.name, log.data()));
+
+        let actions = &alert_rule.actions;
+
+        // first so that the game is stopped as close as possible to the log.
+        // The auto pause may have stopped it already, the pause controller ignores a second pause anyway
+        if actions.pause_game && !self.game_pause.status().is_paused {
+            self.game_pause.send(PauseCommand::Pause);
+        }
+
+        if actions.desktop_notification {
+            alert::spawn_thread_show_desktop_notification(
+                alert_rule.name.clone(),
+                log.data().to_string(),
+            );
+        }
+
+        if actions.flash_window {
+            frame.request_user_attention(UserAttentionType::Critical);
+        }
+
+        if actions.sound {
+            alert::play_sound();
+        }
+
+        // auto scroll would move away from it right after
+        if actions.scroll_to_log {
+            gui_config.log_auto_scroll_to_bottom = false;
+            self.log_selection.index_of_first_selected_log = log_index;
+            self.log_selection.index_of_last_selected_log = log_index;
+            self.scroll.pending_scroll_to_log_index = Some(log_index);
+        }
+    }
+
+    // The console is the tab that stays open, so it is the one warning about leaks
+    fn update_resource_sample_receiver(&mut self, ctx: &Context) {
+        while let Ok(sample) = self.resource_sample_receiver.try_recv() {
+            self.resource_history.push(sample);
+        }
+
+        let is_memory_leaking = self.resource_history.memory_leak_warning().is_some();
+        if is_memory_leaking && !self.is_memory_leak_notified {
+            views::notifications::push(
+                ctx,
+                "The game memory keeps growing, a mod may be leaking memory. See the Resources tab.",
+            );
+        }
+
+        self.is_memory_leak_notified = is_memory_leaking;
+    }
 }
 
 fn is_between<T: Ord + std::marker::Copy>(value: T, bound1: T, bound2: T) -> bool {
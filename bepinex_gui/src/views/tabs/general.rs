@@ -1,50 +1,82 @@
+use std::path::PathBuf;
+
+use clipboard::{ClipboardContext, ClipboardProvider};
 use crossbeam_channel::Receiver;
+use strum::IntoEnumIterator;
 
 use eframe::{
     egui::{self, CentralPanel, Context, Label, Layout, RichText, ScrollArea, TopBottomPanel},
     emath::Align,
-    epaint::FontId,
+    epaint::{Color32, FontId},
 };
 
 use crate::{
     app,
     config::{launch::AppLaunchConfig, Config},
-    data::bepinex_mod::BepInExMod,
-    views,
+    data::{
+        bepinex_log::chainloader::{ChainloaderEvent, TimedChainloaderEvent},
+        bepinex_mod::BepInExMod,
+        dependency_graph::DependencyGraph,
+        installed_plugins::{InstalledPlugin, InstalledPlugins},
+        mod_list::{self, ExportFormat, ExportedMod},
+    },
+    views::{
+        self, actions::Action, dependency_graph::DependencyGraphView,
+        mod_list_diff::ModListDiffView,
+    },
 };
 
 use super::Tab;
 
 pub struct GeneralTab {
-    mod_receiver: Receiver<BepInExMod>,
+    mod_receiver: Receiver<TimedChainloaderEvent>,
     mods: Vec<BepInExMod>,
+    dependency_graph: DependencyGraph,
+    dependency_graph_view: DependencyGraphView,
+    installed_plugins_receiver: Receiver<InstalledPlugins>,
+    installed_plugins: Option<InstalledPlugins>,
+    not_loaded_plugins: Vec<InstalledPlugin>,
+    exported_mods: Vec<ExportedMod>,
+    is_startup_complete: bool,
+    mod_list_diff_view: ModListDiffView,
+    // None until the game is done loading
+    current_session_mod_list: Option<PathBuf>,
 }
 
 impl GeneralTab {
-    pub fn new(mods_receiver: Receiver<BepInExMod>) -> Self {
+    pub fn new(
+        mods_receiver: Receiver<TimedChainloaderEvent>,
+        installed_plugins_receiver: Receiver<InstalledPlugins>,
+    ) -> Self {
         Self {
             mod_receiver: mods_receiver,
             mods: Vec::new(),
+            dependency_graph: DependencyGraph::default(),
+            dependency_graph_view: DependencyGraphView::new(),
+            installed_plugins_receiver,
+            installed_plugins: None,
+            not_loaded_plugins: Vec::new(),
+            exported_mods: Vec::new(),
+            is_startup_complete: false,
+            mod_list_diff_view: ModListDiffView::new(),
+            current_session_mod_list: None,
         }
     }
 
-    fn render_footer(&mut self, data: &AppLaunchConfig, ctx: &Context) {
+    fn render_footer(&mut self, ctx: &Context) {
         TopBottomPanel::bottom("footer").show(ctx, |ui| {
             ui.add_space(25.0);
 
-            app::BepInExGUI::render_useful_buttons_footer(
-                ui,
-                ctx,
-                data.game_folder_full_path(),
-                data.bepinex_log_output_file_full_path(),
-                data.target_process_id(),
-            );
+            app::BepInExGUI::render_useful_buttons_footer(ui, ctx);
         });
     }
 
     fn render(&mut self, gui_config: &Config, ctx: &Context) {
         CentralPanel::default().show(ctx, |ui| {
-            if self.mods.is_empty() {
+            if self.mods.is_empty()
+                && self.dependency_graph.issues().is_empty()
+                && self.installed_plugins.is_none()
+            {
                 ui.vertical_centered_justified(|ui| {
                     let loading_text = "Loading ⌛";
                     let text_size =
@@ -57,21 +89,161 @@ impl GeneralTab {
                 ScrollArea::vertical()
                     .auto_shrink([false; 2])
                     .show(ui, |ui| {
+                        self.render_dependency_issues(ui);
                         self.render_mods(gui_config, ui);
+                        self.render_not_loaded_plugins(ui);
                     });
             }
         });
     }
 
+    fn render_dependency_issues(&self, ui: &mut egui::Ui) {
+        let issues = self.dependency_graph.issues();
+        if issues.is_empty() {
+            return;
+        }
+
+        ui.label(
+            RichText::new(format!("Dependency Issues: {}", issues.len()))
+                .font(FontId::proportional(20.0))
+                .color(Color32::RED),
+        );
+
+        for issue in issues {
+            ui.add(Label::new(RichText::new(issue.to_string()).color(Color32::RED)).wrap(true));
+        }
+
+        ui.separator();
+    }
+
     fn render_mods(&self, _gui_config: &Config, ui: &mut egui::Ui) {
         for mod_ in self.mods.as_slice() {
             ui.add(Label::new(RichText::new(mod_.to_string())));
         }
     }
 
+    fn render_not_loaded_plugins(&self, ui: &mut egui::Ui) {
+        if self.not_loaded_plugins.is_empty() {
+            return;
+        }
+
+        ui.separator();
+
+        let (title, color) = if self.is_startup_complete {
+            ("Installed but not loaded", Color32::YELLOW)
+        } else {
+            (
+                "Installed, not loaded yet",
+                ui.style().visuals.weak_text_color(),
+            )
+        };
+
+        ui.label(
+            RichText::new(format!("{title}: {}", self.not_loaded_plugins.len()))
+                .font(FontId::proportional(20.0))
+                .color(color),
+        );
+
+        for plugin in &self.not_loaded_plugins {
+            let text = match &plugin.version {
+                Some(version) => format!("{} {}", plugin.name, version),
+                None => plugin.name.clone(),
+            };
+
+            ui.add(Label::new(RichText::new(text).color(color)))
+                .on_hover_text(plugin.full_path.display().to_string());
+        }
+    }
+
+    fn render_export_mod_list_menu(&mut self, data: &AppLaunchConfig, ui: &mut egui::Ui) {
+        ui.menu_button(
+            RichText::new("Mod List").font(FontId::proportional(20.0)),
+            |ui| {
+                for format in ExportFormat::iter() {
+                    if ui.button(format!("Copy as {format}")).clicked() {
+                        copy_to_clipboard(mod_list::export(&self.exported_mods, format));
+                        ui.close_menu();
+                    }
+                }
+
+                ui.separator();
+
+                if ui.button("Compare with a previous session").clicked() {
+                    self.mod_list_diff_view.open(
+                        data.target_name(),
+                        self.current_session_mod_list.as_deref(),
+                        &self.exported_mods,
+                    );
+                    ui.close_menu();
+                }
+            },
+        );
+    }
+
+    fn save_mod_list_once_startup_is_complete(&mut self, data: &AppLaunchConfig) {
+        if !self.is_startup_complete || self.current_session_mod_list.is_some() {
+            return;
+        }
+
+        match mod_list::save(data.target_name(), &self.exported_mods) {
+            Ok(file_full_path) => self.current_session_mod_list = Some(file_full_path),
+            Err(err) => {
+                tracing::error!("Failed saving mod list: {}", err);
+                // don't try again every frame
+                self.current_session_mod_list = Some(PathBuf::new());
+            }
+        }
+    }
+
+    fn update_installed_plugins_receiver(&mut self) {
+        if let Ok(installed_plugins) = self.installed_plugins_receiver.try_recv() {
+            self.dependency_graph
+                .set_installed_plugins(&installed_plugins);
+
+            self.installed_plugins = Some(installed_plugins);
+            self.update_installed_plugin_matches();
+        }
+    }
+
+    // Loading events only update these incrementally
+    fn update_installed_plugin_matches(&mut self) {
+        self.not_loaded_plugins = match &self.installed_plugins {
+            Some(installed_plugins) => installed_plugins.not_loaded(&self.mods).cloned().collect(),
+            None => Vec::new(),
+        };
+
+        self.exported_mods =
+            mod_list::make_exported_mods(&self.mods, self.installed_plugins.as_ref());
+    }
+
     fn update_mod_receiver(&mut self) {
-        if let Ok(mod_) = self.mod_receiver.try_recv() {
-            self.mods.push(mod_);
+        while let Ok(timed_chainloader_event) = self.mod_receiver.try_recv() {
+            self.dependency_graph
+                .on_chainloader_event(&timed_chainloader_event.event);
+
+            match timed_chainloader_event.event {
+                ChainloaderEvent::Loading(mod_) => {
+                    self.not_loaded_plugins
+                        .retain(|plugin| !plugin.matches(&mod_));
+                    self.exported_mods.push(mod_list::make_exported_mod(
+                        &mod_,
+                        self.installed_plugins.as_ref(),
+                    ));
+                    self.mods.push(mod_);
+                }
+                ChainloaderEvent::StartupComplete => self.is_startup_complete = true,
+                _ => {}
+            }
+        }
+    }
+}
+
+fn copy_to_clipboard(text: String) {
+    if let Ok(ctx_) = ClipboardProvider::new() {
+        let mut ctx: ClipboardContext = ctx_;
+
+        if let Err(err) = ctx.set_contents(text) {
+            tracing::error!("Failed copying to clipboard: {}", err);
         }
     }
 }
@@ -98,9 +270,28 @@ impl Tab for GeneralTab {
             });
         });
 
-        let loaded_mod_count = self.mods.len();
-        let loaded_mods_text = format!("Loaded Mods: {loaded_mod_count}");
-        ui.label(RichText::new(loaded_mods_text).font(FontId::proportional(20.0)));
+        ui.horizontal(|ui| {
+            let loaded_mod_count = self.mods.len();
+            let loaded_mods_text = match &self.installed_plugins {
+                Some(installed_plugins) => format!(
+                    "Loaded Mods: {loaded_mod_count} / Installed: {}",
+                    installed_plugins.plugins.len()
+                ),
+                None => format!("Loaded Mods: {loaded_mod_count}"),
+            };
+            ui.label(RichText::new(loaded_mods_text).font(FontId::proportional(20.0)));
+
+            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                if ui
+                    .button(RichText::new("Dependency Graph").font(FontId::proportional(20.0)))
+                    .clicked()
+                {
+                    self.dependency_graph_view.is_open = !self.dependency_graph_view.is_open;
+                }
+
+                self.render_export_mod_list_menu(data, ui);
+            });
+        });
     }
 
     fn update(
@@ -110,10 +301,42 @@ impl Tab for GeneralTab {
         ctx: &eframe::egui::Context,
         _frame: &mut eframe::Frame,
     ) {
+        self.update_installed_plugins_receiver();
         self.update_mod_receiver();
+        self.save_mod_list_once_startup_is_complete(data);
 
-        self.render_footer(data, ctx);
+        self.render_footer(ctx);
 
         self.render(gui_config, ctx);
+
+        if self.dependency_graph_view.is_open {
+            self.dependency_graph_view.show(ctx, &self.dependency_graph);
+        }
+
+        if self.mod_list_diff_view.is_open {
+            self.mod_list_diff_view.show(ctx, &self.exported_mods);
+        }
+    }
+
+    fn handle_action(
+        &mut self,
+        _data: &AppLaunchConfig,
+        _gui_config: &mut Config,
+        _ctx: &eframe::egui::Context,
+        action: Action,
+    ) {
+        if action != Action::GameAttached {
+            return;
+        }
+
+        // the events of the previous instance first, then start over
+        self.update_installed_plugins_receiver();
+        self.update_mod_receiver();
+
+        self.mods.clear();
+        self.dependency_graph.clear_chainloader_events();
+        self.update_installed_plugin_matches();
+        self.is_startup_complete = false;
+        self.current_session_mod_list = None;
     }
 }
@@ -1,8 +1,14 @@
-use crate::config::{launch::AppLaunchConfig, Config};
+use crate::{
+    config::{launch::AppLaunchConfig, Config},
+    views::actions::Action,
+};
 
+pub mod configs;
 pub mod console;
 pub mod general;
+pub mod resources;
 pub mod settings;
+pub mod timeline;
 
 pub trait Tab {
     fn name(&self) -> &str;
@@ -21,4 +27,24 @@ pub trait Tab {
         ctx: &eframe::egui::Context,
         frame: &mut eframe::Frame,
     );
+
+    // Called every frame on every tab whether it is selected or not, before the selected one is updated
+    fn update_in_background(
+        &mut self,
+        _data: &AppLaunchConfig,
+        _gui_config: &mut Config,
+        _ctx: &eframe::egui::Context,
+        _frame: &mut eframe::Frame,
+    ) {
+    }
+
+    // Called for the actions that need the state of a tab, on every tab whether it is selected or not
+    fn handle_action(
+        &mut self,
+        _data: &AppLaunchConfig,
+        _gui_config: &mut Config,
+        _ctx: &eframe::egui::Context,
+        _action: Action,
+    ) {
+    }
 }
@@ -0,0 +1,239 @@
+use std::time::SystemTime;
+
+use crossbeam_channel::Receiver;
+use eframe::{
+    egui::{
+        self,
+        plot::{Line, Plot, PlotPoints, VLine},
+        CentralPanel, Context, Layout, RichText, ScrollArea, Ui,
+    },
+    emath::Align,
+    epaint::{Color32, FontId},
+};
+
+use crate::{
+    config::{launch::AppLaunchConfig, Config},
+    data::resource_usage::{ResourceHistory, ResourceKind, ResourceSample, ResourceSpike},
+    views::{
+        self,
+        utils::{bytes::format_bytes, time::format_duration_short},
+    },
+};
+
+use super::Tab;
+
+const PLOT_HEIGHT: f32 = 140.;
+const PLOTS_LINK_ID: &str = "resource_plots";
+
+pub struct ResourcesTab {
+    resource_sample_receiver: Receiver<ResourceSample>,
+    resource_history: ResourceHistory,
+    start_time: SystemTime,
+}
+
+impl ResourcesTab {
+    pub fn new(resource_sample_receiver: Receiver<ResourceSample>) -> Self {
+        Self {
+            resource_sample_receiver,
+            resource_history: ResourceHistory::default(),
+            start_time: SystemTime::now(),
+        }
+    }
+
+    fn render(&self, ctx: &Context) {
+        CentralPanel::default().show(ctx, |ui| {
+            if self.resource_history.samples().is_empty() {
+                ui.vertical_centered_justified(|ui| {
+                    let loading_text = "Loading ⌛";
+                    let text_size =
+                        views::utils::egui::compute_text_size(ui, loading_text, true, false, None);
+                    ui.add_space(ui.available_height() / 2. - text_size.y);
+                    ui.heading(loading_text);
+                });
+                return;
+            }
+
+            ui.spacing_mut().scroll_bar_width = 16.;
+            ScrollArea::vertical()
+                .auto_shrink([false; 2])
+                .show(ui, |ui| {
+                    self.render_plot(ui, "CPU (% of one core)", Some(ResourceKind::Cpu), |s| {
+                        s.cpu_usage as f64
+                    });
+                    self.render_plot(ui, "Memory (MiB)", Some(ResourceKind::Memory), |s| {
+                        s.memory_bytes as f64 / (1024. * 1024.)
+                    });
+                    self.render_plot(ui, "Threads", None, |s| s.thread_count as f64);
+                    if self
+                        .resource_history
+                        .last_sample()
+                        .is_some_and(|s| s.handle_count.is_some())
+                    {
+                        self.render_plot(ui, handle_count_name(), None, |s| {
+                            s.handle_count.unwrap_or_default() as f64
+                        });
+                    }
+                    self.render_plot(ui, "Disk I/O (MiB/s)", Some(ResourceKind::DiskIo), |s| {
+                        s.disk_io_bytes() as f64 / (1024. * 1024.)
+                    });
+
+                    ui.add_space(10.);
+
+                    self.render_spikes(ui);
+                });
+        });
+    }
+
+    fn seconds_since_start(&self, time: SystemTime) -> f64 {
+        time.duration_since(self.start_time)
+            .unwrap_or_default()
+            .as_secs_f64()
+    }
+
+    // Spikes of the given kind are drawn as vertical lines
+    fn render_plot(
+        &self,
+        ui: &mut Ui,
+        name: &str,
+        spike_kind: Option<ResourceKind>,
+        value: impl Fn(&ResourceSample) -> f64,
+    ) {
+        ui.label(RichText::new(name).font(FontId::proportional(20.0)));
+
+        let points: PlotPoints = self
+            .resource_history
+            .samples()
+            .iter()
+            .map(|sample| [self.seconds_since_start(sample.time), value(sample)])
+            .collect();
+
+        Plot::new(name)
+            .height(PLOT_HEIGHT)
+            .include_y(0.)
+            .allow_scroll(false)
+            .link_axis(PLOTS_LINK_ID, true, false)
+            .link_cursor(PLOTS_LINK_ID, true, false)
+            .x_axis_formatter(|x, _| format_duration_short(x))
+            .label_formatter(|_, point| {
+                format!("{}\n{:.1}", format_duration_short(point.x), point.y)
+            })
+            .show(ui, |plot_ui| {
+                plot_ui.line(Line::new(points).name(name));
+
+                for spike in self
+                    .resource_history
+                    .spikes()
+                    .iter()
+                    .filter(|spike| Some(spike.kind) == spike_kind)
+                {
+                    plot_ui.vline(
+                        VLine::new(self.seconds_since_start(spike.time))
+                            .color(Color32::from_rgb(255, 100, 100))
+                            .name(format_spike(spike)),
+                    );
+                }
+            });
+
+        ui.add_space(6.);
+    }
+
+    fn render_spikes(&self, ui: &mut Ui) {
+        ui.label(RichText::new("Spikes").font(FontId::proportional(20.0)));
+
+        if self.resource_history.spikes().is_empty() {
+            ui.label("No spike so far.");
+            return;
+        }
+
+        for spike in self.resource_history.spikes().iter().rev() {
+            ui.label(format!(
+                "{}  {}",
+                format_duration_short(self.seconds_since_start(spike.time)),
+                format_spike(spike)
+            ));
+        }
+    }
+
+    fn update_resource_sample_receiver(&mut self) {
+        while let Ok(sample) = self.resource_sample_receiver.try_recv() {
+            self.resource_history.push(sample);
+        }
+    }
+}
+
+pub fn format_spike(spike: &ResourceSpike) -> String {
+    match spike.kind {
+        ResourceKind::Cpu => format!("CPU spike: {:.0}%", spike.value),
+        ResourceKind::Memory => format!("Memory spike: +{}", format_bytes(spike.value)),
+        ResourceKind::DiskIo => format!("Disk I/O spike: {}/s", format_bytes(spike.value)),
+    }
+}
+
+const fn handle_count_name() -> &'static str {
+    if cfg!(windows) {
+        "Handles"
+    } else {
+        "File Descriptors"
+    }
+}
+
+impl Tab for ResourcesTab {
+    fn name(&self) -> &str {
+        "Resources"
+    }
+
+    fn update_top_panel(
+        &mut self,
+        _data: &AppLaunchConfig,
+        _gui_config: &mut Config,
+        ui: &mut eframe::egui::Ui,
+    ) {
+        egui::menu::bar(ui, |ui| {
+            ui.with_layout(Layout::left_to_right(Align::Center), |ui| {
+                let Some(sample) = self.resource_history.last_sample() else {
+                    ui.label(
+                        RichText::new("Waiting for the game...").font(FontId::proportional(20.0)),
+                    );
+                    return;
+                };
+
+                let mut usage_text = format!(
+                    "CPU {:.0}%   Memory {}   Threads {}",
+                    sample.cpu_usage,
+                    format_bytes(sample.memory_bytes as f64),
+                    sample.thread_count
+                );
+                if let Some(handle_count) = sample.handle_count {
+                    usage_text += &format!("   {} {handle_count}", handle_count_name());
+                }
+                ui.label(RichText::new(usage_text).font(FontId::proportional(20.0)));
+
+                if let Some(memory_leak_warning) = self.resource_history.memory_leak_warning() {
+                    ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                        ui.label(
+                            RichText::new(format!(
+                                "⚠ Memory grew by {} over the last {}, a mod may be leaking memory",
+                                format_bytes(memory_leak_warning.growth_bytes as f64),
+                                format_duration_short(memory_leak_warning.duration.as_secs_f64())
+                            ))
+                            .font(FontId::proportional(16.0))
+                            .color(Color32::YELLOW),
+                        );
+                    });
+                }
+            });
+        });
+    }
+
+    fn update(
+        &mut self,
+        _data: &AppLaunchConfig,
+        _gui_config: &mut Config,
+        ctx: &eframe::egui::Context,
+        _frame: &mut eframe::Frame,
+    ) {
+        self.update_resource_sample_receiver();
+
+        self.render(ctx);
+    }
+}
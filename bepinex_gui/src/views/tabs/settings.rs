@@ -1,77 +1,590 @@
-use std::sync::atomic::Ordering;
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
 
-use eframe::egui::{CentralPanel, Context};
+use eframe::{
+    egui::{
+        Button, CentralPanel, ComboBox, Context, Key, Layout, RichText, ScrollArea, Slider,
+        TextEdit, Ui, WidgetText,
+    },
+    emath::{Align, Vec2},
+    epaint::{Color32, FontId},
+};
+use strum::IntoEnumIterator;
 
 use crate::{
-    config::{launch::AppLaunchConfig, Config},
-    views::components,
+    config::{
+        alert_rules::AlertRule,
+        keymap::{KeyBinding, KeyCommand},
+        launch::AppLaunchConfig,
+        profiles::DEFAULT_PROFILE_NAME,
+        Config,
+    },
+    data::bepinex_log::LogLevel,
+    views::{
+        actions::{self, Action},
+        cfg_entry::{self, EditedCfgFile, EntryEdit, KeyBindingListener, SaveMode, SearchField},
+        components,
+    },
 };
 
 use super::Tab;
 
-pub struct SettingsTab {}
+const GUI_CFG_FILE_INDEX: usize = 0;
+const BEPINEX_CFG_FILE_INDEX: usize = 1;
+
+// The BepInEx.cfg settings deciding which logs get written where
+const BEPINEX_LOGGING_ENTRIES: [(&str, &str); 3] = [
+    ("Logging.Console", "LogLevels"),
+    ("Logging.Disk", "LogLevels"),
+    ("Logging", "UnityLogListening"),
+];
+
+const DISK_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+const CONFIRM_KILL_GAME_LABEL: &str = "Ask before closing the game";
+const GAME_SHUTDOWN_TIMEOUT_LABEL: &str = "Seconds the game gets for quitting before being killed";
+const MAX_GAME_SHUTDOWN_TIMEOUT_SECS: u32 = 120;
+
+const LOG_ALERTS_LABEL: &str = "Log Alerts";
+
+pub struct SettingsTab {
+    new_profile_name: String,
+    search_field: SearchField,
+    // Indexed by GUI_CFG_FILE_INDEX and BEPINEX_CFG_FILE_INDEX
+    cfg_file_full_paths: Vec<PathBuf>,
+    // Same indices, None while the file is missing
+    cfg_files: Vec<Option<EditedCfgFile>>,
+    key_binding_listener: KeyBindingListener,
+    last_disk_check: Instant,
+    // Waiting for a key press to rebind that command
+    listening_key_command: Option<KeyCommand>,
+    // Indexed like the alert rules, the pattern each compile error was computed from
+    alert_rule_pattern_errors: Vec<(String, Option<String>)>,
+}
 
 impl SettingsTab {
-    pub const fn new() -> Self {
-        Self {}
+    pub fn new(bepinex_gui_cfg_full_path: PathBuf, bepinex_cfg_full_path: PathBuf) -> Self {
+        let cfg_file_full_paths = vec![bepinex_gui_cfg_full_path, bepinex_cfg_full_path];
+
+        Self {
+            new_profile_name: String::new(),
+            search_field: SearchField::default(),
+            cfg_files: cfg_file_full_paths
+                .iter()
+                .map(|full_path| EditedCfgFile::read(full_path.clone()).ok())
+                .collect(),
+            cfg_file_full_paths,
+            key_binding_listener: KeyBindingListener::default(),
+            last_disk_check: Instant::now(),
+            listening_key_command: None,
+            alert_rule_pattern_errors: Vec::new(),
+        }
     }
 
-    fn render(&mut self, gui_config: &mut Config, ctx: &Context) {
+    fn render_profiles(&mut self, data: &AppLaunchConfig, gui_config: &mut Config, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("Profile: ").font(FontId::proportional(20.0)));
+
+            let scope_key = gui_config.profile_scope_key();
+            let profile_names = gui_config.profiles.profile_names(&scope_key);
+            let selected_profile_name = gui_config
+                .profiles
+                .selected_profile_name(&scope_key)
+                .to_string();
+
+            let mut profile_to_switch_to = None;
+            ComboBox::from_id_source("combo_box_settings_profile")
+                .width(200.)
+                .selected_text(&selected_profile_name)
+                .show_ui(ui, |ui| {
+                    for profile_name in &profile_names {
+                        if ui
+                            .selectable_label(*profile_name == selected_profile_name, profile_name)
+                            .clicked()
+                        {
+                            profile_to_switch_to = Some(profile_name.clone());
+                        }
+                    }
+                });
+
+            if let Some(profile_name) = profile_to_switch_to {
+                gui_config.switch_profile(&profile_name);
+            }
+
+            if ui
+                .add_enabled(
+                    selected_profile_name != DEFAULT_PROFILE_NAME,
+                    Button::new("Delete"),
+                )
+                .clicked()
+            {
+                gui_config.remove_selected_profile();
+            }
+
+            ui.add(
+                TextEdit::singleline(&mut self.new_profile_name)
+                    .desired_width(200.)
+                    .hint_text("New profile name"),
+            );
+
+            let new_profile_name = self.new_profile_name.trim();
+            if ui
+                .add_enabled(
+                    !new_profile_name.is_empty()
+                        && !profile_names.iter().any(|name| name == new_profile_name),
+                    Button::new("Create"),
+                )
+                .on_hover_text("Create a profile from the current settings")
+                .clicked()
+            {
+                gui_config.create_profile(new_profile_name);
+                self.new_profile_name.clear();
+            }
+        });
+
+        if let Some(modpack_name) = data.modpack_name() {
+            let mut is_per_modpack = gui_config.profiles.is_per_modpack(data.target_name());
+
+            if ui
+                .checkbox(
+                    &mut is_per_modpack,
+                    format!("Separate profiles for each modpack (current: {modpack_name})"),
+                )
+                .changed()
+            {
+                gui_config.set_profiles_per_modpack(is_per_modpack);
+            }
+        }
+    }
+
+    // Both files are written by BepInEx itself, so they can be missing until the game got launched once
+    fn reload_cfg_files_changed_on_disk(&mut self) {
+        if self.last_disk_check.elapsed() < DISK_CHECK_INTERVAL {
+            return;
+        }
+        self.last_disk_check = Instant::now();
+
+        for (cfg_file, full_path) in self.cfg_files.iter_mut().zip(&self.cfg_file_full_paths) {
+            let is_outdated = match cfg_file {
+                Some(cfg_file) => {
+                    !cfg_file.is_dirty
+                        && cfg_file
+                            .cfg_file
+                            .has_changed_on_disk(full_path)
+                            .unwrap_or(false)
+                }
+                None => true,
+            };
+
+            if is_outdated {
+                *cfg_file = EditedCfgFile::read(full_path.clone()).ok();
+            }
+        }
+    }
+
+    fn render(&mut self, gui_config: &mut Config, ctx: &Context) -> Vec<EntryEdit> {
+        let mut edits = Vec::new();
+
         CentralPanel::default().show(ctx, |ui| {
-            let mut button_size = ui.available_size() / 3.;
-            button_size.x = ui.available_width();
+            ScrollArea::vertical()
+                .auto_shrink([false; 2])
+                .show(ui, |ui| {
+                    self.render_gui_cfg_file(ui, &mut edits);
+
+                    self.render_bepinex_logging_entries(ui, &mut edits);
+
+                    self.render_keymap(gui_config, ui);
+
+                    self.render_game_shutdown(gui_config, ui);
 
-            render_close_window_when_game_loaded_checkbox(ui, button_size, gui_config);
+                    self.render_alert_rules(gui_config, ui);
 
-            render_close_window_when_game_closes_checkbox(gui_config, ui, button_size);
+                    if self.search_field.text_lowercase().is_empty() {
+                        ui.label(RichText::new("Appearance").font(FontId::proportional(22.0)));
+                        ui.separator();
 
-            render_switch_theme_button(gui_config, ui, button_size);
+                        let button_size = Vec2::new(ui.available_width(), 40.);
+                        render_switch_theme_button(gui_config, ui, button_size);
+                    }
+                });
         });
+
+        edits
     }
-}
 
-fn render_close_window_when_game_loaded_checkbox(
-    ui: &mut eframe::egui::Ui,
-    button_size: eframe::epaint::Vec2,
-    gui_config: &mut Config,
-) {
-    if components::checkbox(
-        &mut gui_config.close_window_when_game_loaded,
-        "Close this window when the game is loaded",
-        ui,
-        button_size,
-        20.,
-    ) {
-        _ = gui_config.save_bepinex_toml_cfg_file();
+    // Every entry the GUI loader binds, new ones show up without changes here
+    fn render_gui_cfg_file(&mut self, ui: &mut Ui, edits: &mut Vec<EntryEdit>) {
+        ui.label(RichText::new("BepInEx GUI").font(FontId::proportional(22.0)));
+        ui.separator();
+
+        let Some(edited_cfg_file) = &self.cfg_files[GUI_CFG_FILE_INDEX] else {
+            render_missing_cfg_file_label(ui, &self.cfg_file_full_paths[GUI_CFG_FILE_INDEX]);
+            return;
+        };
+        let cfg_file = &edited_cfg_file.cfg_file;
+
+        for section in cfg_file.sections() {
+            for entry in section.entries().iter().filter(|entry| {
+                cfg_entry::matches_search(self.search_field.text_lowercase(), entry)
+            }) {
+                edits.extend(cfg_entry::render(
+                    ui,
+                    GUI_CFG_FILE_INDEX,
+                    section.name(),
+                    entry,
+                    &mut self.key_binding_listener,
+                ));
+            }
+        }
+
+        ui.add_space(16.);
     }
-}
 
-fn render_close_window_when_game_closes_checkbox(
-    gui_config: &mut Config,
-    ui: &mut eframe::egui::Ui,
-    button_size: eframe::epaint::Vec2,
-) {
-    let close_window_when_game_closes = &mut gui_config
-        .close_window_when_game_closes
-        .load(Ordering::Relaxed);
+    fn render_bepinex_logging_entries(&mut self, ui: &mut Ui, edits: &mut Vec<EntryEdit>) {
+        ui.label(RichText::new("BepInEx Logging").font(FontId::proportional(22.0)));
+        ui.separator();
 
-    if components::checkbox(
-        close_window_when_game_closes,
-        "Close this window when the game closes",
-        ui,
-        button_size,
-        20.,
-    ) {
-        gui_config
-            .close_window_when_game_closes
-            .store(*close_window_when_game_closes, Ordering::Relaxed);
+        let Some(edited_cfg_file) = &self.cfg_files[BEPINEX_CFG_FILE_INDEX] else {
+            render_missing_cfg_file_label(ui, &self.cfg_file_full_paths[BEPINEX_CFG_FILE_INDEX]);
+            return;
+        };
+        let cfg_file = &edited_cfg_file.cfg_file;
+
+        // older BepInEx versions don't have all of them
+        for (section_name, key) in BEPINEX_LOGGING_ENTRIES {
+            if let Some(entry) = cfg_file.entry(section_name, key).filter(|entry| {
+                cfg_entry::matches_search(self.search_field.text_lowercase(), entry)
+            }) {
+                edits.extend(cfg_entry::render(
+                    ui,
+                    BEPINEX_CFG_FILE_INDEX,
+                    section_name,
+                    entry,
+                    &mut self.key_binding_listener,
+                ));
+            }
+        }
+
+        ui.label(
+            RichText::new(
+                "Changes to the BepInEx settings apply the next time the game is launched.",
+            )
+            .small()
+            .color(ui.style().visuals.weak_text_color()),
+        );
+
+        ui.add_space(16.);
+    }
+
+    fn render_keymap(&mut self, gui_config: &mut Config, ui: &mut Ui) {
+        let key_commands: Vec<KeyCommand> = KeyCommand::iter()
+            .filter(|key_command| {
+                key_command
+                    .to_string()
+                    .to_lowercase()
+                    .contains(self.search_field.text_lowercase())
+            })
+            .collect();
+
+        if key_commands.is_empty() {
+            return;
+        }
+
+        ui.label(RichText::new("Keyboard Shortcuts").font(FontId::proportional(22.0)));
+        ui.separator();
+
+        let keymap = &mut gui_config.keymap;
+
+        for key_command in key_commands {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(key_command.to_string()).strong());
+
+                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                    if ui
+                        .add_enabled(!keymap.is_default(key_command), Button::new("⟲"))
+                        .on_hover_text("Reset to default")
+                        .clicked()
+                    {
+                        keymap.reset(key_command);
+                    }
+
+                    if ui
+                        .button("✖")
+                        .on_hover_text("Remove the shortcut")
+                        .clicked()
+                    {
+                        keymap.set_key_binding(key_command, None);
+                    }
+
+                    let is_listening = self.listening_key_command == Some(key_command);
+
+                    let text = if is_listening {
+                        WidgetText::from(
+                            RichText::new("Press a key... (Escape to cancel)")
+                                .color(Color32::YELLOW),
+                        )
+                    } else {
+                        WidgetText::from(keymap.key_binding(key_command).map_or_else(
+                            || "None".to_string(),
+                            |key_binding| key_binding.to_string(),
+                        ))
+                    };
+
+                    if ui.button(text).clicked() {
+                        self.listening_key_command = if is_listening {
+                            None
+                        } else {
+                            Some(key_command)
+                        };
+                    }
+                });
+            });
 
-        _ = gui_config.save_bepinex_toml_cfg_file();
+            if let Some(conflict) = keymap.conflict(key_command) {
+                ui.label(RichText::new(format!("Conflicts with: {conflict}")).color(Color32::RED));
+            }
+
+            ui.add_space(4.);
+        }
+
+        ui.add_space(16.);
+    }
+
+    fn render_game_shutdown(&self, gui_config: &mut Config, ui: &mut Ui) {
+        if ![CONFIRM_KILL_GAME_LABEL, GAME_SHUTDOWN_TIMEOUT_LABEL]
+            .iter()
+            .any(|label| {
+                label
+                    .to_lowercase()
+                    .contains(self.search_field.text_lowercase())
+            })
+        {
+            return;
+        }
+
+        ui.label(RichText::new("Game Shutdown").font(FontId::proportional(22.0)));
+        ui.separator();
+
+        ui.checkbox(&mut gui_config.confirm_kill_game, CONFIRM_KILL_GAME_LABEL);
+
+        ui.horizontal(|ui| {
+            ui.add(Slider::new(
+                &mut gui_config.game_shutdown_timeout_secs,
+                0..=MAX_GAME_SHUTDOWN_TIMEOUT_SECS,
+            ));
+            ui.label(GAME_SHUTDOWN_TIMEOUT_LABEL);
+        });
+
+        ui.label(
+            RichText::new(
+                "The game is asked to quit first so that it doesn't get killed while saving.",
+            )
+            .small()
+            .color(ui.style().visuals.weak_text_color()),
+        );
+
+        ui.add_space(16.);
+    }
+
+    // Compiling the regexes every frame is slow, so only the changed patterns are compiled again
+    fn update_alert_rule_pattern_errors(&mut self, alert_rules: &[AlertRule]) {
+        self.alert_rule_pattern_errors.truncate(alert_rules.len());
+
+        for (i, rule) in alert_rules.iter().enumerate() {
+            let is_up_to_date = match self.alert_rule_pattern_errors.get(i) {
+                Some((pattern, _)) => *pattern == rule.pattern,
+                None => false,
+            };
+            if is_up_to_date {
+                continue;
+            }
+
+            let pattern_error = (
+                rule.pattern.clone(),
+                rule.compile_pattern().err().map(|err| err.to_string()),
+            );
+
+            if i < self.alert_rule_pattern_errors.len() {
+                self.alert_rule_pattern_errors[i] = pattern_error;
+            } else {
+                self.alert_rule_pattern_errors.push(pattern_error);
+            }
+        }
+    }
+
+    fn render_alert_rules(&mut self, gui_config: &mut Config, ui: &mut Ui) {
+        self.update_alert_rule_pattern_errors(&gui_config.alert_rules);
+
+        let matches_search = |rule: &AlertRule| {
+            self.search_field.text_lowercase().is_empty()
+                || rule
+                    .name
+                    .to_lowercase()
+                    .contains(self.search_field.text_lowercase())
+                || rule
+                    .pattern
+                    .to_lowercase()
+                    .contains(self.search_field.text_lowercase())
+        };
+
+        let is_section_searched = LOG_ALERTS_LABEL
+            .to_lowercase()
+            .contains(self.search_field.text_lowercase());
+        if !is_section_searched && !gui_config.alert_rules.iter().any(matches_search) {
+            return;
+        }
+
+        ui.label(RichText::new(LOG_ALERTS_LABEL).font(FontId::proportional(22.0)));
+        ui.separator();
+
+        ui.label(
+            RichText::new(
+                "A log matching every non empty field of a rule triggers its actions, even when the GUI is not focused.",
+            )
+            .small()
+            .color(ui.style().visuals.weak_text_color()),
+        );
+
+        ui.add_space(4.);
+
+        let mut rule_index_to_remove = None;
+
+        for (i, rule) in gui_config.alert_rules.iter_mut().enumerate() {
+            if !is_section_searched && !matches_search(rule) {
+                continue;
+            }
+
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut rule.is_enabled, "");
+                ui.add(TextEdit::singleline(&mut rule.name).desired_width(200.));
+
+                ComboBox::from_id_source(("combo_box_alert_rule_level", i))
+                    .selected_text(format!("{} or worse", rule.max_level))
+                    .show_ui(ui, |ui| {
+                        for log_level in LogLevel::iter().filter(|log_level| {
+                            !matches!(log_level, LogLevel::None | LogLevel::All)
+                        }) {
+                            ui.selectable_value(
+                                &mut rule.max_level,
+                                log_level,
+                                log_level.to_string(),
+                            );
+                        }
+                    });
+
+                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                    if ui.button("✖").on_hover_text("Remove the rule").clicked() {
+                        rule_index_to_remove = Some(i);
+                    }
+                });
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Source");
+                ui.add(
+                    TextEdit::singleline(&mut rule.source)
+                        .hint_text("Any")
+                        .desired_width(150.),
+                );
+
+                ui.label("Pattern");
+                ui.add(
+                    TextEdit::singleline(&mut rule.pattern)
+                        .hint_text("Any log, regex")
+                        .desired_width(ui.available_width()),
+                );
+            });
+
+            if let Some((_, Some(err))) = self.alert_rule_pattern_errors.get(i) {
+                ui.label(RichText::new(format!("Invalid pattern: {err}")).color(Color32::RED));
+            }
+
+            ui.horizontal_wrapped(|ui| {
+                let actions = &mut rule.actions;
+                ui.checkbox(&mut actions.desktop_notification, "Desktop notification");
+                ui.checkbox(&mut actions.flash_window, "Flash the window");
+                ui.checkbox(&mut actions.sound, "Play a sound");
+                ui.checkbox(&mut actions.scroll_to_log, "Scroll to the log");
+                ui.checkbox(&mut actions.pause_game, "Pause the game");
+            });
+
+            ui.add_space(8.);
+        }
+
+        if let Some(i) = rule_index_to_remove {
+            gui_config.alert_rules.remove(i);
+        }
+
+        if ui.button("Add Rule").clicked() {
+            gui_config.alert_rules.push(AlertRule::default());
+        }
+
+        ui.add_space(16.);
+    }
+
+    // Runs before the keymap so that the pressed key doesn't also trigger the command it was bound to
+    fn update_key_command_listener(&mut self, gui_config: &mut Config, ctx: &Context) {
+        let Some(key_command) = self.listening_key_command else {
+            return;
+        };
+
+        let Some((key, modifiers)) = cfg_entry::pressed_key(ctx) else {
+            return;
+        };
+
+        ctx.input_mut(|i| i.consume_key(modifiers, key));
+        self.listening_key_command = None;
+
+        if key == Key::Escape && modifiers.is_none() {
+            return;
+        }
+
+        gui_config.keymap.set_key_binding(
+            key_command,
+            Some(KeyBinding::from_modifiers(key, modifiers)),
+        );
+    }
+
+    fn apply_edits(&mut self, edits: Vec<EntryEdit>) {
+        for edit in edits {
+            if let Some(cfg_file) = &mut self.cfg_files[edit.entry_id.cfg_file_index] {
+                cfg_file.apply_edit(&edit);
+            }
+        }
+    }
+
+    fn save_dirty_cfg_files(&mut self, gui_config: &mut Config, ctx: &Context) {
+        if !cfg_entry::can_save(ctx) {
+            return;
+        }
+
+        for (cfg_file_index, cfg_file) in self.cfg_files.iter_mut().enumerate() {
+            let Some(cfg_file) = cfg_file else {
+                continue;
+            };
+
+            // keeps the values the GUI acts on in sync with what was just written
+            if cfg_file.save_if_dirty(ctx, &SaveMode::MergeChangesOnDisk)
+                && cfg_file_index == GUI_CFG_FILE_INDEX
+            {
+                _ = gui_config.read_bepinex_toml_cfg_file();
+            }
+        }
     }
 }
 
+fn render_missing_cfg_file_label(ui: &mut Ui, cfg_file_full_path: &Path) {
+    ui.label(format!(
+        "{} was not found, BepInEx creates it the first time the game is launched.",
+        cfg_entry::file_name(cfg_file_full_path)
+    ));
+    ui.add_space(16.);
+}
+
 fn render_switch_theme_button(
-    gui_config: &mut Config,
+    gui_config: &Config,
     ui: &mut eframe::egui::Ui,
     button_size: eframe::epaint::Vec2,
 ) {
@@ -88,8 +601,7 @@ fn render_switch_theme_button(
         20.,
         Some(ui.style().visuals.widgets.noninteractive.bg_fill),
     ) {
-        gui_config.dark_mode = !gui_config.dark_mode;
-        gui_config.theme_just_changed = true;
+        actions::dispatch(ui.ctx(), Action::SwitchTheme);
     }
 }
 
@@ -100,10 +612,19 @@ impl Tab for SettingsTab {
 
     fn update_top_panel(
         &mut self,
-        _data: &AppLaunchConfig,
-        _gui_config: &mut Config,
-        _ui: &mut eframe::egui::Ui,
+        data: &AppLaunchConfig,
+        gui_config: &mut Config,
+        ui: &mut eframe::egui::Ui,
     ) {
+        self.render_profiles(data, gui_config, ui);
+
+        ui.horizontal(|ui| {
+            self.search_field.show(ui, |search_text| {
+                TextEdit::singleline(search_text)
+                    .desired_width(300.)
+                    .hint_text("Setting name or description")
+            });
+        });
     }
 
     fn update(
@@ -113,6 +634,26 @@ impl Tab for SettingsTab {
         ctx: &eframe::egui::Context,
         _frame: &mut eframe::Frame,
     ) {
-        self.render(gui_config, ctx);
+        self.reload_cfg_files_changed_on_disk();
+        self.update_key_command_listener(gui_config, ctx);
+
+        let mut edits = Vec::new();
+        edits.extend(self.key_binding_listener.update(ctx));
+        edits.extend(self.render(gui_config, ctx));
+
+        self.apply_edits(edits);
+        self.save_dirty_cfg_files(gui_config, ctx);
+    }
+
+    fn handle_action(
+        &mut self,
+        _data: &AppLaunchConfig,
+        _gui_config: &mut Config,
+        _ctx: &eframe::egui::Context,
+        action: Action,
+    ) {
+        if action == Action::FocusSearch {
+            self.search_field.focus();
+        }
     }
 }
@@ -0,0 +1,242 @@
+use std::time::{Duration, SystemTime};
+
+use crossbeam_channel::Receiver;
+use eframe::{
+    egui::{self, CentralPanel, Context, Label, Layout, RichText, ScrollArea, Sense, Ui},
+    emath::{Align, Rect, Vec2},
+    epaint::{Color32, FontId, Rounding},
+};
+
+use crate::{
+    backend::{file_explorer_utils, process},
+    config::{launch::AppLaunchConfig, Config},
+    data::{bepinex_log::chainloader::TimedChainloaderEvent, startup_profile::StartupProfile},
+    views::{self, actions::Action},
+};
+
+use super::Tab;
+
+const SLOWEST_PLUGIN_COUNT: usize = 10;
+const CHROME_TRACE_FILE_NAME: &str = "startup_trace.json";
+
+pub struct TimelineTab {
+    mod_receiver: Receiver<TimedChainloaderEvent>,
+    startup_profile: StartupProfile,
+}
+
+impl TimelineTab {
+    pub const fn new(
+        mod_receiver: Receiver<TimedChainloaderEvent>,
+        process_start_time: Option<SystemTime>,
+    ) -> Self {
+        Self {
+            mod_receiver,
+            startup_profile: StartupProfile::new(process_start_time),
+        }
+    }
+
+    fn render(&mut self, ctx: &Context) {
+        CentralPanel::default().show(ctx, |ui| {
+            if self.startup_profile.plugin_load_spans().is_empty() {
+                ui.vertical_centered_justified(|ui| {
+                    let loading_text = "Loading ⌛";
+                    let text_size =
+                        views::utils::egui::compute_text_size(ui, loading_text, true, false, None);
+                    ui.add_space(ui.available_height() / 2. - text_size.y);
+                    ui.heading(loading_text);
+                });
+            } else {
+                ui.spacing_mut().scroll_bar_width = 16.;
+                ScrollArea::vertical()
+                    .auto_shrink([false; 2])
+                    .show(ui, |ui| {
+                        self.render_slowest_plugins(ui);
+
+                        ui.add_space(10.);
+
+                        self.render_plugin_load_spans(ui);
+                    });
+            }
+        });
+    }
+
+    fn render_slowest_plugins(&self, ui: &mut Ui) {
+        ui.label(RichText::new("Slowest Plugins").font(FontId::proportional(20.0)));
+
+        for span in self.startup_profile.slowest_plugins(SLOWEST_PLUGIN_COUNT) {
+            ui.label(format!(
+                "{}  {}",
+                format_duration(span.duration().unwrap_or_default()),
+                span.mod_.to_string()
+            ));
+        }
+    }
+
+    fn render_plugin_load_spans(&self, ui: &mut Ui) {
+        ui.label(RichText::new("Timeline").font(FontId::proportional(20.0)));
+
+        let Some(startup_start_time) = self.startup_profile.startup_start_time() else {
+            return;
+        };
+        let total_duration = self
+            .startup_profile
+            .startup_duration()
+            .unwrap_or_default()
+            .as_secs_f32()
+            .max(f32::EPSILON);
+
+        const NAME_WIDTH: f32 = 250.;
+        const ROW_HEIGHT: f32 = 16.;
+
+        let bar_color = ui.style().visuals.selection.bg_fill;
+        let background_color = ui.style().visuals.extreme_bg_color;
+
+        for span in self.startup_profile.plugin_load_spans() {
+            ui.horizontal(|ui| {
+                ui.add_sized(
+                    Vec2::new(NAME_WIDTH, ROW_HEIGHT),
+                    Label::new(span.mod_.to_string()).wrap(false),
+                );
+
+                let (rect, response) = ui.allocate_exact_size(
+                    Vec2::new(ui.available_width(), ROW_HEIGHT),
+                    Sense::hover(),
+                );
+
+                let seconds_since_startup = |time: SystemTime| {
+                    time.duration_since(startup_start_time)
+                        .unwrap_or_default()
+                        .as_secs_f32()
+                };
+                let start = seconds_since_startup(span.start);
+                let end = seconds_since_startup(span.end.unwrap_or_else(SystemTime::now));
+
+                let x_from_seconds = |seconds: f32| {
+                    rect.min.x + rect.width() * (seconds / total_duration).clamp(0., 1.)
+                };
+
+                // make sure very fast plugins are still visible
+                let bar_min_x = x_from_seconds(start);
+                let bar_max_x = x_from_seconds(end).max(bar_min_x + 1.);
+
+                let painter = ui.painter();
+                painter.rect_filled(rect, Rounding::none(), background_color);
+                painter.rect_filled(
+                    Rect::from_x_y_ranges(bar_min_x..=bar_max_x, rect.y_range()),
+                    Rounding::none(),
+                    if span.end.is_some() {
+                        bar_color
+                    } else {
+                        Color32::YELLOW
+                    },
+                );
+
+                response.on_hover_text(format!(
+                    "{}\nStarted at {}\nTook {}",
+                    span.mod_.to_string(),
+                    format_duration(Duration::from_secs_f32(start)),
+                    span.duration()
+                        .map_or_else(|| "Still loading".to_string(), format_duration)
+                ));
+            });
+        }
+    }
+
+    fn export_chrome_trace(&self, data: &AppLaunchConfig) {
+        let Some(bepinex_folder_full_path) = data.bepinex_log_output_file_full_path().parent()
+        else {
+            return;
+        };
+
+        let trace_file_full_path = bepinex_folder_full_path.join(CHROME_TRACE_FILE_NAME);
+
+        match self
+            .startup_profile
+            .export_chrome_trace(&trace_file_full_path)
+        {
+            Ok(_) => {
+                file_explorer_utils::highlight_path_in_explorer(&trace_file_full_path);
+            }
+            Err(err) => {
+                tracing::error!("Failed exporting startup chrome trace: {}", err);
+            }
+        }
+    }
+
+    fn update_mod_receiver(&mut self) {
+        while let Ok(timed_chainloader_event) = self.mod_receiver.try_recv() {
+            self.startup_profile
+                .on_chainloader_event(&timed_chainloader_event);
+        }
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.2}s", duration.as_secs_f32())
+}
+
+impl Tab for TimelineTab {
+    fn name(&self) -> &str {
+        "Timeline"
+    }
+
+    fn update_top_panel(
+        &mut self,
+        data: &AppLaunchConfig,
+        _gui_config: &mut Config,
+        ui: &mut eframe::egui::Ui,
+    ) {
+        egui::menu::bar(ui, |ui| {
+            ui.with_layout(Layout::left_to_right(Align::default()), |ui| {
+                let startup_duration = self
+                    .startup_profile
+                    .startup_duration()
+                    .map_or_else(|| "-".to_string(), format_duration);
+
+                let startup_text = if self.startup_profile.is_startup_complete() {
+                    format!("Startup took {startup_duration}")
+                } else {
+                    format!("Starting up for {startup_duration}")
+                };
+                ui.label(RichText::new(startup_text).font(FontId::proportional(20.0)));
+
+                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                    if ui
+                        .button(
+                            RichText::new("Export Chrome Trace").font(FontId::proportional(20.0)),
+                        )
+                        .clicked()
+                    {
+                        self.export_chrome_trace(data);
+                    }
+                });
+            });
+        });
+    }
+
+    fn update(
+        &mut self,
+        _data: &AppLaunchConfig,
+        _gui_config: &mut Config,
+        ctx: &eframe::egui::Context,
+        _frame: &mut eframe::Frame,
+    ) {
+        self.update_mod_receiver();
+
+        self.render(ctx);
+    }
+
+    fn handle_action(
+        &mut self,
+        data: &AppLaunchConfig,
+        _gui_config: &mut Config,
+        _ctx: &eframe::egui::Context,
+        action: Action,
+    ) {
+        if action == Action::GameAttached {
+            self.update_mod_receiver();
+            self.startup_profile =
+                StartupProfile::new(process::start_time(data.target_process_id()));
+        }
+    }
+}
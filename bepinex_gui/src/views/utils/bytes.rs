@@ -0,0 +1,16 @@
+const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+pub fn format_bytes(bytes: f64) -> String {
+    let mut value = bytes;
+    let mut unit_index = 0;
+    while value >= 1024. && unit_index < UNITS.len() - 1 {
+        value /= 1024.;
+        unit_index += 1;
+    }
+
+    if unit_index == 0 {
+        format!("{value:.0} {}", UNITS[unit_index])
+    } else {
+        format!("{value:.1} {}", UNITS[unit_index])
+    }
+}
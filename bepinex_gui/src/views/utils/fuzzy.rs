@@ -0,0 +1,39 @@
+// Subsequence match, "pgm" matches "Pause / Resume Game".
+// Consecutive characters and characters starting a word score higher, None when it doesn't match.
+pub fn score(pattern_lowercase: &str, text: &str) -> Option<i32> {
+    const MATCH_SCORE: i32 = 1;
+    const CONSECUTIVE_BONUS: i32 = 4;
+    const WORD_START_BONUS: i32 = 6;
+
+    let mut pattern_chars = pattern_lowercase.chars().peekable();
+    let mut score = 0;
+    let mut previous_char_matched = false;
+    let mut previous_char = ' ';
+
+    for text_char in text.chars() {
+        let Some(pattern_char) = pattern_chars.peek() else {
+            break;
+        };
+
+        if text_char.to_lowercase().eq(pattern_char.to_lowercase()) {
+            score += MATCH_SCORE;
+
+            if previous_char_matched {
+                score += CONSECUTIVE_BONUS;
+            }
+
+            if !previous_char.is_alphanumeric() {
+                score += WORD_START_BONUS;
+            }
+
+            previous_char_matched = true;
+            pattern_chars.next();
+        } else {
+            previous_char_matched = false;
+        }
+
+        previous_char = text_char;
+    }
+
+    pattern_chars.peek().is_none().then_some(score)
+}
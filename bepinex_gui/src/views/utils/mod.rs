@@ -1 +1,4 @@
+pub mod bytes;
 pub mod egui;
+pub mod fuzzy;
+pub mod time;
@@ -0,0 +1,34 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const MINUTE: u64 = 60;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+
+pub fn format_time_ago(unix_timestamp: u64) -> String {
+    let elapsed = SystemTime::now()
+        .duration_since(UNIX_EPOCH + Duration::from_secs(unix_timestamp))
+        .unwrap_or_default()
+        .as_secs();
+
+    match elapsed {
+        x if x < HOUR => format!("{} minutes ago", x / MINUTE),
+        x if x < DAY => format!("{} hours ago", x / HOUR),
+        x => format!("{} days ago", x / DAY),
+    }
+}
+
+// 1:05 or 1:02:05
+pub fn format_duration_short(seconds: f64) -> String {
+    let seconds = seconds.max(0.) as u64;
+
+    if seconds < HOUR {
+        format!("{}:{:02}", seconds / MINUTE, seconds % MINUTE)
+    } else {
+        format!(
+            "{}:{:02}:{:02}",
+            seconds / HOUR,
+            (seconds % HOUR) / MINUTE,
+            seconds % MINUTE
+        )
+    }
+}